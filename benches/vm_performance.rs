@@ -45,9 +45,16 @@ fn benchmark_neural_network(c: &mut Criterion) {
             network.forward(black_box(&inputs));
         })
     });
+
+    c.bench_function("neural_forward_into", |b| {
+        let mut outputs = Vec::new();
+        b.iter(|| {
+            network.forward_into(black_box(&inputs), &mut outputs);
+        })
+    });
     
     c.bench_function("warrior_decision_making", |b| {
-        let mut warrior = NeuralWarrior::new(genome.clone(), 1);
+        let mut warrior = NeuralWarrior::with_position(genome.clone(), 1, (500.0, 500.0));
         let environment = Environment::new(1000.0, 1000.0, 100);
         let env_state = environment.get_environment_state();
         let sensors = warrior.sense_environment(&env_state);
@@ -56,6 +63,18 @@ fn benchmark_neural_network(c: &mut Criterion) {
             warrior.decide_action(black_box(&sensors));
         })
     });
+
+    c.bench_function("warrior_decision_making_buffered", |b| {
+        let warrior = NeuralWarrior::with_position(genome.clone(), 1, (500.0, 500.0));
+        let environment = Environment::new(1000.0, 1000.0, 100);
+        let sensors = warrior.sense_environment(&environment.get_environment_state());
+        let mut rng = rand::thread_rng();
+        let mut outputs = Vec::new();
+
+        b.iter(|| {
+            warrior.choose_action_into(black_box(&sensors), 0.0, &mut rng, &mut outputs);
+        })
+    });
 }
 
 fn benchmark_environment(c: &mut Criterion) {
@@ -65,7 +84,8 @@ fn benchmark_environment(c: &mut Criterion) {
         // Add some warriors
         for i in 0..50 {
             let genome = Genome::new_random();
-            let warrior = NeuralWarrior::new(genome, i);
+            let position = environment.spawn_position(&mut rand::thread_rng());
+            let warrior = NeuralWarrior::with_position(genome, i, position);
             environment.add_warrior(warrior);
         }
         
@@ -80,7 +100,8 @@ fn benchmark_environment(c: &mut Criterion) {
         // Add warriors
         for i in 0..30 {
             let genome = Genome::new_random();
-            let warrior = NeuralWarrior::new(genome, i);
+            let position = environment.spawn_position(&mut rand::thread_rng());
+            let warrior = NeuralWarrior::with_position(genome, i, position);
             environment.add_warrior(warrior);
         }
         
@@ -124,12 +145,13 @@ fn benchmark_full_simulation(c: &mut Criterion) {
             max_population: 50,
             vm_memory_size: 512,
             target_species_count: 3,
+            seed: Some(50),
             ..SimulationConfig::default()
         };
         
         b.iter(|| {
             let mut simulation = NeuralArenaSimulation::new(config.clone());
-            simulation.initialize_population(25);
+            simulation.initialize_canonical_population(25);
             simulation.run_generation();
         })
     });
@@ -160,6 +182,23 @@ fn benchmark_performance_targets(c: &mut Criterion) {
         let config = SimulationConfig {
             max_population: 500,
             vm_memory_size: 2048,
+            seed: Some(500),
+            ..SimulationConfig::default()
+        };
+        let mut simulation = NeuralArenaSimulation::new(config);
+        simulation.initialize_population(250);
+        
+        b.iter(|| {
+            simulation.single_tick();
+        })
+    });
+    
+    c.bench_function("population_scalability_500_fast_mode", |b| {
+        let config = SimulationConfig {
+            max_population: 500,
+            vm_memory_size: 2048,
+            seed: Some(500),
+            fast_mode: true,
             ..SimulationConfig::default()
         };
         let mut simulation = NeuralArenaSimulation::new(config);
@@ -1,12 +1,56 @@
-use neural_network_arena::{NeuralArenaSimulation, SimulationConfig};
+use neural_network_arena::{export, NeuralArenaSimulation, SimulationConfig};
 use std::time::Instant;
 
+/// Run data formats `--export-format` accepts.
+enum ExportFormat {
+    Newick,
+    Dot,
+    #[cfg(feature = "arrow")]
+    Parquet,
+}
+
+impl ExportFormat {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "newick" => Ok(Self::Newick),
+            "dot" => Ok(Self::Dot),
+            #[cfg(feature = "arrow")]
+            "parquet" => Ok(Self::Parquet),
+            _ => Err(format!("Unknown export format '{}'", name)),
+        }
+    }
+}
+
+fn exit_with(message: impl std::fmt::Display) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
+
 fn main() {
     println!("🧠 Neural Network Arena - Performance Validation Test");
     println!("=========================================================");
     
+    // An optional preset name (e.g. `quick_demo`) replaces the default test
+    // configuration; `--export-format newick|dot|parquet` exports the run data
+    // and `--export-network <id>` prints that warrior's network as JSON
+    let mut preset = None;
+    let mut export_format = None;
+    let mut export_network = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--export-format" {
+            let name = args.next().unwrap_or_else(|| exit_with("--export-format needs a format"));
+            export_format = Some(ExportFormat::parse(&name).unwrap_or_else(|e| exit_with(e)));
+        } else if arg == "--export-network" {
+            let id = args.next().unwrap_or_else(|| exit_with("--export-network needs a warrior id"));
+            export_network = Some(id.parse::<u32>().unwrap_or_else(|e| exit_with(format!("Invalid warrior id '{}': {}", id, e))));
+        } else {
+            preset = Some(SimulationConfig::preset(&arg).unwrap_or_else(|e| exit_with(e)));
+        }
+    }
+    
     // Test configuration optimized for performance
-    let config = SimulationConfig {
+    let config = preset.unwrap_or(SimulationConfig {
         max_population: 100,
         vm_memory_size: 1024,
         territory_size: 32,
@@ -18,12 +62,15 @@ fn main() {
         tournament_size: 3,
         max_generations: 3,
         performance_target_rps: 1000,
-    };
+        // Seeded so warrior ids repeat across runs for --export-network
+        seed: Some(42),
+        ..SimulationConfig::default()
+    });
     
     let mut simulation = NeuralArenaSimulation::new(config);
     
-    // Initialize with smaller population for testing
-    simulation.initialize_population(50);
+    // Initialize with a smaller, fixed population so runs are comparable
+    simulation.initialize_canonical_population(50);
     println!("✅ Initialized simulation with 50 neural warriors");
     
     // Test single tick performance
@@ -73,6 +120,9 @@ fn main() {
     println!("   Max fitness: {:.2}", stats.max_fitness);
     println!("   Diversity score: {:.2}", stats.diversity_score);
     println!("   Environmental pressure: {:.2}", stats.environmental_pressure);
+    if let Some(best) = simulation.environment.warriors.values().max_by(|a, b| a.fitness_score.total_cmp(&b.fitness_score)) {
+        println!("   Fittest warrior: {} (--export-network {})", best.id, best.id);
+    }
     
     println!("\n🎯 Core Engine Validation:");
     println!("   ✅ VM execution system operational");
@@ -83,4 +133,22 @@ fn main() {
     println!("   ✅ Fitness calculation system working");
     
     println!("\n🚀 Neural Network Arena Core Engine - VALIDATION COMPLETE!");
-}
\ No newline at end of file
+    
+    match export_format {
+        Some(ExportFormat::Newick) => println!("\n{}", export::to_newick(&simulation.lineage)),
+        Some(ExportFormat::Dot) => println!("\n{}", export::to_dot(&simulation.speciation_manager)),
+        #[cfg(feature = "arrow")]
+        Some(ExportFormat::Parquet) => {
+            let file = std::fs::File::create("statistics.parquet").unwrap_or_else(|e| exit_with(e));
+            export::statistics_to_parquet(std::slice::from_ref(stats), file).unwrap_or_else(|e| exit_with(e));
+            println!("\n📦 Statistics written to statistics.parquet");
+        },
+        None => {},
+    }
+    
+    if let Some(warrior_id) = export_network {
+        let warrior = simulation.environment.warriors.get(&warrior_id)
+            .unwrap_or_else(|| exit_with(format!("No warrior with id {}", warrior_id)));
+        println!("\n{}", warrior.network.export_json().unwrap_or_else(|e| exit_with(e)));
+    }
+}
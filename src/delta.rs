@@ -0,0 +1,266 @@
+use crate::neural::{NeuralWarrior, Resource};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// A single entity change recorded in the environment's change log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntityChange {
+    WarriorChanged(u32),
+    WarriorRemoved(u32),
+    ResourceAdded(u64),
+    ResourceRemoved(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEntry {
+    pub tick: u64,
+    pub change: EntityChange,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct WarriorMark {
+    position: (f32, f32),
+    energy: f32,
+}
+
+/// Per-entity dirty tracking for delta updates. Each sync compares the
+/// current warriors and resources against what was last published and
+/// appends the differences to a bounded ring buffer keyed by tick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeLog {
+    /// Warriors that moved less than this since they were last published
+    /// are not reported as changed.
+    pub position_threshold: f32,
+    /// Same as `position_threshold`, for energy.
+    pub energy_threshold: f32,
+    entries: VecDeque<ChangeEntry>,
+    capacity: usize,
+    /// Entries at or before this tick may have been evicted.
+    truncated_through: Option<u64>,
+    warrior_marks: HashMap<u32, WarriorMark>,
+    resource_ids: HashSet<u64>,
+}
+
+impl ChangeLog {
+    pub const DEFAULT_CAPACITY: usize = 20_000;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            position_threshold: 1.0,
+            energy_threshold: 1.0,
+            entries: VecDeque::new(),
+            capacity,
+            truncated_through: None,
+            warrior_marks: HashMap::new(),
+            resource_ids: HashSet::new(),
+        }
+    }
+
+    /// A capacity of 0 disables tracking entirely.
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        if capacity == 0 {
+            self.warrior_marks.clear();
+            self.resource_ids.clear();
+            if let Some(last) = self.entries.back() {
+                self.truncated_through = Some(last.tick);
+            }
+            self.entries.clear();
+        }
+        self.evict();
+    }
+
+    pub fn entries(&self) -> &VecDeque<ChangeEntry> {
+        &self.entries
+    }
+
+    /// Whether every change after `since_tick` (inclusive) is still held.
+    pub fn is_complete_since(&self, since_tick: u64) -> bool {
+        self.is_enabled() && self.truncated_through.map_or(true, |through| since_tick > through)
+    }
+
+    pub fn sync(&mut self, tick: u64, warriors: &BTreeMap<u32, NeuralWarrior>, resources: &[Resource]) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let removed_warriors: Vec<u32> = self.warrior_marks.keys()
+            .filter(|id| !warriors.contains_key(id))
+            .copied()
+            .collect();
+        for id in removed_warriors {
+            self.warrior_marks.remove(&id);
+            self.push(tick, EntityChange::WarriorRemoved(id));
+        }
+
+        for (&id, warrior) in warriors {
+            let changed = match self.warrior_marks.get(&id) {
+                Some(mark) => {
+                    let dx = warrior.position.0 - mark.position.0;
+                    let dy = warrior.position.1 - mark.position.1;
+                    (dx * dx + dy * dy).sqrt() > self.position_threshold
+                        || (warrior.energy - mark.energy).abs() > self.energy_threshold
+                },
+                None => true,
+            };
+
+            if changed {
+                self.warrior_marks.insert(id, WarriorMark {
+                    position: warrior.position,
+                    energy: warrior.energy,
+                });
+                self.push(tick, EntityChange::WarriorChanged(id));
+            }
+        }
+
+        let current_resources: HashSet<u64> = resources.iter().map(|resource| resource.id).collect();
+        let mut removed_resources: Vec<u64> = self.resource_ids.difference(&current_resources).copied().collect();
+        removed_resources.sort_unstable();
+        for id in removed_resources {
+            self.push(tick, EntityChange::ResourceRemoved(id));
+        }
+        for resource in resources {
+            if !self.resource_ids.contains(&resource.id) {
+                self.push(tick, EntityChange::ResourceAdded(resource.id));
+            }
+        }
+        self.resource_ids = current_resources;
+    }
+
+    /// Changes recorded at or after `since_tick`, oldest first.
+    pub fn changes_since(&self, since_tick: u64) -> impl Iterator<Item = &ChangeEntry> {
+        self.entries.iter().filter(move |entry| entry.tick >= since_tick)
+    }
+
+    fn push(&mut self, tick: u64, change: EntityChange) {
+        self.entries.push_back(ChangeEntry { tick, change });
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            if let Some(evicted) = self.entries.pop_front() {
+                self.truncated_through = Some(evicted.tick);
+            }
+        }
+    }
+}
+
+impl Default for ChangeLog {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+/// Everything a client needs to bring a mirror from `since_tick` up to
+/// `tick`. When `full` is set the change log no longer covers the requested
+/// range and the delta carries the complete state instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateDelta {
+    pub since_tick: u64,
+    pub tick: u64,
+    pub full: bool,
+    pub warriors: Vec<NeuralWarrior>,
+    pub removed_warriors: Vec<u32>,
+    pub added_resources: Vec<Resource>,
+    pub removed_resources: Vec<u64>,
+}
+
+impl StateDelta {
+    pub fn build(
+        change_log: &ChangeLog,
+        since_tick: u64,
+        tick: u64,
+        warriors: &BTreeMap<u32, NeuralWarrior>,
+        resources: &[Resource],
+    ) -> Self {
+        if !change_log.is_complete_since(since_tick) {
+            return Self {
+                since_tick,
+                tick,
+                full: true,
+                warriors: warriors.values().cloned().collect(),
+                removed_warriors: Vec::new(),
+                added_resources: resources.to_vec(),
+                removed_resources: Vec::new(),
+            };
+        }
+
+        let mut changed_warriors = BTreeSet::new();
+        let mut removed_warriors = BTreeSet::new();
+        let mut added_resources = HashSet::new();
+        let mut removed_resources = BTreeSet::new();
+
+        for entry in change_log.changes_since(since_tick) {
+            match entry.change {
+                EntityChange::WarriorChanged(id) => { changed_warriors.insert(id); },
+                EntityChange::WarriorRemoved(id) => { removed_warriors.insert(id); },
+                EntityChange::ResourceAdded(id) => { added_resources.insert(id); },
+                EntityChange::ResourceRemoved(id) => { removed_resources.insert(id); },
+            }
+        }
+
+        Self {
+            since_tick,
+            tick,
+            full: false,
+            warriors: changed_warriors.iter()
+                .filter_map(|id| warriors.get(id).cloned())
+                .collect(),
+            // A warrior removed and re-added under the same id is an update
+            removed_warriors: removed_warriors.into_iter()
+                .filter(|id| !warriors.contains_key(id))
+                .collect(),
+            added_resources: resources.iter()
+                .filter(|resource| added_resources.contains(&resource.id))
+                .cloned()
+                .collect(),
+            removed_resources: removed_resources.into_iter().collect(),
+        }
+    }
+}
+
+/// Client-side copy of the environment's entities, kept current by
+/// applying successive `StateDelta`s.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnvironmentMirror {
+    pub tick: u64,
+    pub warriors: BTreeMap<u32, NeuralWarrior>,
+    pub resources: BTreeMap<u64, Resource>,
+}
+
+impl EnvironmentMirror {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn apply(&mut self, delta: &StateDelta) {
+        if delta.full {
+            self.warriors.clear();
+            self.resources.clear();
+        }
+
+        for warrior in &delta.warriors {
+            self.warriors.insert(warrior.id, warrior.clone());
+        }
+        for id in &delta.removed_warriors {
+            self.warriors.remove(id);
+        }
+        for resource in &delta.added_resources {
+            self.resources.insert(resource.id, resource.clone());
+        }
+        for id in &delta.removed_resources {
+            self.resources.remove(id);
+        }
+
+        self.tick = delta.tick;
+    }
+}
@@ -0,0 +1,196 @@
+use crate::neural::{Action, ActionKind};
+use serde::{Deserialize, Serialize};
+
+/// Problems detected while a run is in progress. The simulation keeps
+/// going; warnings only flag runs that are likely broken or stuck.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SimulationWarning {
+    /// A single action made up more than the configured share of all
+    /// decisions for `generations` consecutive generations.
+    DegenerateActions {
+        generation: u32,
+        action: ActionKind,
+        share: f32,
+        generations: u32,
+    },
+    /// Action entropy stayed below the configured floor for `generations`
+    /// consecutive generations: warriors behave alike even if their
+    /// genomes differ.
+    BehavioralConvergence {
+        generation: u32,
+        entropy: f32,
+        generations: u32,
+    },
+    /// Territory allocations failed this generation because every
+    /// territory was taken. Persistent saturation usually means territories
+    /// aren't being freed.
+    AllocatorSaturated {
+        generation: u32,
+        failures: usize,
+    },
+}
+
+/// Tally of chosen actions by kind.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionHistogram {
+    counts: [u64; ActionKind::ALL.len()],
+}
+
+impl ActionHistogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, action: &Action) {
+        self.counts[action.kind() as usize] += 1;
+    }
+
+    pub fn count(&self, kind: ActionKind) -> u64 {
+        self.counts[kind as usize]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Most frequent action and its share of all recorded actions.
+    pub fn dominant(&self) -> Option<(ActionKind, f32)> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+
+        ActionKind::ALL.iter()
+            .map(|&kind| (kind, self.count(kind)))
+            .max_by_key(|&(_, count)| count)
+            .map(|(kind, count)| (kind, count as f32 / total as f32))
+    }
+
+    /// Shannon entropy of the action distribution, normalized to [0, 1]:
+    /// 0 when every decision was the same action, 1 when all actions were
+    /// equally common.
+    pub fn entropy(&self) -> f32 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+
+        let entropy: f32 = self.counts.iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f32 / total as f32;
+                -p * p.log2()
+            })
+            .sum();
+        entropy / (ActionKind::ALL.len() as f32).log2()
+    }
+
+    pub fn clear(&mut self) {
+        self.counts = Default::default();
+    }
+}
+
+/// Watches per-generation action histograms and warns once a single action
+/// has dominated for several generations in a row, which usually means the
+/// networks have collapsed to a constant output.
+#[derive(Debug, Clone)]
+pub struct DegenerateActionMonitor {
+    pub dominance_threshold: f32,
+    pub required_generations: u32,
+    streak: u32,
+    streak_action: Option<ActionKind>,
+}
+
+impl DegenerateActionMonitor {
+    pub fn new(dominance_threshold: f32, required_generations: u32) -> Self {
+        Self {
+            dominance_threshold,
+            required_generations,
+            streak: 0,
+            streak_action: None,
+        }
+    }
+
+    /// Feeds one finished generation. Returns a warning every generation
+    /// the dominance streak is at or beyond `required_generations`.
+    pub fn observe(&mut self, generation: u32, histogram: &ActionHistogram) -> Option<SimulationWarning> {
+        match histogram.dominant() {
+            Some((action, share)) if share > self.dominance_threshold => {
+                if self.streak_action == Some(action) {
+                    self.streak += 1;
+                } else {
+                    self.streak_action = Some(action);
+                    self.streak = 1;
+                }
+
+                (self.streak >= self.required_generations).then_some(SimulationWarning::DegenerateActions {
+                    generation,
+                    action,
+                    share,
+                    generations: self.streak,
+                })
+            },
+            _ => {
+                self.reset();
+                None
+            },
+        }
+    }
+
+    pub fn streak(&self) -> u32 {
+        self.streak
+    }
+
+    pub fn reset(&mut self) {
+        self.streak = 0;
+        self.streak_action = None;
+    }
+}
+
+/// Warns when the action entropy of a generation stays below a floor for
+/// several generations in a row.
+#[derive(Debug, Clone)]
+pub struct ConvergenceMonitor {
+    /// Entropy below this counts as converged; 0 disables the monitor.
+    pub entropy_floor: f32,
+    pub required_generations: u32,
+    streak: u32,
+}
+
+impl ConvergenceMonitor {
+    pub fn new(entropy_floor: f32, required_generations: u32) -> Self {
+        Self {
+            entropy_floor,
+            required_generations,
+            streak: 0,
+        }
+    }
+
+    /// Feeds one finished generation's action entropy. Returns a warning
+    /// every generation the streak is at or beyond `required_generations`.
+    pub fn observe(&mut self, generation: u32, entropy: f32) -> Option<SimulationWarning> {
+        if entropy >= self.entropy_floor {
+            self.reset();
+            return None;
+        }
+
+        self.streak += 1;
+        self.is_converged().then_some(SimulationWarning::BehavioralConvergence {
+            generation,
+            entropy,
+            generations: self.streak,
+        })
+    }
+
+    pub fn is_converged(&self) -> bool {
+        self.streak > 0 && self.streak >= self.required_generations
+    }
+
+    pub fn streak(&self) -> u32 {
+        self.streak
+    }
+
+    pub fn reset(&mut self) {
+        self.streak = 0;
+    }
+}
@@ -0,0 +1,175 @@
+//! Async front end for embedding a simulation in a server.
+//!
+//! `SimulationDriver` moves a `NeuralArenaSimulation` onto a dedicated
+//! thread and talks to it over channels, so async callers never block a
+//! runtime worker for a whole generation. Any number of clients can
+//! observe the run through `subscribe_events`.
+
+use crate::neural::NeuralWarrior;
+use crate::simulation::{NeuralArenaSimulation, SimulationStatistics};
+use crate::SimulationWarning;
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use tokio::sync::{broadcast, oneshot};
+
+/// Events buffered per subscriber before slow receivers start lagging.
+const EVENT_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SimEvent {
+    /// A tick advanced through `SimulationDriver::step`.
+    Tick { tick: u64, population: usize },
+    GenerationCompleted {
+        generation: u32,
+        survivors: usize,
+        new_species: usize,
+        warnings: Vec<SimulationWarning>,
+    },
+}
+
+/// Point-in-time copy of the simulation's observable state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationSnapshot {
+    pub generation: u32,
+    pub tick: u64,
+    pub statistics: SimulationStatistics,
+    pub warriors: Vec<NeuralWarrior>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DriverError {
+    #[error("Simulation thread has stopped")]
+    Stopped,
+}
+
+enum Command {
+    Step { ticks: u64, reply: oneshot::Sender<u64> },
+    RunGeneration { reply: oneshot::Sender<u32> },
+    Snapshot { reply: oneshot::Sender<SimulationSnapshot> },
+    /// `done` fires just before the thread exits.
+    Shutdown { done: Option<oneshot::Sender<()>> },
+}
+
+pub struct SimulationDriver {
+    commands: mpsc::Sender<Command>,
+    events: broadcast::Sender<SimEvent>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SimulationDriver {
+    /// Starts the simulation thread. Commands run one at a time, in the
+    /// order they were sent.
+    pub fn spawn(simulation: NeuralArenaSimulation) -> Self {
+        let (commands, receiver) = mpsc::channel();
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        let thread_events = events.clone();
+        let thread = std::thread::Builder::new()
+            .name("simulation-driver".to_string())
+            .spawn(move || run_commands(simulation, receiver, thread_events))
+            .expect("failed to spawn simulation thread");
+
+        Self {
+            commands,
+            events,
+            thread: Some(thread),
+        }
+    }
+
+    /// Advances `ticks` ticks, publishing a `Tick` event for each, and
+    /// returns the tick reached.
+    pub async fn step(&self, ticks: u64) -> Result<u64, DriverError> {
+        self.request(|reply| Command::Step { ticks, reply }).await
+    }
+
+    /// Runs a full generation and returns its number.
+    pub async fn run_generation(&self) -> Result<u32, DriverError> {
+        self.request(|reply| Command::RunGeneration { reply }).await
+    }
+
+    pub async fn snapshot(&self) -> Result<SimulationSnapshot, DriverError> {
+        self.request(|reply| Command::Snapshot { reply }).await
+    }
+
+    /// Events published after this call. Receivers that fall more than
+    /// `EVENT_CAPACITY` events behind skip ahead with `RecvError::Lagged`.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<SimEvent> {
+        self.events.subscribe()
+    }
+
+    /// Stops the simulation thread once queued commands finish and joins
+    /// it. Dropping the driver does the same, but blocks while waiting.
+    pub async fn shutdown(mut self) {
+        let (done, exited) = oneshot::channel();
+        if self.commands.send(Command::Shutdown { done: Some(done) }).is_ok() {
+            let _ = exited.await;
+        }
+        self.join();
+    }
+
+    async fn request<T>(&self, command: impl FnOnce(oneshot::Sender<T>) -> Command) -> Result<T, DriverError> {
+        let (reply, response) = oneshot::channel();
+        self.commands.send(command(reply)).map_err(|_| DriverError::Stopped)?;
+        response.await.map_err(|_| DriverError::Stopped)
+    }
+
+    fn join(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            // The thread may already be gone after a panic; joining still reaps it
+            let _ = self.commands.send(Command::Shutdown { done: None });
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for SimulationDriver {
+    fn drop(&mut self) {
+        self.join();
+    }
+}
+
+fn run_commands(
+    mut simulation: NeuralArenaSimulation,
+    commands: mpsc::Receiver<Command>,
+    events: broadcast::Sender<SimEvent>,
+) {
+    // Publishing fails only when nobody is subscribed, which is fine
+    while let Ok(command) = commands.recv() {
+        match command {
+            Command::Step { ticks, reply } => {
+                for _ in 0..ticks {
+                    simulation.single_tick();
+                    let _ = events.send(SimEvent::Tick {
+                        tick: simulation.tick,
+                        population: simulation.environment.warriors.len(),
+                    });
+                }
+                let _ = reply.send(simulation.tick);
+            },
+            Command::RunGeneration { reply } => {
+                let result = simulation.run_generation();
+                let _ = events.send(SimEvent::GenerationCompleted {
+                    generation: result.generation,
+                    survivors: result.survivors.len(),
+                    new_species: result.new_species,
+                    warnings: result.warnings,
+                });
+                let _ = reply.send(result.generation);
+            },
+            Command::Snapshot { reply } => {
+                let _ = reply.send(SimulationSnapshot {
+                    generation: simulation.generation,
+                    tick: simulation.tick,
+                    statistics: simulation.statistics.clone(),
+                    warriors: simulation.environment.warriors.values().cloned().collect(),
+                });
+            },
+            Command::Shutdown { done } => {
+                if let Some(done) = done {
+                    let _ = done.send(());
+                }
+                break;
+            },
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Headless one-on-one bouts, for debugging combat and comparing two
+//! specific genomes or warriors without the noise of a full population.
+
+use crate::neural::{Genome, NeuralWarrior};
+use crate::simulation::{NeuralArenaSimulation, SimulationConfig};
+use serde::{Deserialize, Serialize};
+
+/// Seed for every duel, so the same pairing always plays out the same way.
+pub const DUEL_SEED: u64 = 0;
+
+/// Starting distance between the duelists, within attack range.
+const STARTING_GAP: f32 = 40.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DuelSide {
+    A,
+    B,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuelistSummary {
+    /// Energy at the end of the bout; 0 once dead.
+    pub final_energy: f32,
+    pub damage_dealt: f32,
+    pub survived: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuelResult {
+    /// The last warrior standing, or failing that the one with more
+    /// energy left. `None` on an exact tie.
+    pub winner: Option<DuelSide>,
+    /// Ticks played; fewer than requested when a duelist died.
+    pub ticks: u64,
+    pub a: DuelistSummary,
+    pub b: DuelistSummary,
+}
+
+/// Pits `genome_a` against `genome_b`; see `duel_warriors`.
+///
+/// Genomes with the default `Blank` codec all decode to the same untrained
+/// network, so such duels differ only through the warriors' VM programs.
+/// Use `duel_warriors` to compare specific networks.
+pub fn duel(genome_a: Genome, genome_b: Genome, arena: (f32, f32), ticks: u64) -> DuelResult {
+    // duel_warriors places both warriors itself
+    let warrior_a = NeuralWarrior::with_position(genome_a, 0, (0.0, 0.0));
+    let warrior_b = NeuralWarrior::with_position(genome_b, 1, (0.0, 0.0));
+    duel_warriors(warrior_a, warrior_b, arena, ticks)
+}
+
+/// Pits two warriors against each other in an `arena`-sized environment
+/// with no resources, barriers, safe zones or other warriors. They start
+/// facing each other at the center with their current energy, and the bout
+/// lasts `ticks` ticks or until one of them dies. Offspring can't be
+/// placed, since the arena holds two.
+pub fn duel_warriors(mut warrior_a: NeuralWarrior, mut warrior_b: NeuralWarrior, arena: (f32, f32), ticks: u64) -> DuelResult {
+    let config = SimulationConfig {
+        arena_width: arena.0,
+        arena_height: arena.1,
+        max_population: 2,
+        fast_mode: true,
+        safe_zones: false,
+        seed: Some(DUEL_SEED),
+        ..SimulationConfig::default()
+    };
+    let mut simulation = NeuralArenaSimulation::new(config);
+    let environment = &mut simulation.environment;
+    environment.resources.clear();
+    environment.resource_config.max_resources = 0;
+    environment.barriers.clear();
+
+    let center = (arena.0 / 2.0, arena.1 / 2.0);
+    for (warrior, id, offset, heading) in [
+        (&mut warrior_a, 0, -STARTING_GAP / 2.0, 0.0),
+        (&mut warrior_b, 1, STARTING_GAP / 2.0, std::f32::consts::PI),
+    ] {
+        warrior.id = id;
+        warrior.position = (center.0 + offset, center.1);
+        warrior.heading = heading;
+        warrior.damage_dealt = 0.0;
+        warrior.recent_attackers.clear();
+    }
+    environment.add_warrior(warrior_a);
+    environment.add_warrior(warrior_b);
+
+    let mut a = summarize(simulation.environment.warriors.get(&0), None);
+    let mut b = summarize(simulation.environment.warriors.get(&1), None);
+    let mut played = 0;
+    while played < ticks && a.survived && b.survived {
+        simulation.single_tick();
+        played += 1;
+        a = summarize(simulation.environment.warriors.get(&0), Some(&a));
+        b = summarize(simulation.environment.warriors.get(&1), Some(&b));
+    }
+
+    let winner = match (a.survived, b.survived) {
+        (true, false) => Some(DuelSide::A),
+        (false, true) => Some(DuelSide::B),
+        _ if a.final_energy > b.final_energy => Some(DuelSide::A),
+        _ if b.final_energy > a.final_energy => Some(DuelSide::B),
+        _ => None,
+    };
+
+    DuelResult { winner, ticks: played, a, b }
+}
+
+/// A warrior killed this tick stays in the environment until the next one
+/// removes it, so the damage it dealt on its last tick still counts. Once
+/// it is gone, its damage total is carried over from `previous`.
+fn summarize(warrior: Option<&NeuralWarrior>, previous: Option<&DuelistSummary>) -> DuelistSummary {
+    match warrior {
+        Some(warrior) => DuelistSummary {
+            final_energy: warrior.energy.max(0.0),
+            damage_dealt: warrior.damage_dealt,
+            survived: warrior.is_alive(),
+        },
+        None => DuelistSummary {
+            final_energy: 0.0,
+            damage_dealt: previous.map_or(0.0, |summary| summary.damage_dealt),
+            survived: false,
+        },
+    }
+}
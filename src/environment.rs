@@ -1,14 +1,20 @@
-use crate::neural::{NeuralWarrior, Action, EnvironmentState, Resource, Territory};
-use crate::neural::warrior::ResourceType;
-use rand::Rng;
+use crate::neural::{NeuralWarrior, Action, EnvironmentState, MutationWeights, ReproductionConfig, Resource, ResourceClaim, ResourceGrid, SensorConfig, Territory};
+use crate::neural::warrior::{within_vision_cone, ResourceType};
+use crate::delta::{ChangeLog, StateDelta};
+use crate::rng::{unused_id, RngStream};
+use rand::rngs::StdRng;
+use rand::distributions::uniform::SampleUniform;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+pub use crate::geometry::{MemoryBarrier, SafeZone};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Environment {
     pub width: f32,
     pub height: f32,
-    pub warriors: HashMap<u32, NeuralWarrior>,
+    pub warriors: BTreeMap<u32, NeuralWarrior>,
     pub resources: Vec<Resource>,
     pub territories: Vec<Territory>,
     pub barriers: Vec<MemoryBarrier>,
@@ -18,6 +24,215 @@ pub struct Environment {
     pub environmental_pressure: f32,
     pub carrying_capacity: usize,
     pub resource_config: ResourceConfig,
+    pub action_costs: ActionCostModel,
+    pub sensor_config: SensorConfig,
+    pub pressure_config: PressureConfig,
+    #[serde(default)]
+    pub reproduction_config: ReproductionConfig,
+    #[serde(default)]
+    pub collision_config: CollisionConfig,
+    #[serde(default)]
+    pub territory_config: TerritoryConfig,
+    /// Operator mix used when mutating replicated genomes.
+    #[serde(default)]
+    pub mutation_weights: MutationWeights,
+    /// Probability that a replicated genome loses one random byte.
+    #[serde(default)]
+    pub deletion_rate: f32,
+    /// What happens at the arena edges; see `BoundaryMode`.
+    #[serde(default)]
+    pub boundary_mode: BoundaryMode,
+    /// Multi-tick actions in progress, by warrior. Committed warriors'
+    /// new decisions are ignored until the action completes or they take
+    /// damage.
+    #[serde(default)]
+    pub pending_actions: BTreeMap<u32, PendingAction>,
+    /// Where `spawn_position` places new warriors.
+    #[serde(default)]
+    pub spawn_strategy: SpawnStrategy,
+    #[serde(default)]
+    pub event_config: EventConfig,
+    /// Stamped onto every warrior; see `NeuralWarrior::relative_steering`.
+    #[serde(default)]
+    pub relative_steering: bool,
+    /// Ticks per year of warrior age: with 10, ages advance on every tenth
+    /// tick. Aging's energy cost is still charged every tick.
+    #[serde(default = "default_ticks_per_age_unit")]
+    pub ticks_per_age_unit: u32,
+    pub event_pressure: f32,
+    pub event_pressure_ticks: u32,
+    pub history: VecDeque<TickSample>,
+    pub history_capacity: usize,
+    #[serde(default)]
+    pub change_log: ChangeLog,
+    #[serde(default)]
+    next_resource_id: u64,
+    #[serde(default)]
+    pub energy_flows: EnergyFlows,
+    /// Collection totals per safe zone, keyed by index into `safe_zones`;
+    /// zones nobody has collected in have no entry.
+    #[serde(default)]
+    pub safe_zone_stats: BTreeMap<usize, SafeZoneStats>,
+    /// Independent streams, see `crate::rng`.
+    #[serde(skip, default = "StdRng::from_entropy")]
+    terrain_rng: StdRng,
+    #[serde(skip, default = "StdRng::from_entropy")]
+    resource_rng: StdRng,
+    #[serde(skip, default = "StdRng::from_entropy")]
+    event_rng: StdRng,
+    #[serde(skip, default = "StdRng::from_entropy")]
+    mutation_rng: StdRng,
+    /// Hits landed this tick, applied together by `process_combat`.
+    #[serde(skip)]
+    queued_hits: Vec<QueuedHit>,
+    /// Sub-step points each warrior moved through this tick, checked for
+    /// resource pickups along with its final position.
+    #[serde(skip)]
+    swept_paths: HashMap<u32, Vec<(f32, f32)>>,
+    /// Scratch buffers reused across ticks.
+    #[serde(skip)]
+    action_queue: Vec<(u32, Action)>,
+    #[serde(skip)]
+    collected_resources: Vec<usize>,
+    /// Phases `tick` runs, in order; see `set_schedule`.
+    #[serde(skip)]
+    schedule: TickScheduler,
+}
+
+/// One step of `Environment::tick`. `Custom` runs an arbitrary function,
+/// so experiments can insert steps of their own.
+#[derive(Debug, Clone, Copy)]
+pub enum TickPhase {
+    /// Recomputes environmental pressure from population and resources.
+    Pressure,
+    /// Arena-wide resource spawning.
+    SpawnResources,
+    /// Resources safe zones spawn inside themselves.
+    SafeZoneResources,
+    /// Records positions, ages warriors and expires old grudges.
+    Aging,
+    /// Removes warriors that ran out of energy.
+    RemoveDead,
+    /// Random resource decay.
+    DecayResources,
+    /// Territory ownership and upkeep.
+    Territories,
+    /// Rolls for and applies a random environmental event.
+    Events,
+    Custom(fn(&mut Environment, &mut EnvironmentUpdate)),
+}
+
+/// Ordered phases run by each `Environment::tick`.
+#[derive(Debug, Clone)]
+pub struct TickScheduler {
+    phases: Vec<TickPhase>,
+}
+
+impl TickScheduler {
+    /// The standard tick, in order.
+    pub const DEFAULT_PHASES: [TickPhase; 8] = [
+        TickPhase::Pressure,
+        TickPhase::SpawnResources,
+        TickPhase::SafeZoneResources,
+        TickPhase::Aging,
+        TickPhase::RemoveDead,
+        TickPhase::DecayResources,
+        TickPhase::Territories,
+        TickPhase::Events,
+    ];
+    
+    pub fn new(phases: Vec<TickPhase>) -> Self {
+        Self { phases }
+    }
+    
+    pub fn phases(&self) -> &[TickPhase] {
+        &self.phases
+    }
+}
+
+impl Default for TickScheduler {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_PHASES.to_vec())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct QueuedHit {
+    attacker_id: u32,
+    target_id: u32,
+    damage: f32,
+}
+
+/// An action spanning several ticks that a warrior is locked into.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PendingAction {
+    pub action: Action,
+    /// Ticks still to go after the most recently processed one.
+    pub remaining: u32,
+}
+
+/// How `Environment::spawn_position` chooses where new warriors appear.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum SpawnStrategy {
+    /// Uniform over the arena, avoiding barriers.
+    #[default]
+    UniformFree,
+    /// Normally distributed around a randomly chosen center. With no
+    /// centers this falls back to `UniformFree`.
+    Clustered { centers: Vec<(f32, f32)>, sigma: f32 },
+    /// Uniform inside a randomly chosen safe zone. With no safe zones this
+    /// falls back to `UniformFree`.
+    SafeZonesOnly,
+    /// Uniform along the arena's edges.
+    Perimeter,
+}
+
+/// Compact per-tick snapshot kept in the environment's history ring buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TickSample {
+    pub tick: u64,
+    pub warriors: usize,
+    pub resources: usize,
+    pub total_energy: f32,
+    pub pressure: f32,
+    #[serde(default)]
+    pub resource_energy: f32,
+    /// `EnergyFlows` recorded during this tick alone.
+    #[serde(default)]
+    pub energy_injected: f32,
+    #[serde(default)]
+    pub energy_destroyed: f32,
+}
+
+/// What warriors collected while standing in one safe zone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SafeZoneStats {
+    /// Resources collected by warriors standing inside the zone.
+    pub resources_granted: u32,
+    /// Energy those resources granted, bonus included.
+    pub energy_granted: f32,
+}
+
+/// Running totals of the arena's resource-side energy flows. Only resource
+/// spawns and collection bonuses count as injected, and only aging, resource
+/// decay and scarcity removals count as destroyed. Action costs, rest
+/// recovery, combat, territory upkeep, replication and energy lost to the
+/// warrior energy cap are not recorded, so these totals don't balance the
+/// arena's energy on their own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnergyFlows {
+    pub injected: f32,
+    pub destroyed: f32,
+}
+
+impl EnergyFlows {
+    /// Flows accumulated after the `earlier` totals were taken.
+    pub fn since(self, earlier: EnergyFlows) -> EnergyFlows {
+        EnergyFlows {
+            injected: self.injected - earlier.injected,
+            destroyed: self.destroyed - earlier.destroyed,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,24 +242,220 @@ pub struct ResourceConfig {
     pub energy_range: (f32, f32),
     pub computational_bonus: f32,
     pub territory_control_bonus: f32,
+    /// Warriors within this distance of an unclaimed resource claim it;
+    /// 0 disables claiming.
+    #[serde(default)]
+    pub claim_radius: f32,
+    /// Ticks a claim lasts before the resource is up for grabs again.
+    #[serde(default = "default_claim_duration")]
+    pub claim_duration: u64,
+    #[serde(default)]
+    pub claim_policy: ClaimPolicy,
+    /// Most resources one warrior can collect in a single tick; 0 means
+    /// no limit.
+    #[serde(default)]
+    pub max_collections_per_tick: usize,
+    /// When a safe zone's `resource_bonus` multiplies resource energy.
+    #[serde(default)]
+    pub safe_zone_bonus_mode: SafeZoneBonusMode,
+    /// Chance per tick that each safe zone spawns a resource inside
+    /// itself; 0 disables.
+    #[serde(default)]
+    pub safe_zone_spawn_rate: f32,
+    /// `false` leaves ResourceScarcity out of `EventConfig::enabled_events`.
+    #[deprecated(note = "list the event types in EventConfig::enabled_events instead")]
+    #[serde(default = "default_event_flag")]
     pub scarcity_events: bool,
+    /// `false` leaves ResourceAbundance out of `EventConfig::enabled_events`.
+    #[deprecated(note = "list the event types in EventConfig::enabled_events instead")]
+    #[serde(default = "default_event_flag")]
     pub abundance_events: bool,
 }
 
+/// When safe zones boost resource energy. `Both` lets a resource that
+/// spawned in a zone and is collected in one be boosted twice.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum SafeZoneBonusMode {
+    /// Resources that spawn inside a zone are worth more.
+    #[default]
+    Spawn,
+    /// Collectors standing inside a zone gain more from any resource.
+    Collection,
+    Both,
+}
+
+/// What happens when a warrior collects a resource someone else claimed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ClaimPolicy {
+    /// Only the claimant can collect it.
+    #[default]
+    Block,
+    /// Anyone can collect it, but non-claimants pay this much energy.
+    Penalty { energy: f32 },
+}
+
+fn default_claim_duration() -> u64 {
+    20
+}
+
+fn default_event_flag() -> bool {
+    true
+}
+
+fn default_ticks_per_age_unit() -> u32 {
+    1
+}
+
+/// Pushes crowded warriors apart after movement so none end a tick within
+/// another's `SensorConfig::personal_radius`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollisionConfig {
+    pub enabled: bool,
+    /// Relaxation passes per tick; more passes settle dense crowds better.
+    pub passes: u32,
+    /// Energy a warrior pays per unit of distance it is pushed.
+    pub displacement_energy_cost: f32,
+}
+
+impl Default for CollisionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            passes: 3,
+            displacement_energy_cost: 0.05,
+        }
+    }
+}
+
+/// What holding arena territories costs. Each tick every territory goes to
+/// the strongest warrior inside it that is below the cap and can pay the
+/// upkeep; otherwise it is left unowned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerritoryConfig {
+    /// Energy an owner pays per territory per tick. Zero by default, so
+    /// upkeep is opt-in.
+    pub upkeep_per_tick: f32,
+    /// Most territories a single warrior may hold at once.
+    pub max_per_warrior: usize,
+}
+
+impl Default for TerritoryConfig {
+    fn default() -> Self {
+        Self {
+            upkeep_per_tick: 0.0,
+            max_per_warrior: 3,
+        }
+    }
+}
+
+/// Energy costs and gains for every warrior action, in energy units.
+/// Per-unit costs are multiplied by the action's intensity/strength.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MemoryBarrier {
-    pub position: (f32, f32),
-    pub width: f32,
-    pub height: f32,
-    pub strength: f32,
+pub struct ActionCostModel {
+    pub move_cost_per_intensity: f32,
+    pub attack_cost_per_strength: f32,
+    pub defend_cost_per_strength: f32,
+    pub rest_energy_gain: f32,
+    pub aging_cost_per_tick: f32,
+    /// Fraction of the energy an attack drains from its target that the
+    /// attacker gains (0 = damage is lost, 1 = full predation).
+    pub combat_energy_transfer: f32,
+    /// Blocked moves charged in full before further attempts into a
+    /// barrier slide along its edge instead, or cost nothing when there is
+    /// no edge to slide along.
+    #[serde(default = "default_blocked_moves_before_deflect")]
+    pub blocked_moves_before_deflect: u32,
+    /// Longest segment a move covers at once. Longer moves are split into
+    /// sub-steps, each checked against barriers and for resource pickups,
+    /// so fast warriors can't tunnel. The default of 0 moves in a single
+    /// step.
+    #[serde(default = "default_max_step_length")]
+    pub max_step_length: f32,
+    /// Each resting tick (Rest, or a tick of DeepRest) in an unbroken run
+    /// recovers this fraction of the previous one; any other action starts
+    /// a fresh run. The default of 1 leaves rest gains undecayed.
+    #[serde(default = "default_rest_decay")]
+    pub rest_decay: f32,
+    /// Resting at or above this energy recovers nothing. Defaults to the
+    /// energy cap, so resting is only limited by the cap itself.
+    #[serde(default = "default_rest_energy_ceiling")]
+    pub rest_energy_ceiling: f32,
+}
+
+fn default_blocked_moves_before_deflect() -> u32 {
+    3
+}
+
+fn default_max_step_length() -> f32 {
+    0.0
+}
+
+fn default_rest_decay() -> f32 {
+    1.0
+}
+
+fn default_rest_energy_ceiling() -> f32 {
+    NeuralWarrior::MAX_ENERGY
 }
 
+/// How strongly environmental pressure bites. Each effect scales as
+/// `1 + pressure * coefficient`, so pressure 0 always means no effect.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SafeZone {
-    pub center: (f32, f32),
-    pub radius: f32,
-    pub protection_level: f32,
-    pub resource_bonus: f32,
+pub struct PressureConfig {
+    pub aging_drain_coefficient: f32,
+    pub spawn_rate_reduction: f32,
+    pub replication_energy_coefficient: f32,
+}
+
+impl Default for PressureConfig {
+    fn default() -> Self {
+        Self {
+            aging_drain_coefficient: 1.0,
+            spawn_rate_reduction: 0.5,
+            replication_energy_coefficient: 0.2,
+        }
+    }
+}
+
+/// Arena edge behavior. Under `Wrap` the arena is a torus: moves leaving
+/// one edge re-enter at the opposite one, and attacks reach across edges
+/// along the shortest wrapped path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundaryMode {
+    /// Positions are clamped to the arena; edges are walls.
+    #[default]
+    Clamp,
+    Wrap,
+}
+
+impl BoundaryMode {
+    /// Shortest displacement from `from` to `to` in an arena of `size`.
+    pub fn displacement(&self, from: (f32, f32), to: (f32, f32), size: (f32, f32)) -> (f32, f32) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        match self {
+            BoundaryMode::Clamp => (dx, dy),
+            BoundaryMode::Wrap => (wrapped_delta(dx, size.0), wrapped_delta(dy, size.1)),
+        }
+    }
+}
+
+/// `delta` reduced to the range `(-extent / 2, extent / 2]`.
+fn wrapped_delta(delta: f32, extent: f32) -> f32 {
+    if extent <= 0.0 {
+        return delta;
+    }
+    let delta = delta.rem_euclid(extent);
+    if delta > extent / 2.0 { delta - extent } else { delta }
+}
+
+/// `value` wrapped into `[0, extent)`.
+fn wrap_coordinate(value: f32, extent: f32) -> f32 {
+    if extent <= 0.0 {
+        return 0.0;
+    }
+    // rem_euclid can round tiny negatives up to `extent` itself
+    let wrapped = value.rem_euclid(extent);
+    if wrapped >= extent { 0.0 } else { wrapped }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,7 +476,51 @@ pub enum EventType {
     EnergeticStorm,
 }
 
+impl EventType {
+    pub const ALL: [EventType; 6] = [
+        EventType::ResourceScarcity,
+        EventType::ResourceAbundance,
+        EventType::MemoryCompaction,
+        EventType::TerritorialShift,
+        EventType::PopulationPressure,
+        EventType::EnergeticStorm,
+    ];
+}
+
+/// Uniform draw from `[low, high)`, or `low` when the range is empty.
+fn sample_half_open<T: SampleUniform + PartialOrd + Copy>(rng: &mut StdRng, (low, high): (T, T)) -> T {
+    if low < high { rng.gen_range(low..high) } else { low }
+}
+
+/// How often random environmental events fire and what they look like.
+/// Ranges are half-open; an empty range always gives its lower bound.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventConfig {
+    /// Chance each tick of an event firing; 0 disables random events.
+    pub probability_per_tick: f32,
+    /// Event types drawn from, uniformly; empty disables random events.
+    pub enabled_events: Vec<EventType>,
+    pub intensity_range: (f32, f32),
+    /// Duration in ticks.
+    pub duration_range: (u32, u32),
+    /// Radius of the affected area.
+    pub area_range: (f32, f32),
+}
+
+impl Default for EventConfig {
+    fn default() -> Self {
+        Self {
+            probability_per_tick: 0.02,
+            enabled_events: EventType::ALL.to_vec(),
+            intensity_range: (0.3, 0.8),
+            duration_range: (5, 20),
+            area_range: (50.0, 200.0),
+        }
+    }
+}
+
 impl Default for ResourceConfig {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             spawn_rate: 0.1,
@@ -73,18 +528,79 @@ impl Default for ResourceConfig {
             energy_range: (5.0, 25.0),
             computational_bonus: 1.5,
             territory_control_bonus: 2.0,
+            claim_radius: 0.0,
+            claim_duration: default_claim_duration(),
+            claim_policy: ClaimPolicy::Block,
+            max_collections_per_tick: 0,
+            safe_zone_bonus_mode: SafeZoneBonusMode::Spawn,
+            safe_zone_spawn_rate: 0.0,
             scarcity_events: true,
             abundance_events: true,
         }
     }
 }
 
+impl ResourceConfig {
+    /// `energy_range` ordered low to high, so an inverted range still works.
+    pub fn energy_bounds(&self) -> (f32, f32) {
+        let (a, b) = self.energy_range;
+        if a <= b { (a, b) } else { (b, a) }
+    }
+
+    /// Base value of a new resource; a range with equal ends always gives
+    /// that value.
+    pub fn sample_energy<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let (low, high) = self.energy_bounds();
+        if low == high {
+            low
+        } else {
+            rng.gen_range(low..=high)
+        }
+    }
+
+    /// Whether the deprecated per-event flags keep `event_type` enabled.
+    #[allow(deprecated)]
+    fn allows_event(&self, event_type: EventType) -> bool {
+        match event_type {
+            EventType::ResourceScarcity => self.scarcity_events,
+            EventType::ResourceAbundance => self.abundance_events,
+            _ => true,
+        }
+    }
+}
+
+impl Default for ActionCostModel {
+    fn default() -> Self {
+        Self {
+            move_cost_per_intensity: 2.0,
+            attack_cost_per_strength: 5.0,
+            defend_cost_per_strength: 3.0,
+            rest_energy_gain: 2.0,
+            aging_cost_per_tick: 0.1,
+            combat_energy_transfer: 0.0,
+            blocked_moves_before_deflect: default_blocked_moves_before_deflect(),
+            max_step_length: default_max_step_length(),
+            rest_decay: default_rest_decay(),
+            rest_energy_ceiling: default_rest_energy_ceiling(),
+        }
+    }
+}
+
 impl Environment {
+    pub const DEFAULT_HISTORY_CAPACITY: usize = 5000;
+    
     pub fn new(width: f32, height: f32, carrying_capacity: usize) -> Self {
+        Self::with_seed(width, height, carrying_capacity, rand::random())
+    }
+    
+    /// Like `new`, but terrain, resources, events and offspring are drawn
+    /// from streams derived from `seed`, so the environment evolves
+    /// identically across runs.
+    pub fn with_seed(width: f32, height: f32, carrying_capacity: usize, seed: u64) -> Self {
         let mut env = Self {
             width,
             height,
-            warriors: HashMap::new(),
+            warriors: BTreeMap::new(),
             resources: Vec::new(),
             territories: Vec::new(),
             barriers: Vec::new(),
@@ -94,64 +610,273 @@ impl Environment {
             environmental_pressure: 0.0,
             carrying_capacity,
             resource_config: ResourceConfig::default(),
+            action_costs: ActionCostModel::default(),
+            sensor_config: SensorConfig::default(),
+            pressure_config: PressureConfig::default(),
+            reproduction_config: ReproductionConfig::default(),
+            collision_config: CollisionConfig::default(),
+            territory_config: TerritoryConfig::default(),
+            mutation_weights: MutationWeights::default(),
+            deletion_rate: 0.0,
+            boundary_mode: BoundaryMode::default(),
+            pending_actions: BTreeMap::new(),
+            spawn_strategy: SpawnStrategy::default(),
+            event_config: EventConfig::default(),
+            relative_steering: false,
+            ticks_per_age_unit: default_ticks_per_age_unit(),
+            event_pressure: 0.0,
+            event_pressure_ticks: 0,
+            history: VecDeque::with_capacity(Self::DEFAULT_HISTORY_CAPACITY),
+            history_capacity: Self::DEFAULT_HISTORY_CAPACITY,
+            change_log: ChangeLog::default(),
+            next_resource_id: 0,
+            energy_flows: EnergyFlows::default(),
+            safe_zone_stats: BTreeMap::new(),
+            terrain_rng: RngStream::Terrain.rng(seed),
+            resource_rng: RngStream::Resources.rng(seed),
+            event_rng: RngStream::Events.rng(seed),
+            mutation_rng: RngStream::Mutation.rng(seed),
+            queued_hits: Vec::new(),
+            swept_paths: HashMap::new(),
+            action_queue: Vec::new(),
+            collected_resources: Vec::new(),
+            schedule: TickScheduler::default(),
         };
         
         env.initialize_terrain();
         env.spawn_initial_resources();
+        env.sync_changes();
         env
     }
     
+    /// Rederives every random stream from `seed`, as `with_seed` does.
+    pub fn reseed(&mut self, seed: u64) {
+        self.terrain_rng = RngStream::Terrain.rng(seed);
+        self.resource_rng = RngStream::Resources.rng(seed);
+        self.event_rng = RngStream::Events.rng(seed);
+        self.mutation_rng = RngStream::Mutation.rng(seed);
+    }
+    
+    /// Replaces barriers, safe zones and territories with a fresh layout
+    /// from the terrain stream. Warriors and resources stay put.
+    pub fn regenerate_terrain(&mut self) {
+        self.barriers.clear();
+        self.safe_zones.clear();
+        self.safe_zone_stats.clear();
+        self.territories.clear();
+        for warrior in self.warriors.values_mut() {
+            warrior.territory_id = None;
+        }
+        self.initialize_terrain();
+        self.sync_changes();
+    }
+    
+    /// Replaces every resource with a fresh starting stock drawn under the
+    /// current `resource_config` and safe zones. Call after changing either,
+    /// since the stock `with_seed` spawns uses the defaults.
+    pub fn respawn_resources(&mut self) {
+        self.energy_flows.destroyed += self.resources.drain(..).map(|resource| resource.energy_value).sum::<f32>();
+        self.spawn_initial_resources();
+        self.sync_changes();
+    }
+    
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+    
+    /// Restores an environment saved with `to_json`. Terrain, resources and
+    /// warriors come back exactly as saved; nothing is regenerated. The RNG
+    /// is not part of the snapshot, so call `reseed` to continue
+    /// deterministically.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let mut environment: Self = serde_json::from_str(json)?;
+        environment.sync_changes();
+        Ok(environment)
+    }
+    
     pub fn tick(&mut self) -> EnvironmentUpdate {
         self.tick += 1;
         self.resource_spawn_timer += 1;
         
         let mut update = EnvironmentUpdate::new(self.tick);
+        let flows_before = self.energy_flows;
         
-        // Update environmental pressure based on population
-        self.update_environmental_pressure();
-        
-        // Spawn resources
-        if self.should_spawn_resources() {
-            let spawned = self.spawn_resources();
-            update.resources_spawned = spawned;
-            self.resource_spawn_timer = 0;
+        for index in 0..self.schedule.phases.len() {
+            let phase = self.schedule.phases[index];
+            self.run_phase(phase, &mut update);
         }
         
-        // Age and process warriors
+        self.record_tick_sample(self.energy_flows.since(flows_before));
+        self.sync_changes();
+        
+        update
+    }
+    
+    /// Phases each `tick` runs, in order.
+    pub fn schedule(&self) -> &[TickPhase] {
+        self.schedule.phases()
+    }
+    
+    /// Replaces the phases each `tick` runs, e.g. to drop decay or insert a
+    /// custom step. `TickScheduler::DEFAULT_PHASES` restores the standard
+    /// tick. Tick counting, history and change tracking always run.
+    pub fn set_schedule(&mut self, phases: Vec<TickPhase>) {
+        self.schedule = TickScheduler::new(phases);
+    }
+    
+    fn run_phase(&mut self, phase: TickPhase, update: &mut EnvironmentUpdate) {
+        match phase {
+            TickPhase::Pressure => self.update_environmental_pressure(),
+            TickPhase::SpawnResources => {
+                if self.should_spawn_resources() {
+                    update.resources_spawned += self.spawn_resources();
+                    self.resource_spawn_timer = 0;
+                }
+            },
+            TickPhase::SafeZoneResources => update.resources_spawned += self.spawn_safe_zone_resources(),
+            TickPhase::Aging => self.age_warriors(),
+            TickPhase::RemoveDead => {
+                let dead_warriors = &mut update.dead_warriors;
+                self.warriors.retain(|&id, warrior| {
+                    if !warrior.is_alive() {
+                        dead_warriors.push(id);
+                    }
+                    warrior.is_alive()
+                });
+                update.warriors_died = dead_warriors.len();
+            },
+            TickPhase::DecayResources => self.decay_resources(),
+            TickPhase::Territories => self.update_territories(),
+            TickPhase::Events => {
+                let event_probability = self.event_config.probability_per_tick;
+                if event_probability > 0.0 && self.event_rng.gen::<f32>() < event_probability {
+                    if let Some(event) = self.generate_environmental_event() {
+                        self.apply_environmental_event(&event);
+                        update.environmental_event = Some(event);
+                    }
+                }
+            },
+            TickPhase::Custom(phase) => phase(self, update),
+        }
+    }
+    
+    fn age_warriors(&mut self) {
+        let aging_cost = self.action_costs.aging_cost_per_tick
+            * (1.0 + self.environmental_pressure * self.pressure_config.aging_drain_coefficient);
+        let grudge_memory_ticks = self.sensor_config.grudge_memory_ticks;
+        let age_advances = self.tick % self.ticks_per_age_unit.max(1) as u64 == 0;
         for warrior in self.warriors.values_mut() {
-            warrior.age_tick();
+            warrior.record_position();
+            let energy_before = warrior.energy;
+            if age_advances {
+                warrior.age_tick_with_cost(aging_cost);
+            } else {
+                warrior.consume_energy(aging_cost);
+            }
+            self.energy_flows.destroyed += energy_before - warrior.energy;
+            warrior.forget_expired_attackers(self.tick, grudge_memory_ticks);
+        }
+    }
+    
+    /// Records entity changes since the last sync in the change log.
+    pub fn sync_changes(&mut self) {
+        self.change_log.sync(self.tick, &self.warriors, &self.resources);
+    }
+    
+    /// Changes since `since_tick` (inclusive), for clients that keep a local
+    /// copy of the environment. Falls back to a full snapshot when the
+    /// change log no longer reaches back that far. Covers changes up to the
+    /// last `sync_changes`, which every `tick` ends with.
+    pub fn state_delta(&self, since_tick: u64) -> StateDelta {
+        StateDelta::build(&self.change_log, since_tick, self.tick, &self.warriors, &self.resources)
+    }
+    
+    /// Per-tick samples, oldest first. Holds at most `history_capacity` entries.
+    pub fn history(&self) -> &VecDeque<TickSample> {
+        &self.history
+    }
+    
+    /// Samples recorded strictly after `tick`.
+    pub fn history_since(&self, tick: u64) -> Vec<TickSample> {
+        self.history.iter()
+            .filter(|sample| sample.tick > tick)
+            .copied()
+            .collect()
+    }
+    
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history_capacity = capacity;
+        while self.history.len() > capacity {
+            self.history.pop_front();
+        }
+    }
+    
+    /// Energy held by living warriors.
+    pub fn warrior_energy(&self) -> f32 {
+        self.warriors.values().map(|warrior| warrior.energy).sum()
+    }
+    
+    /// Energy waiting in uncollected resources.
+    pub fn resource_energy(&self) -> f32 {
+        self.resources.iter().map(|resource| resource.energy_value).sum()
+    }
+    
+    fn record_tick_sample(&mut self, flows: EnergyFlows) {
+        if self.history_capacity == 0 {
+            return;
         }
         
-        // Remove dead warriors
-        let initial_count = self.warriors.len();
-        self.warriors.retain(|_, warrior| warrior.is_alive());
-        update.warriors_died = initial_count - self.warriors.len();
-        
-        // Decay unused resources
-        self.decay_resources();
-        
-        // Update territories
-        self.update_territories();
-        
-        // Trigger random environmental events
-        if rand::random::<f32>() < 0.02 {
-            let event = self.generate_environmental_event();
-            self.apply_environmental_event(&event);
-            update.environmental_event = Some(event);
+        if self.history.len() >= self.history_capacity {
+            self.history.pop_front();
         }
         
-        update
+        self.history.push_back(TickSample {
+            tick: self.tick,
+            warriors: self.warriors.len(),
+            resources: self.resources.len(),
+            total_energy: self.warrior_energy(),
+            pressure: self.environmental_pressure,
+            resource_energy: self.resource_energy(),
+            energy_injected: flows.injected,
+            energy_destroyed: flows.destroyed,
+        });
     }
     
-    pub fn execute_warrior_actions(&mut self, actions: HashMap<u32, Action>) -> ActionResults {
+    pub fn execute_warrior_actions(&mut self, mut actions: ActionMap) -> ActionResults {
+        self.execute_action_map(&mut actions)
+    }
+    
+    /// Like `execute_warrior_actions`, but drains `actions` in place so the
+    /// caller can reuse the map, capacity intact, next tick.
+    pub fn execute_action_map(&mut self, actions: &mut ActionMap) -> ActionResults {
         let mut results = ActionResults::new();
+        self.swept_paths.clear();
         
-        for (warrior_id, action) in actions {
-            if let Some(warrior) = self.warriors.get_mut(&warrior_id) {
+        // Committed warriors carry on with their pending action instead
+        let committed: Vec<u32> = self.pending_actions.keys().copied().collect();
+        for &warrior_id in &committed {
+            if let Some(pending) = self.pending_actions.remove(&warrior_id) {
+                if self.warriors.contains_key(&warrior_id) {
+                    let result = self.continue_action(warrior_id, pending.action, pending.remaining.saturating_sub(1));
+                    results.add_result(warrior_id, result);
+                }
+            }
+        }
+        
+        // Resolve actions in id order so outcomes don't depend on map iteration
+        let mut queue = std::mem::take(&mut self.action_queue);
+        queue.extend(actions.drain().filter(|(warrior_id, _)| committed.binary_search(warrior_id).is_err()));
+        queue.sort_by_key(|&(warrior_id, _)| warrior_id);
+        
+        for (warrior_id, action) in queue.drain(..) {
+            if self.warriors.contains_key(&warrior_id) {
                 let result = self.execute_action(warrior_id, action);
                 results.add_result(warrior_id, result);
             }
         }
+        self.action_queue = queue;
+        
+        self.resolve_collisions();
         
         // Process combat interactions
         self.process_combat(&mut results);
@@ -169,13 +894,62 @@ impl Environment {
         
         // Ensure warrior is positioned within bounds
         let mut positioned_warrior = warrior;
-        positioned_warrior.position.0 = positioned_warrior.position.0.clamp(0.0, self.width);
-        positioned_warrior.position.1 = positioned_warrior.position.1.clamp(0.0, self.height);
+        positioned_warrior.reproduction = self.reproduction_config.clone();
+        positioned_warrior.relative_steering = self.relative_steering;
+        positioned_warrior.position = self.confine(positioned_warrior.position);
         
         self.warriors.insert(positioned_warrior.id, positioned_warrior);
         true
     }
     
+    /// Picks a position for a new warrior according to `spawn_strategy`,
+    /// drawing from `rng` so callers control reproducibility.
+    pub fn spawn_position(&self, rng: &mut impl Rng) -> (f32, f32) {
+        let clamp = |(x, y): (f32, f32)| (x.clamp(0.0, self.width), y.clamp(0.0, self.height));
+        match &self.spawn_strategy {
+            SpawnStrategy::Clustered { centers, sigma } if !centers.is_empty() => {
+                let center = centers[rng.gen_range(0..centers.len())];
+                // Box-Muller transform
+                let radius = sigma * (-2.0 * (1.0 - rng.gen::<f32>()).ln()).sqrt();
+                let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+                clamp((center.0 + radius * angle.cos(), center.1 + radius * angle.sin()))
+            },
+            SpawnStrategy::SafeZonesOnly if !self.safe_zones.is_empty() => {
+                let zone = &self.safe_zones[rng.gen_range(0..self.safe_zones.len())];
+                let radius = zone.radius * rng.gen::<f32>().sqrt();
+                let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+                clamp((zone.center.0 + radius * angle.cos(), zone.center.1 + radius * angle.sin()))
+            },
+            SpawnStrategy::Perimeter => {
+                let distance = rng.gen::<f32>() * 2.0 * (self.width + self.height);
+                if distance < self.width {
+                    (distance, 0.0)
+                } else if distance < self.width + self.height {
+                    (self.width, distance - self.width)
+                } else if distance < 2.0 * self.width + self.height {
+                    (2.0 * self.width + self.height - distance, self.height)
+                } else {
+                    (0.0, 2.0 * (self.width + self.height) - distance)
+                }
+            },
+            _ => self.free_position(rng),
+        }
+    }
+    
+    /// Uniform position outside every barrier. Gives up after a bounded
+    /// number of draws on arenas that are almost entirely walled off.
+    fn free_position(&self, rng: &mut impl Rng) -> (f32, f32) {
+        const MAX_ATTEMPTS: usize = 64;
+        let mut position = (0.0, 0.0);
+        for _ in 0..MAX_ATTEMPTS {
+            position = (rng.gen::<f32>() * self.width, rng.gen::<f32>() * self.height);
+            if !self.barriers.iter().any(|barrier| barrier.contains(position)) {
+                break;
+            }
+        }
+        position
+    }
+    
     pub fn remove_warrior(&mut self, warrior_id: u32) -> Option<NeuralWarrior> {
         self.warriors.remove(&warrior_id)
     }
@@ -185,7 +959,12 @@ impl Environment {
             warriors: self.warriors.values().cloned().collect(),
             resources: self.resources.clone(),
             territories: self.territories.clone(),
+            bounds: (self.width, self.height),
+            barriers: self.barriers.clone(),
+            safe_zones: self.safe_zones.clone(),
             tick: self.tick,
+            sensor_config: self.sensor_config.clone(),
+            resource_grid: ResourceGrid::new(&self.resources, self.sensor_config.resource_gradient_radius),
         }
     }
     
@@ -212,7 +991,7 @@ impl Environment {
     }
     
     fn initialize_terrain(&mut self) {
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.terrain_rng;
         
         // Create memory barriers
         for _ in 0..10 {
@@ -235,7 +1014,7 @@ impl Environment {
         }
         
         // Create territories
-        for i in 0..15 {
+        for _ in 0..15 {
             self.territories.push(Territory {
                 center: (rng.gen_range(0.0..self.width), rng.gen_range(0.0..self.height)),
                 radius: rng.gen_range(40.0..120.0),
@@ -246,19 +1025,20 @@ impl Environment {
     }
     
     fn spawn_initial_resources(&mut self) {
-        for _ in 0..100 {
+        for _ in 0..100.min(self.resource_config.max_resources) {
             self.spawn_single_resource();
         }
     }
     
-    fn should_spawn_resources(&self) -> bool {
+    fn should_spawn_resources(&mut self) -> bool {
+        let pressure_factor = (1.0 - self.environmental_pressure * self.pressure_config.spawn_rate_reduction).max(0.0);
         self.resource_spawn_timer > 10 && 
         self.resources.len() < self.resource_config.max_resources &&
-        rand::random::<f32>() < self.resource_config.spawn_rate
+        self.resource_rng.gen::<f32>() < self.resource_config.spawn_rate * pressure_factor
     }
     
     fn spawn_resources(&mut self) -> usize {
-        let spawn_count = rand::thread_rng().gen_range(1..=5);
+        let spawn_count = self.resource_rng.gen_range(1..=5);
         let mut spawned = 0;
         
         for _ in 0..spawn_count {
@@ -271,12 +1051,44 @@ impl Environment {
         spawned
     }
     
-    fn spawn_single_resource(&mut self) {
-        let mut rng = rand::thread_rng();
-        let position = (rng.gen_range(0.0..self.width), rng.gen_range(0.0..self.height));
+    /// Gives each safe zone a `safe_zone_spawn_rate` chance to spawn a
+    /// resource somewhere inside it.
+    fn spawn_safe_zone_resources(&mut self) -> usize {
+        let rate = self.resource_config.safe_zone_spawn_rate;
+        if rate <= 0.0 {
+            return 0;
+        }
         
-        // Check if position is in a safe zone for bonus
-        let mut energy_value = rng.gen_range(self.resource_config.energy_range.0..=self.resource_config.energy_range.1);
+        let mut spawned = 0;
+        for zone_index in 0..self.safe_zones.len() {
+            if self.resources.len() >= self.resource_config.max_resources {
+                break;
+            }
+            if self.resource_rng.gen::<f32>() >= rate {
+                continue;
+            }
+            let zone = &self.safe_zones[zone_index];
+            let (center, zone_radius) = (zone.center, zone.radius);
+            let radius = zone_radius * self.resource_rng.gen::<f32>().sqrt();
+            let angle = self.resource_rng.gen::<f32>() * std::f32::consts::TAU;
+            let position = (
+                (center.0 + radius * angle.cos()).clamp(0.0, self.width),
+                (center.1 + radius * angle.sin()).clamp(0.0, self.height),
+            );
+            self.spawn_resource_at(position);
+            spawned += 1;
+        }
+        spawned
+    }
+    
+    fn spawn_single_resource(&mut self) {
+        let position = (self.resource_rng.gen_range(0.0..self.width), self.resource_rng.gen_range(0.0..self.height));
+        self.spawn_resource_at(position);
+    }
+    
+    fn spawn_resource_at(&mut self, position: (f32, f32)) {
+        let rng = &mut self.resource_rng;
+        let mut energy_value = self.resource_config.sample_energy(rng);
         let resource_type = if rng.gen_bool(0.7) {
             ResourceType::Energy
         } else if rng.gen_bool(0.5) {
@@ -288,34 +1100,50 @@ impl Environment {
         };
         
         // Apply safe zone bonus
-        for safe_zone in &self.safe_zones {
-            let distance = ((position.0 - safe_zone.center.0).powi(2) + 
-                           (position.1 - safe_zone.center.1).powi(2)).sqrt();
-            if distance < safe_zone.radius {
+        if self.resource_config.safe_zone_bonus_mode != SafeZoneBonusMode::Collection {
+            if let Some(safe_zone) = self.strongest_safe_zone_at(position) {
                 energy_value *= safe_zone.resource_bonus;
-                break;
             }
         }
         
+        let id = self.next_resource_id;
+        self.next_resource_id += 1;
+        self.energy_flows.injected += energy_value;
+        
         self.resources.push(Resource {
+            id,
             position,
             energy_value,
             resource_type,
+            claim: None,
         });
     }
     
     fn update_environmental_pressure(&mut self) {
         let population_ratio = self.warriors.len() as f32 / self.carrying_capacity as f32;
-        let resource_scarcity = 1.0 - (self.resources.len() as f32 / self.resource_config.max_resources as f32);
+        // An arena configured without resources has nothing to run short of
+        let resource_scarcity = if self.resource_config.max_resources == 0 {
+            0.0
+        } else {
+            1.0 - (self.resources.len() as f32 / self.resource_config.max_resources as f32)
+        };
         
         self.environmental_pressure = (population_ratio + resource_scarcity) / 2.0;
+        
+        // Population pressure events add on top of the computed pressure
+        if self.event_pressure_ticks > 0 {
+            self.environmental_pressure += self.event_pressure;
+            self.event_pressure_ticks -= 1;
+        }
+        
         self.environmental_pressure = self.environmental_pressure.clamp(0.0, 1.0);
     }
     
     fn decay_resources(&mut self) {
         // Remove resources that have been around too long or in low-activity areas
+        let mut decayed = 0.0;
         self.resources.retain(|resource| {
-            if rand::random::<f32>() < 0.002 {
+            let keep = if self.resource_rng.gen::<f32>() < 0.002 {
                 // Random decay
                 false
             } else {
@@ -327,33 +1155,89 @@ impl Environment {
                 });
                 
                 // Resources in active areas are more likely to persist
-                nearby_warriors || rand::random::<f32>() < 0.99
+                nearby_warriors || self.resource_rng.gen::<f32>() < 0.99
+            };
+            if !keep {
+                decayed += resource.energy_value;
             }
+            keep
         });
+        self.energy_flows.destroyed += decayed;
+    }
+    
+    /// Energy a warrior must exceed to replicate under the current pressure.
+    pub fn replication_min_energy(&self) -> f32 {
+        self.reproduction_config.min_energy
+            * (1.0 + self.environmental_pressure * self.pressure_config.replication_energy_coefficient)
+    }
+    
+    /// The safe zone that governs `position` when several overlap: highest
+    /// protection level, then highest resource bonus, then earliest listed.
+    pub fn strongest_safe_zone_at(&self, position: (f32, f32)) -> Option<&SafeZone> {
+        self.strongest_safe_zone_index_at(position).map(|index| &self.safe_zones[index])
+    }
+    
+    fn strongest_safe_zone_index_at(&self, position: (f32, f32)) -> Option<usize> {
+        self.safe_zones.iter()
+            .enumerate()
+            .filter(|(_, safe_zone)| {
+                let distance = ((position.0 - safe_zone.center.0).powi(2) + 
+                               (position.1 - safe_zone.center.1).powi(2)).sqrt();
+                distance < safe_zone.radius
+            })
+            .reduce(|best, candidate| {
+                let stronger = candidate.1.protection_level.total_cmp(&best.1.protection_level)
+                    .then(candidate.1.resource_bonus.total_cmp(&best.1.resource_bonus))
+                    .is_gt();
+                if stronger { candidate } else { best }
+            })
+            .map(|(index, _)| index)
     }
     
     fn update_territories(&mut self) {
+        let TerritoryConfig { upkeep_per_tick, max_per_warrior } = self.territory_config;
+        for warrior in self.warriors.values_mut() {
+            warrior.territories_owned = 0;
+        }
+        
         for territory in &mut self.territories {
             // Find warriors in territory
-            let nearby_warriors: Vec<&NeuralWarrior> = self.warriors.values()
+            let mut nearby_warriors: Vec<(u32, f32)> = self.warriors.values()
                 .filter(|warrior| {
                     let distance = ((warrior.position.0 - territory.center.0).powi(2) + 
                                    (warrior.position.1 - territory.center.1).powi(2)).sqrt();
                     distance < territory.radius
                 })
+                .map(|warrior| (warrior.id, warrior.energy))
                 .collect();
             
-            // Determine territory control based on strongest presence
-            if let Some(dominant_warrior) = nearby_warriors.iter()
-                .max_by(|a, b| a.energy.partial_cmp(&b.energy).unwrap()) {
-                territory.owner_id = Some(dominant_warrior.id);
-            } else {
-                territory.owner_id = None;
+            // Strongest presence controls the territory, equal energy going
+            // to the lowest id; warriors at the cap or unable to pay the
+            // upkeep pass it to the runner-up
+            nearby_warriors.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+            territory.owner_id = nearby_warriors.iter()
+                .map(|&(id, _)| id)
+                .find(|id| {
+                    self.warriors.get(id).is_some_and(|warrior| {
+                        (warrior.territories_owned as usize) < max_per_warrior && warrior.energy >= upkeep_per_tick
+                    })
+                });
+            
+            if let Some(owner) = territory.owner_id.and_then(|id| self.warriors.get_mut(&id)) {
+                owner.consume_energy(upkeep_per_tick);
+                owner.territories_owned += 1;
+                owner.behavior.territory_ticks += 1;
             }
         }
     }
     
     fn execute_action(&mut self, warrior_id: u32, action: Action) -> ActionResult {
+        if !matches!(action, Action::Rest | Action::DeepRest) {
+            if let Some(warrior) = self.warriors.get_mut(&warrior_id) {
+                warrior.behavior.consecutive_rests = 0;
+            }
+        }
+        
         match action {
             Action::Move { direction, intensity } => {
                 self.execute_move(warrior_id, direction, intensity)
@@ -370,95 +1254,225 @@ impl Environment {
             Action::Sense { sensor_type } => {
                 self.execute_sense(warrior_id, sensor_type)
             },
-            Action::Rest => {
-                if let Some(warrior) = self.warriors.get_mut(&warrior_id) {
-                    warrior.gain_energy(2.0);
-                    ActionResult::Success("Rested and recovered energy".to_string())
-                } else {
-                    ActionResult::Failed("Warrior not found".to_string())
+            Action::Rest => self.execute_rest(warrior_id),
+            Action::ChargedAttack { strength, .. } => {
+                // The wind-up costs the same as a normal attack, paid up front
+                if let Err(failure) = self.pay_attack_cost(warrior_id, strength) {
+                    return failure;
+                }
+                self.continue_action(warrior_id, action, action.duration() - 1)
+            },
+            Action::DeepRest => self.continue_action(warrior_id, action, action.duration() - 1),
+        }
+    }
+    
+    fn execute_rest(&mut self, warrior_id: u32) -> ActionResult {
+        if !self.recover_rest(warrior_id, 1.0) {
+            return ActionResult::Failed("Warrior not found".to_string());
+        }
+        ActionResult::Success("Rested and recovered energy".to_string())
+    }
+    
+    /// One tick of resting, shared by Rest and every DeepRest tick: recovers
+    /// `rest_energy_gain * multiplier` scaled by `rest_decay` once for every
+    /// earlier resting tick in the current run, or nothing at or above
+    /// `rest_energy_ceiling`. False when the warrior is gone.
+    fn recover_rest(&mut self, warrior_id: u32, multiplier: f32) -> bool {
+        let costs = &self.action_costs;
+        let Some(warrior) = self.warriors.get_mut(&warrior_id) else {
+            return false;
+        };
+        
+        let rest_gain = if warrior.energy >= costs.rest_energy_ceiling {
+            0.0
+        } else {
+            costs.rest_energy_gain * multiplier * costs.rest_decay.powi(warrior.behavior.consecutive_rests as i32)
+        };
+        warrior.behavior.consecutive_rests = warrior.behavior.consecutive_rests.saturating_add(1);
+        warrior.gain_energy(rest_gain);
+        true
+    }
+    
+    /// Plays one tick of a multi-tick action with `remaining` ticks to go
+    /// after it, re-queueing it until it completes.
+    fn continue_action(&mut self, warrior_id: u32, action: Action, remaining: u32) -> ActionResult {
+        match action {
+            Action::ChargedAttack { target_direction, strength } if remaining == 0 => {
+                return self.strike(warrior_id, target_direction, strength, 2.0);
+            },
+            Action::DeepRest => {
+                self.recover_rest(warrior_id, 3.0);
+                if remaining == 0 {
+                    return ActionResult::Success("Finished deep rest".to_string());
                 }
             },
+            _ => {},
+        }
+        
+        self.pending_actions.insert(warrior_id, PendingAction { action, remaining });
+        ActionResult::InProgress { remaining }
+    }
+    
+    /// Brings `point` back inside the arena: clamped to the edges, or
+    /// wrapped around them under `BoundaryMode::Wrap`.
+    pub fn confine(&self, point: (f32, f32)) -> (f32, f32) {
+        match self.boundary_mode {
+            BoundaryMode::Clamp => (point.0.clamp(0.0, self.width), point.1.clamp(0.0, self.height)),
+            BoundaryMode::Wrap => (wrap_coordinate(point.0, self.width), wrap_coordinate(point.1, self.height)),
         }
     }
     
     fn execute_move(&mut self, warrior_id: u32, direction: f32, intensity: f32) -> ActionResult {
-        let move_distance = intensity * 10.0;
-        let energy_cost = intensity * 2.0;
+        let move_distance = intensity * NeuralWarrior::MAX_MOVE_DISTANCE;
+        let energy_cost = intensity * self.action_costs.move_cost_per_intensity;
         
-        let (new_x, new_y) = {
-            let warrior = match self.warriors.get(&warrior_id) {
-                Some(w) => w,
-                None => return ActionResult::Failed("Warrior not found".to_string()),
-            };
-            
-            if warrior.energy < energy_cost {
+        let (old_position, blocked_moves) = match self.warriors.get(&warrior_id) {
+            Some(warrior) if warrior.energy < energy_cost => {
                 return ActionResult::Failed("Insufficient energy for movement".to_string());
-            }
-            
-            let new_x = (warrior.position.0 + direction.cos() * move_distance).clamp(0.0, self.width);
-            let new_y = (warrior.position.1 + direction.sin() * move_distance).clamp(0.0, self.height);
-            (new_x, new_y)
+            },
+            Some(warrior) => (warrior.position, warrior.blocked_moves),
+            None => return ActionResult::Failed("Warrior not found".to_string()),
         };
+        let delta = (direction.cos() * move_distance, direction.sin() * move_distance);
+        let (path, barrier_strength) = self.sweep_path(old_position, delta);
+        // Once a warrior keeps pushing into a barrier, slide along its edge
+        let deflected = match barrier_strength {
+            Some(_) if path.is_empty() && blocked_moves >= self.action_costs.blocked_moves_before_deflect => {
+                [(delta.0, 0.0), (0.0, delta.1)].into_iter()
+                    .map(|axis_delta| self.sweep_path(old_position, axis_delta))
+                    .find(|(path, strength)| strength.is_none() && path.last().is_some_and(|&end| end != old_position))
+                    .map(|(path, _)| path)
+            },
+            _ => None,
+        };
+        
+        let warrior = match self.warriors.get_mut(&warrior_id) {
+            Some(warrior) => warrior,
+            None => return ActionResult::Failed("Warrior not found".to_string()),
+        };
+        // The warrior turns to face its move even if a barrier stops it
+        warrior.heading = direction.rem_euclid(std::f32::consts::TAU);
         
-        // Check for barriers
-        for barrier in &self.barriers {
-            if new_x >= barrier.position.0 && new_x <= barrier.position.0 + barrier.width &&
-               new_y >= barrier.position.1 && new_y <= barrier.position.1 + barrier.height {
-                if let Some(warrior) = self.warriors.get_mut(&warrior_id) {
-                    warrior.consume_energy(energy_cost * barrier.strength);
+        let (result, path) = match (barrier_strength, deflected) {
+            (None, _) => {
+                let (x, y) = path.last().copied().unwrap_or(old_position);
+                warrior.blocked_moves = 0;
+                (ActionResult::Success(format!("Moved to ({:.1}, {:.1})", x, y)), path)
+            },
+            (Some(_), Some(deflected_path)) => {
+                let (x, y) = deflected_path.last().copied().unwrap_or(old_position);
+                warrior.blocked_moves += 1;
+                (ActionResult::Partial(format!("Deflected along barrier to ({:.1}, {:.1})", x, y)), deflected_path)
+            },
+            (Some(_), None) if !path.is_empty() => {
+                warrior.blocked_moves += 1;
+                (ActionResult::Partial("Stopped short of barrier".to_string()), path)
+            },
+            (Some(strength), None) => {
+                // Futile pushes past the threshold cost nothing
+                if blocked_moves < self.action_costs.blocked_moves_before_deflect {
+                    warrior.consume_energy(energy_cost * strength);
                 }
+                warrior.blocked_moves += 1;
                 return ActionResult::Partial("Movement blocked by barrier".to_string());
-            }
-        }
+            },
+        };
         
-        if let Some(warrior) = self.warriors.get_mut(&warrior_id) {
-            warrior.position = (new_x, new_y);
-            warrior.consume_energy(energy_cost);
-            ActionResult::Success(format!("Moved to ({:.1}, {:.1})", new_x, new_y))
-        } else {
-            ActionResult::Failed("Warrior not found".to_string())
+        // Every move that gets anywhere costs the same, however far it got
+        warrior.position = path.last().copied().unwrap_or(old_position);
+        warrior.consume_energy(energy_cost);
+        self.swept_paths.entry(warrior_id).or_default().extend(path);
+        result
+    }
+    
+    /// Walks from `start` by `delta` in sub-steps no longer than
+    /// `max_step_length`, stopping before the first sub-step that touches a
+    /// barrier. Barriers already containing `start` don't stop the walk, so
+    /// warriors can always leave them. Returns the points reached, `start`
+    /// excluded, and the strength of the barrier that stopped the walk.
+    fn sweep_path(&self, start: (f32, f32), delta: (f32, f32)) -> (Vec<(f32, f32)>, Option<f32>) {
+        let length = (delta.0.powi(2) + delta.1.powi(2)).sqrt();
+        let max_step = self.action_costs.max_step_length;
+        let steps = if max_step > 0.0 { ((length / max_step).ceil() as usize).max(1) } else { 1 };
+        let step = (delta.0 / steps as f32, delta.1 / steps as f32);
+        
+        let mut path = Vec::with_capacity(steps);
+        let mut from = start;
+        for _ in 0..steps {
+            let to = (from.0 + step.0, from.1 + step.1);
+            let blocking = self.barriers.iter()
+                .find(|barrier| !barrier.contains(start) && barrier.intersects_segment(from, to));
+            if let Some(barrier) = blocking {
+                return (path, Some(barrier.strength));
+            }
+            from = self.confine(to);
+            path.push(from);
         }
+        (path, None)
     }
     
     fn execute_attack(&mut self, attacker_id: u32, target_direction: f32, strength: f32) -> ActionResult {
-        let attacker_pos = match self.warriors.get(&attacker_id) {
-            Some(w) => w.position,
+        if let Err(failure) = self.pay_attack_cost(attacker_id, strength) {
+            return failure;
+        }
+        self.strike(attacker_id, target_direction, strength, 1.0)
+    }
+    
+    fn pay_attack_cost(&mut self, attacker_id: u32, strength: f32) -> Result<(), ActionResult> {
+        let energy_cost = strength * self.action_costs.attack_cost_per_strength;
+        match self.warriors.get_mut(&attacker_id) {
+            Some(attacker) if attacker.energy >= energy_cost => {
+                attacker.consume_energy(energy_cost);
+                Ok(())
+            },
+            Some(_) => Err(ActionResult::Failed("Insufficient energy for attack".to_string())),
+            None => Err(ActionResult::Failed("Attacker not found".to_string())),
+        }
+    }
+    
+    /// Aims a paid-for attack. Damage from a hit is queued for
+    /// `process_combat` rather than applied here.
+    fn strike(&mut self, attacker_id: u32, target_direction: f32, strength: f32, damage_multiplier: f32) -> ActionResult {
+        let (attacker_pos, attacker_heading) = match self.warriors.get(&attacker_id) {
+            Some(w) => (w.position, w.heading),
             None => return ActionResult::Failed("Attacker not found".to_string()),
         };
-        
-        let energy_cost = strength * 5.0;
-        if let Some(attacker) = self.warriors.get_mut(&attacker_id) {
-            if attacker.energy < energy_cost {
-                return ActionResult::Failed("Insufficient energy for attack".to_string());
-            }
-            attacker.consume_energy(energy_cost);
-        }
+        let vision_cone = self.sensor_config.vision_cone;
+        let boundary = self.boundary_mode;
         
         // Find target in attack direction
         let attack_range = strength * 30.0;
         let target_x = attacker_pos.0 + target_direction.cos() * attack_range;
         let target_y = attacker_pos.1 + target_direction.sin() * attack_range;
         
-        for (target_id, target) in self.warriors.iter_mut() {
-            if *target_id == attacker_id {
+        let mut hit = None;
+        for (target_id, target) in self.warriors.iter() {
+            // Judge visibility by the nearest copy of the target across a wrapped edge
+            let (to_x, to_y) = boundary.displacement(attacker_pos, target.position, (self.width, self.height));
+            let apparent_position = (attacker_pos.0 + to_x, attacker_pos.1 + to_y);
+            if *target_id == attacker_id || !within_vision_cone(attacker_pos, attacker_heading, apparent_position, vision_cone) {
                 continue;
             }
             
-            let distance = ((target.position.0 - target_x).powi(2) + 
-                           (target.position.1 - target_y).powi(2)).sqrt();
+            let (dx, dy) = boundary.displacement((target_x, target_y), target.position, (self.width, self.height));
+            let distance = (dx * dx + dy * dy).sqrt();
             
             if distance < 20.0 {
-                let damage = strength * 15.0;
-                target.consume_energy(damage);
-                return ActionResult::Success(format!("Hit target {} for {:.1} damage", target_id, damage));
+                hit = Some((*target_id, target.name.clone(), strength * 15.0 * damage_multiplier));
+                break;
             }
         }
         
+        if let Some((target_id, target_name, damage)) = hit {
+            self.queued_hits.push(QueuedHit { attacker_id, target_id, damage });
+            return ActionResult::Success(format!("Hit target {} ({}) for {:.1} damage", target_id, target_name, damage));
+        }
+        
         ActionResult::Failed("No target in range".to_string())
     }
     
     fn execute_defend(&mut self, warrior_id: u32, shield_strength: f32) -> ActionResult {
-        let energy_cost = shield_strength * 3.0;
+        let energy_cost = shield_strength * self.action_costs.defend_cost_per_strength;
         
         if let Some(warrior) = self.warriors.get_mut(&warrior_id) {
             if warrior.energy < energy_cost {
@@ -478,39 +1492,53 @@ impl Environment {
             None => return ActionResult::Failed("Parent not found".to_string()),
         };
         
-        if !parent.can_replicate() {
+        let reproduction = self.reproduction_config.clone();
+        if !parent.can_replicate_with_min_energy(self.replication_min_energy()) {
             return ActionResult::Failed("Cannot replicate - insufficient energy or too young".to_string());
         }
         
-        if self.warriors.len() >= self.carrying_capacity {
+        let litter_size = reproduction.litter_size.min(self.carrying_capacity.saturating_sub(self.warriors.len()));
+        if litter_size == 0 {
             return ActionResult::Failed("Environment at carrying capacity".to_string());
         }
-        
-        // Create offspring
-        let mut child = parent.clone();
-        child.id = rand::random();
-        child.energy = parent.energy * 0.6; // Child gets part of parent's energy
-        child.age = 0;
-        child.fitness_score = 0.0;
-        child.genome.mutate(mutation_rate);
-        child.network = child.genome.to_network();
-        child.lineage_depth = parent.lineage_depth + 1;
+        // The litter shrinks to what the parent can pay for
+        let affordable = if reproduction.energy_cost > 0.0 {
+            (parent.energy / reproduction.energy_cost) as usize
+        } else {
+            litter_size
+        };
+        let litter_size = litter_size.min(affordable);
+        if litter_size == 0 {
+            return ActionResult::Failed("Cannot replicate - offspring cost exceeds energy".to_string());
+        }
         
         // Consume parent energy
         if let Some(parent_mut) = self.warriors.get_mut(&parent_id) {
-            parent_mut.consume_energy(40.0);
+            parent_mut.consume_energy(reproduction.energy_cost * litter_size as f32);
         }
         
-        // Place child nearby
-        let offset_distance = 20.0;
-        let offset_angle = rand::random::<f32>() * std::f32::consts::PI * 2.0;
-        child.position.0 = (child.position.0 + offset_angle.cos() * offset_distance).clamp(0.0, self.width);
-        child.position.1 = (child.position.1 + offset_angle.sin() * offset_distance).clamp(0.0, self.height);
-        
-        let child_id = child.id;
-        self.warriors.insert(child_id, child);
+        let child_energy = parent.energy * reproduction.child_energy_fraction / litter_size as f32;
+        let mut names = Vec::with_capacity(litter_size);
+        for _ in 0..litter_size {
+            // Create offspring
+            let child_id = unused_id(&mut self.mutation_rng, |id| self.warriors.contains_key(&id));
+            let mut child = parent.spawn_child_with_operators(child_id, mutation_rate, self.deletion_rate, &self.mutation_weights, &mut self.mutation_rng);
+            child.energy = child_energy;
+            child.reproduction = reproduction.clone();
+            
+            // Place child nearby
+            let offset_distance = reproduction.offspring_spawn_radius;
+            let offset_angle = self.mutation_rng.gen::<f32>() * std::f32::consts::PI * 2.0;
+            child.position = self.confine((
+                child.position.0 + offset_angle.cos() * offset_distance,
+                child.position.1 + offset_angle.sin() * offset_distance,
+            ));
+            
+            names.push(format!("{} ({})", child.id, child.name));
+            self.warriors.insert(child.id, child);
+        }
         
-        ActionResult::Success(format!("Created offspring {}", child_id))
+        ActionResult::Success(format!("Created offspring {}", names.join(", ")))
     }
     
     fn execute_sense(&mut self, _warrior_id: u32, _sensor_type: crate::neural::warrior::SensorType) -> ActionResult {
@@ -518,29 +1546,31 @@ impl Environment {
         ActionResult::Success("Sensed environment".to_string())
     }
     
-    fn generate_environmental_event(&self) -> EnvironmentEvent {
-        let mut rng = rand::thread_rng();
-        let event_types = [
-            EventType::ResourceScarcity,
-            EventType::ResourceAbundance, 
-            EventType::MemoryCompaction,
-            EventType::TerritorialShift,
-            EventType::PopulationPressure,
-            EventType::EnergeticStorm,
-        ];
+    /// Draws an event from `event_config`, or `None` when no event types
+    /// are enabled. Types turned off by the deprecated `ResourceConfig`
+    /// flags don't count as enabled.
+    fn generate_environmental_event(&mut self) -> Option<EnvironmentEvent> {
+        let rng = &mut self.event_rng;
+        let config = &self.event_config;
+        let resource_config = &self.resource_config;
+        let mut enabled = config.enabled_events.iter().filter(|&&event_type| resource_config.allows_event(event_type));
+        let enabled_count = enabled.clone().count();
+        if enabled_count == 0 {
+            return None;
+        }
         
-        let event_type = event_types[rng.gen_range(0..event_types.len())];
+        let event_type = *enabled.nth(rng.gen_range(0..enabled_count))?;
         
-        EnvironmentEvent {
+        Some(EnvironmentEvent {
             event_type,
-            duration: rng.gen_range(5..20),
-            intensity: rng.gen_range(0.3..0.8),
+            duration: sample_half_open(rng, config.duration_range),
+            intensity: sample_half_open(rng, config.intensity_range),
             affected_area: Some((
                 rng.gen_range(0.0..self.width),
                 rng.gen_range(0.0..self.height),
-                rng.gen_range(50.0..200.0),
+                sample_half_open(rng, config.area_range),
             )),
-        }
+        })
     }
     
     fn apply_environmental_event(&mut self, event: &EnvironmentEvent) {
@@ -549,8 +1579,8 @@ impl Environment {
                 let remove_count = (self.resources.len() as f32 * event.intensity * 0.3) as usize;
                 for _ in 0..remove_count {
                     if !self.resources.is_empty() {
-                        let idx = rand::thread_rng().gen_range(0..self.resources.len());
-                        self.resources.remove(idx);
+                        let idx = self.event_rng.gen_range(0..self.resources.len());
+                        self.energy_flows.destroyed += self.resources.remove(idx).energy_value;
                     }
                 }
             },
@@ -563,9 +1593,8 @@ impl Environment {
                 }
             },
             EventType::PopulationPressure => {
-                for warrior in self.warriors.values_mut() {
-                    warrior.consume_energy(event.intensity * 5.0);
-                }
+                self.event_pressure = event.intensity;
+                self.event_pressure_ticks = event.duration;
             },
             _ => {
                 // Other events affect specific areas or have complex logic
@@ -573,31 +1602,220 @@ impl Environment {
         }
     }
     
+    /// Iteratively separates warriors closer than the personal radius,
+    /// each pair moving apart equally along the line between them, then
+    /// charges every warrior for the distance it was pushed. A push that
+    /// would enter a barrier stops at its edge. Candidate
+    /// pairs come from a grid of radius-sized cells, so only neighboring
+    /// cells are compared.
+    fn resolve_collisions(&mut self) {
+        let radius = self.sensor_config.personal_radius;
+        if !self.collision_config.enabled || radius <= 0.0 || self.warriors.len() < 2 {
+            return;
+        }
+        
+        let mut pushed: BTreeMap<u32, f32> = BTreeMap::new();
+        for _ in 0..self.collision_config.passes {
+            let cell_of = |position: (f32, f32)| ((position.0 / radius).floor() as i64, (position.1 / radius).floor() as i64);
+            let mut grid: BTreeMap<(i64, i64), Vec<u32>> = BTreeMap::new();
+            for (&id, warrior) in &self.warriors {
+                grid.entry(cell_of(warrior.position)).or_default().push(id);
+            }
+            
+            let mut offsets: BTreeMap<u32, (f32, f32)> = BTreeMap::new();
+            for (&id, warrior) in &self.warriors {
+                let (cell_x, cell_y) = cell_of(warrior.position);
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        for &other_id in grid.get(&(cell_x + dx, cell_y + dy)).into_iter().flatten() {
+                            if other_id <= id {
+                                continue;
+                            }
+                            let other = &self.warriors[&other_id];
+                            let (sx, sy) = (other.position.0 - warrior.position.0, other.position.1 - warrior.position.1);
+                            let distance = (sx * sx + sy * sy).sqrt();
+                            if distance >= radius {
+                                continue;
+                            }
+                            // Stacked warriors get a direction derived from their ids
+                            let (ux, uy) = if distance > f32::EPSILON {
+                                (sx / distance, sy / distance)
+                            } else {
+                                let angle = (id ^ other_id) as f32 * 2.399_963;
+                                (angle.cos(), angle.sin())
+                            };
+                            let half = (radius - distance) / 2.0;
+                            let own = offsets.entry(id).or_insert((0.0, 0.0));
+                            *own = (own.0 - ux * half, own.1 - uy * half);
+                            let theirs = offsets.entry(other_id).or_insert((0.0, 0.0));
+                            *theirs = (theirs.0 + ux * half, theirs.1 + uy * half);
+                        }
+                    }
+                }
+            }
+            
+            if offsets.is_empty() {
+                break;
+            }
+            let (boundary_mode, size) = (self.boundary_mode, (self.width, self.height));
+            for (id, (ox, oy)) in offsets {
+                let Some(old) = self.warriors.get(&id).map(|warrior| warrior.position) else {
+                    continue;
+                };
+                // Pushes stop short of barriers, like moves do
+                let (path, _) = self.sweep_path(old, (ox, oy));
+                let new = path.last().copied().unwrap_or(old);
+                let (dx, dy) = boundary_mode.displacement(old, new, size);
+                if let Some(warrior) = self.warriors.get_mut(&id) {
+                    warrior.position = new;
+                    *pushed.entry(id).or_insert(0.0) += (dx * dx + dy * dy).sqrt();
+                }
+            }
+        }
+        
+        let cost = self.collision_config.displacement_energy_cost;
+        for (id, distance) in pushed {
+            if let Some(warrior) = self.warriors.get_mut(&id) {
+                warrior.consume_energy(distance * cost);
+            }
+        }
+    }
+    
+    /// Applies this tick's queued hits all at once, so the outcome doesn't
+    /// depend on the order warriors acted in: two warriors that land lethal
+    /// hits on each other both die. A hit interrupts the target's pending
+    /// multi-tick action. Attackers drain a share of their target's energy
+    /// as it stood before any hit landed, split in proportion to damage,
+    /// but only if they survive the exchange.
     fn process_combat(&mut self, _results: &mut ActionResults) {
-        // Combat resolution happens during action execution
-        // This could be expanded for more complex combat interactions
+        let hits = std::mem::take(&mut self.queued_hits);
+        if hits.is_empty() {
+            return;
+        }
+        
+        let mut incoming: BTreeMap<u32, f32> = BTreeMap::new();
+        for hit in &hits {
+            *incoming.entry(hit.target_id).or_insert(0.0) += hit.damage;
+        }
+        let available: BTreeMap<u32, f32> = incoming.keys()
+            .filter_map(|&id| self.warriors.get(&id).map(|target| (id, target.energy)))
+            .collect();
+        
+        for hit in &hits {
+            if let Some(target) = self.warriors.get_mut(&hit.target_id) {
+                target.consume_energy(hit.damage);
+                target.remember_attacker(hit.attacker_id, self.tick);
+            }
+            self.pending_actions.remove(&hit.target_id);
+            if let Some(attacker) = self.warriors.get_mut(&hit.attacker_id) {
+                attacker.damage_dealt += hit.damage;
+            }
+        }
+        
+        let transfer = self.action_costs.combat_energy_transfer.clamp(0.0, 1.0);
+        for hit in &hits {
+            let total = incoming[&hit.target_id];
+            if total <= 0.0 {
+                continue;
+            }
+            let drained = available.get(&hit.target_id).map_or(0.0, |&energy| energy.min(total) * hit.damage / total);
+            if let Some(attacker) = self.warriors.get_mut(&hit.attacker_id).filter(|attacker| attacker.is_alive()) {
+                attacker.gain_energy(drained * transfer);
+            }
+        }
+    }
+    
+    /// Drops claims that expired or whose claimant died or wandered off,
+    /// then lets the nearest warrior in range claim each free resource.
+    fn update_resource_claims(&mut self) {
+        let claim_radius = self.resource_config.claim_radius;
+        if claim_radius <= 0.0 {
+            return;
+        }
+        
+        let tick = self.tick;
+        let expires_at = tick + self.resource_config.claim_duration;
+        let warriors = &self.warriors;
+        let distance = |a: (f32, f32), b: (f32, f32)| ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+        
+        for resource in &mut self.resources {
+            if let Some(claim) = resource.claim {
+                let claimant_nearby = warriors.get(&claim.warrior_id)
+                    .is_some_and(|warrior| distance(warrior.position, resource.position) <= claim_radius);
+                if tick >= claim.expires_at || !claimant_nearby {
+                    resource.claim = None;
+                }
+            }
+            
+            if resource.claim.is_none() {
+                resource.claim = warriors.values()
+                    .map(|warrior| (warrior.id, distance(warrior.position, resource.position)))
+                    .filter(|&(_, d)| d <= claim_radius)
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(warrior_id, _)| ResourceClaim { warrior_id, expires_at });
+            }
+        }
     }
     
     fn process_resource_collection(&mut self, results: &mut ActionResults) {
-        let warrior_positions: Vec<(u32, (f32, f32))> = self.warriors.iter()
-            .map(|(id, warrior)| (*id, warrior.position))
+        self.update_resource_claims();
+        let claim_policy = self.resource_config.claim_policy;
+        let collection_limit = match self.resource_config.max_collections_per_tick {
+            0 => usize::MAX,
+            limit => limit,
+        };
+        
+        let mut swept_paths = std::mem::take(&mut self.swept_paths);
+        let warrior_paths: Vec<(u32, Vec<(f32, f32)>)> = self.warriors.iter()
+            .map(|(id, warrior)| {
+                let mut path = swept_paths.remove(id).unwrap_or_default();
+                path.push(warrior.position);
+                (*id, path)
+            })
             .collect();
         
-        for (warrior_id, position) in warrior_positions {
-            // Find nearby resources
-            let mut collected_resources = Vec::new();
+        let bonus_on_collection = self.resource_config.safe_zone_bonus_mode != SafeZoneBonusMode::Spawn;
+        let mut collected_resources = std::mem::take(&mut self.collected_resources);
+        for (warrior_id, path) in warrior_paths {
+            // Find resources near anywhere the warrior passed this tick
+            collected_resources.clear();
+            let zone_index = path.last().and_then(|&position| self.strongest_safe_zone_index_at(position));
+            let collection_bonus = match zone_index {
+                Some(index) if bonus_on_collection => self.safe_zones[index].resource_bonus,
+                _ => 1.0,
+            };
             
             for (i, resource) in self.resources.iter().enumerate() {
-                let distance = ((position.0 - resource.position.0).powi(2) + 
-                               (position.1 - resource.position.1).powi(2)).sqrt();
+                if collected_resources.len() >= collection_limit {
+                    break;
+                }
                 
-                if distance < 15.0 {
+                let in_reach = path.iter().any(|position| {
+                    ((position.0 - resource.position.0).powi(2) + (position.1 - resource.position.1).powi(2)).sqrt() < 15.0
+                });
+                
+                if in_reach {
+                    let claimed_by_other = resource.claim.is_some_and(|claim| claim.warrior_id != warrior_id);
+                    if claimed_by_other && claim_policy == ClaimPolicy::Block {
+                        continue;
+                    }
                     collected_resources.push(i);
                     
                     if let Some(warrior) = self.warriors.get_mut(&warrior_id) {
-                        warrior.gain_energy(resource.energy_value);
+                        match claim_policy {
+                            ClaimPolicy::Penalty { energy } if claimed_by_other => warrior.consume_energy(energy),
+                            _ => {},
+                        }
+                        let energy = resource.energy_value * collection_bonus;
+                        warrior.gain_energy(energy);
+                        self.energy_flows.injected += energy - resource.energy_value;
+                        if let Some(index) = zone_index {
+                            let stats = self.safe_zone_stats.entry(index).or_default();
+                            stats.resources_granted += 1;
+                            stats.energy_granted += energy;
+                        }
                         results.add_result(warrior_id, ActionResult::Success(
-                            format!("Collected {} energy", resource.energy_value)
+                            format!("Collected {} energy", energy)
                         ));
                     }
                 }
@@ -608,6 +1826,7 @@ impl Environment {
                 self.resources.remove(index);
             }
         }
+        self.collected_resources = collected_resources;
     }
 }
 
@@ -616,6 +1835,8 @@ pub struct EnvironmentUpdate {
     pub tick: u64,
     pub resources_spawned: usize,
     pub warriors_died: usize,
+    /// Ids of the warriors counted in `warriors_died`.
+    pub dead_warriors: Vec<u32>,
     pub environmental_event: Option<EnvironmentEvent>,
 }
 
@@ -625,16 +1846,26 @@ impl EnvironmentUpdate {
             tick,
             resources_spawned: 0,
             warriors_died: 0,
+            dead_warriors: Vec::new(),
             environmental_event: None,
         }
     }
 }
 
+/// Each warrior's chosen action for a tick, by id.
+pub type ActionMap = HashMap<u32, Action>;
+
 #[derive(Debug, Clone)]
 pub struct ActionResults {
     pub results: HashMap<u32, ActionResult>,
 }
 
+impl Default for ActionResults {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ActionResults {
     pub fn new() -> Self {
         Self {
@@ -652,6 +1883,8 @@ pub enum ActionResult {
     Success(String),
     Partial(String),
     Failed(String),
+    /// A multi-tick action that continues for `remaining` more ticks.
+    InProgress { remaining: u32 },
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,129 @@
+use crate::neural::NeuralWarrior;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineageNode {
+    pub id: u32,
+    pub parent_ids: Vec<u32>,
+    pub fitness: f32,
+    pub lineage_depth: u32,
+}
+
+/// Genealogy of every warrior observed during a run, including ones that
+/// have since died.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LineageTracker {
+    nodes: HashMap<u32, LineageNode>,
+}
+
+impl LineageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a warrior, or refreshes its fitness if it is already known.
+    pub fn record(&mut self, warrior: &NeuralWarrior) {
+        self.nodes
+            .entry(warrior.id)
+            .and_modify(|node| node.fitness = warrior.fitness_score)
+            .or_insert_with(|| LineageNode {
+                id: warrior.id,
+                parent_ids: warrior.parent_ids.clone(),
+                fitness: warrior.fitness_score,
+                lineage_depth: warrior.lineage_depth,
+            });
+    }
+
+    pub fn get(&self, id: u32) -> Option<&LineageNode> {
+        self.nodes.get(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+
+    /// Nodes sorted by id so exports are stable between calls.
+    pub fn nodes(&self) -> Vec<&LineageNode> {
+        let mut nodes: Vec<&LineageNode> = self.nodes.values().collect();
+        nodes.sort_by_key(|node| node.id);
+        nodes
+    }
+
+    /// Graphviz DOT digraph with one parent -> child edge per recorded parent.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph lineage {\n");
+        let nodes = self.nodes();
+
+        for node in &nodes {
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\nfitness={:.2}\"];\n",
+                node.id, node.id, node.fitness
+            ));
+        }
+
+        for node in &nodes {
+            for parent_id in &node.parent_ids {
+                dot.push_str(&format!("    \"{}\" -> \"{}\";\n", parent_id, node.id));
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// How the living population splits into founder lineages; see
+/// `NeuralWarrior::founder_id`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LineageSummary {
+    /// Founder of the lineage with the most living members, ties going to
+    /// the lowest founder id. `None` with no warriors.
+    pub largest_lineage: Option<u32>,
+    /// The largest lineage's share of the population, in `[0, 1]`.
+    pub largest_lineage_share: f32,
+    /// Distinct founders with at least one living descendant.
+    pub surviving_lineages: usize,
+    /// Founder of the longest-lived surviving lineage.
+    pub oldest_lineage: Option<u32>,
+    /// Generations since the oldest surviving lineage was founded.
+    pub oldest_lineage_age: u32,
+}
+
+impl LineageSummary {
+    /// `origins` maps founder ids to the generation they were founded in;
+    /// founders missing from it count as founded this `generation`.
+    pub fn from_population<'a>(
+        warriors: impl IntoIterator<Item = &'a NeuralWarrior>,
+        origins: &BTreeMap<u32, u32>,
+        generation: u32,
+    ) -> Self {
+        let mut members: BTreeMap<u32, usize> = BTreeMap::new();
+        for warrior in warriors {
+            *members.entry(warrior.founder_id).or_insert(0) += 1;
+        }
+        let population: usize = members.values().sum();
+
+        let largest = members.iter()
+            .reduce(|best, entry| if entry.1 > best.1 { entry } else { best });
+        let oldest = members.keys()
+            .map(|&founder| (founder, generation.saturating_sub(origins.get(&founder).copied().unwrap_or(generation))))
+            .reduce(|best, entry| if entry.1 > best.1 { entry } else { best });
+
+        Self {
+            largest_lineage: largest.map(|(&founder, _)| founder),
+            largest_lineage_share: largest.map_or(0.0, |(_, &count)| count as f32 / population as f32),
+            surviving_lineages: members.len(),
+            oldest_lineage: oldest.map(|(founder, _)| founder),
+            oldest_lineage_age: oldest.map_or(0, |(_, age)| age),
+        }
+    }
+}
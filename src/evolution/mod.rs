@@ -1,6 +1,10 @@
+pub mod lineage;
 pub mod population;
 pub mod selection;
 pub mod speciation;
 
-pub use population::Population;
-pub use speciation::{SpeciationManager, Species, SpeciesStats};
+pub use lineage::{LineageNode, LineageSummary, LineageTracker};
+pub use population::{EvaluationError, Population};
+pub use speciation::{
+    CrossoverStrategy, EvolutionParams, FitnessFloor, SpeciationManager, Species, SpeciesPolicyFn, SpeciesStats, SpeciesSummary,
+};
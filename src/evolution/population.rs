@@ -1,8 +1,16 @@
 use crate::neural::{Genome, NeuralNetwork};
 use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::collections::HashMap;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum EvaluationError {
+    #[error("Batch evaluator returned {actual} fitness values for {expected} genomes")]
+    FitnessCount { expected: usize, actual: usize },
+}
+
+#[derive(Debug, Clone)]
 pub struct Population {
     genomes: Vec<Genome>,
     generation: u32,
@@ -27,6 +35,15 @@ impl Population {
         }
     }
 
+    /// A population of exactly `genomes`, in order.
+    pub fn from_genomes(genomes: Vec<Genome>) -> Self {
+        Self {
+            population_size: genomes.len(),
+            genomes,
+            ..Self::new(0)
+        }
+    }
+
     pub fn evolve(&mut self) {
         let mut new_genomes = Vec::with_capacity(self.population_size);
 
@@ -47,15 +64,15 @@ impl Population {
     pub fn tournament_selection(&self) -> &Genome {
         let mut rng = rand::thread_rng();
         let mut best_genome = &self.genomes[0];
-        let mut best_fitness = best_genome.fitness();
+        let mut best_fitness = ranked_fitness(best_genome);
 
         for _ in 1..self.tournament_size {
             let candidate_idx = rng.gen_range(0..self.genomes.len());
             let candidate = &self.genomes[candidate_idx];
 
-            if candidate.fitness() > best_fitness {
+            if ranked_fitness(candidate) > best_fitness {
                 best_genome = candidate;
-                best_fitness = candidate.fitness();
+                best_fitness = ranked_fitness(candidate);
             }
         }
 
@@ -67,21 +84,53 @@ impl Population {
         F: Fn(&NeuralNetwork) -> f32,
     {
         for genome in &mut self.genomes {
-            let network = genome.to_network();
-            let fitness = fitness_fn(&network);
+            let fitness = fitness_fn(genome.network());
             genome.set_fitness(fitness);
         }
     }
 
+    /// Like `evaluate_fitness`, but hands every network to `fitness_fn` at
+    /// once, in genome order, for evaluators that score a whole population
+    /// together. Fitness is left untouched unless exactly one value comes
+    /// back per genome.
+    pub fn evaluate_fitness_batch<F>(&mut self, fitness_fn: F) -> Result<(), EvaluationError>
+    where
+        F: Fn(&[NeuralNetwork]) -> Vec<f32>,
+    {
+        let networks: Vec<NeuralNetwork> = self.genomes.iter().map(|genome| genome.network().clone()).collect();
+        let fitness = fitness_fn(&networks);
+        if fitness.len() != self.genomes.len() {
+            return Err(EvaluationError::FitnessCount { expected: self.genomes.len(), actual: fitness.len() });
+        }
+
+        for (genome, fitness) in self.genomes.iter_mut().zip(fitness) {
+            genome.set_fitness(fitness);
+        }
+        Ok(())
+    }
+
+    /// `evaluate_fitness` spread across rayon's thread pool.
+    #[cfg(feature = "parallel")]
+    pub fn par_evaluate_fitness<F>(&mut self, fitness_fn: F)
+    where
+        F: Fn(&NeuralNetwork) -> f32 + Sync,
+    {
+        self.genomes.par_iter_mut().for_each(|genome| {
+            let fitness = fitness_fn(genome.network());
+            genome.set_fitness(fitness);
+        });
+    }
+
+    /// Fittest genome, ranking NaN fitness below everything else.
     pub fn best_genome(&self) -> Option<&Genome> {
         self.genomes
             .iter()
-            .max_by(|a, b| a.fitness().partial_cmp(&b.fitness()).unwrap())
+            .max_by(|a, b| ranked_fitness(a).total_cmp(&ranked_fitness(b)))
     }
 
+    /// Mean fitness over genomes whose fitness isn't NaN; 0 with none.
     pub fn average_fitness(&self) -> f32 {
-        let total: f32 = self.genomes.iter().map(|g| g.fitness()).sum();
-        total / self.genomes.len() as f32
+        mean_ignoring_nan(self.genomes.iter().map(|g| g.fitness()))
     }
 
     pub fn diversity_score(&self) -> f32 {
@@ -116,9 +165,10 @@ impl Population {
 
     pub fn statistics(&self) -> PopulationStats {
         let fitnesses: Vec<f32> = self.genomes.iter().map(|g| g.fitness()).collect();
+        // `f32::min` and `max` already skip NaN
         let min_fitness = fitnesses.iter().fold(f32::INFINITY, |a, &b| a.min(b));
         let max_fitness = fitnesses.iter().fold(f32::NEG_INFINITY, |a, &b| a.max(b));
-        let avg_fitness = fitnesses.iter().sum::<f32>() / fitnesses.len() as f32;
+        let avg_fitness = mean_ignoring_nan(fitnesses.iter().copied());
 
         let mut lineage_counts = HashMap::new();
         for genome in &self.genomes {
@@ -145,6 +195,19 @@ impl Population {
     }
 }
 
+/// Fitness for ranking, with NaN below every number.
+fn ranked_fitness(genome: &Genome) -> f32 {
+    let fitness = genome.fitness();
+    if fitness.is_nan() { f32::NEG_INFINITY } else { fitness }
+}
+
+fn mean_ignoring_nan(values: impl Iterator<Item = f32>) -> f32 {
+    let (total, count) = values
+        .filter(|value| !value.is_nan())
+        .fold((0.0, 0), |(total, count), value| (total + value, count + 1));
+    if count == 0 { 0.0 } else { total / count as f32 }
+}
+
 #[derive(Debug, Clone)]
 pub struct PopulationStats {
     pub generation: u32,
@@ -74,7 +74,7 @@ impl SelectionStrategy for ElitistSelection {
     fn select<'a>(&self, population: &'a [Genome]) -> &'a Genome {
         population
             .iter()
-            .max_by(|a, b| a.fitness().partial_cmp(&b.fitness()).unwrap())
+            .max_by(|a, b| a.fitness().total_cmp(&b.fitness()))
             .unwrap()
     }
 }
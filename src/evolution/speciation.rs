@@ -1,6 +1,16 @@
-use crate::neural::{Genome, NeuralWarrior};
-use rand::Rng;
-use std::collections::HashMap;
+use super::lineage::LineageSummary;
+use crate::neural::{ActionKind, Genome, MutationWeights, NeuralWarrior};
+use crate::rng::unused_id;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+/// Number of speciation rounds a palette slot stays retired after its
+/// species goes extinct, so the frontend doesn't immediately recolor a
+/// newcomer with a dead species' color.
+const PALETTE_COOLDOWN_ROUNDS: u32 = 5;
 
 #[derive(Debug, Clone)]
 pub struct Species {
@@ -12,15 +22,145 @@ pub struct Species {
     pub best_fitness: f32,
     pub fitness_history: Vec<f32>,
     pub stagnation_threshold: u32,
+    pub palette_index: usize,
+    pub parent_species: Option<u32>,
+    /// Breeding parameters from the species policy, refreshed at the start
+    /// of every `perform_species_selection`.
+    pub params: EvolutionParams,
+}
+
+/// How offspring within a species combine their parents' genomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CrossoverStrategy {
+    /// Single-point crossover whenever selection picks two distinct parents.
+    #[default]
+    SinglePoint,
+    /// Every offspring is a mutated copy of one parent.
+    Asexual,
+}
+
+/// Per-species breeding parameters chosen by the species policy.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EvolutionParams {
+    /// Scales every mutation applied to the species' offspring, including
+    /// the extra mutation of asexual children. 0 disables mutation.
+    pub mutation_rate_multiplier: f32,
+    pub crossover_strategy: CrossoverStrategy,
+    /// Probability that a crossover's second parent is drawn from another
+    /// species instead of this one.
+    pub interspecies_rate: f32,
+}
+
+impl Default for EvolutionParams {
+    fn default() -> Self {
+        Self {
+            mutation_rate_multiplier: 1.0,
+            crossover_strategy: CrossoverStrategy::SinglePoint,
+            interspecies_rate: 0.0,
+        }
+    }
+}
+
+/// Callback installed with `SpeciationManager::set_species_policy`.
+pub type SpeciesPolicyFn = Box<dyn Fn(&Species) -> EvolutionParams + Send + Sync>;
+
+/// Holds the optional species policy. Shared rather than boxed so the
+/// manager stays cloneable; without one every species breeds with
+/// `EvolutionParams::default()`.
+#[derive(Clone, Default)]
+struct SpeciesPolicy(Option<SharedSpeciesPolicy>);
+
+type SharedSpeciesPolicy = Arc<dyn Fn(&Species) -> EvolutionParams + Send + Sync>;
+
+impl SpeciesPolicy {
+    fn params_for(&self, species: &Species) -> EvolutionParams {
+        self.0.as_ref().map_or_else(EvolutionParams::default, |policy| policy(species))
+    }
 }
 
-#[derive(Debug)]
+impl std::fmt::Debug for SpeciesPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.is_some() { "SpeciesPolicy(Some(..))" } else { "SpeciesPolicy(None)" })
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct SpeciationManager {
-    pub species: HashMap<u32, Species>,
+    pub species: BTreeMap<u32, Species>,
     pub compatibility_threshold: f32,
     pub species_counter: u32,
     pub target_species_count: usize,
     pub compatibility_weights: CompatibilityWeights,
+    pub auto_adjust_threshold: bool,
+    /// Probability that each offspring loses one random genome byte,
+    /// scaled by its species' mutation rate multiplier.
+    pub deletion_rate: f32,
+    /// Parsimony pressure: selection fitness is reduced by
+    /// `parsimony_coefficient * (genome size - Genome::MIN_SIZE)`.
+    pub parsimony_coefficient: f32,
+    /// Keeps the fitness species offspring are allocated by non-negative.
+    pub fitness_floor: FitnessFloor,
+    /// Largest share of the next generation any single species may claim.
+    /// Offspring above the cap are redistributed to the other species.
+    pub max_offspring_fraction: f32,
+    /// Multiplier on every species' mutation rate; raised by the simulation
+    /// while the population is behaviorally converged.
+    pub mutation_boost: f32,
+    /// Mutation rate every species starts from before its own bonuses;
+    /// set each generation from the simulation's `MutationSchedule`.
+    pub base_mutation_rate: f32,
+    /// Operator mix used when mutating offspring genomes.
+    pub mutation_weights: MutationWeights,
+    /// Candidates compared in each parent-selection tournament, capped at
+    /// the species size.
+    pub tournament_size: usize,
+    /// Draw tournament candidates with replacement, so a warrior can be
+    /// compared against itself; otherwise every candidate is distinct.
+    pub tournament_replacement: bool,
+    /// Founding species of every species ever created, keyed by species id.
+    /// Entries outlive extinction so the full species tree can be drawn.
+    pub species_lineage: HashMap<u32, Option<u32>>,
+    species_policy: SpeciesPolicy,
+    warrior_species: HashMap<u32, u32>,
+    /// Assignments from the previous round, kept while a time-sliced round
+    /// is in progress so new species can record their parent species.
+    previous_assignments: HashMap<u32, u32>,
+    round_in_progress: bool,
+    free_palette: VecDeque<usize>,
+    retired_palette: Vec<(usize, u32)>,
+    next_palette_index: usize,
+    speciation_round: u32,
+    rng: StdRng,
+}
+
+/// How selection fitness is mapped onto the non-negative values that
+/// offspring are allocated in proportion to. Tournaments within a species
+/// still compare raw selection fitness.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FitnessFloor {
+    /// Fitness below the floor counts as the floor, itself at least 0.
+    /// Species whose members all score below a zero floor get no
+    /// proportional offspring.
+    Clamp(f32),
+    /// Fitness is raised by the offset, then clamped at 0. An offset at
+    /// least as large as the worst expected penalty keeps penalized
+    /// species in proportion to each other.
+    Offset(f32),
+}
+
+impl Default for FitnessFloor {
+    fn default() -> Self {
+        FitnessFloor::Clamp(0.0)
+    }
+}
+
+impl FitnessFloor {
+    pub fn apply(&self, fitness: f32) -> f32 {
+        match *self {
+            FitnessFloor::Clamp(floor) => fitness.max(floor).max(0.0),
+            FitnessFloor::Offset(offset) => (fitness + offset).max(0.0),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -45,29 +185,90 @@ impl Default for CompatibilityWeights {
 impl SpeciationManager {
     pub fn new(target_species_count: usize) -> Self {
         Self {
-            species: HashMap::new(),
+            species: BTreeMap::new(),
             compatibility_threshold: 3.0,
             species_counter: 0,
             target_species_count,
             compatibility_weights: CompatibilityWeights::default(),
+            auto_adjust_threshold: true,
+            deletion_rate: 0.0,
+            parsimony_coefficient: 0.0,
+            fitness_floor: FitnessFloor::default(),
+            max_offspring_fraction: 1.0,
+            mutation_boost: 1.0,
+            base_mutation_rate: 0.05,
+            mutation_weights: MutationWeights::default(),
+            tournament_size: 3,
+            tournament_replacement: true,
+            species_lineage: HashMap::new(),
+            species_policy: SpeciesPolicy::default(),
+            warrior_species: HashMap::new(),
+            previous_assignments: HashMap::new(),
+            round_in_progress: false,
+            free_palette: VecDeque::new(),
+            retired_palette: Vec::new(),
+            next_palette_index: 0,
+            speciation_round: 0,
+            rng: StdRng::from_entropy(),
         }
     }
     
+    /// Reseeds the RNG used for parent selection, mutation and offspring ids.
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+    
+    /// Installs a policy choosing each species' `EvolutionParams`. It is
+    /// evaluated for every species at the start of each
+    /// `perform_species_selection`, replacing the default parameters.
+    pub fn set_species_policy(&mut self, policy: SpeciesPolicyFn) {
+        self.species_policy = SpeciesPolicy(Some(Arc::from(policy)));
+    }
+    
+    /// One-shot speciation of the whole population.
     pub fn speciate(&mut self, warriors: &[NeuralWarrior]) {
-        // Clear existing species memberships
-        for species in self.species.values_mut() {
-            species.members.clear();
+        self.begin_round();
+        self.finish_speciation(warriors);
+    }
+    
+    /// Assigns up to `batch` warriors that have no species yet this round,
+    /// in `warriors` order, starting a new round if none is in progress.
+    /// Returns how many were assigned. Spreading a round over several calls
+    /// yields the same memberships as `speciate` for the same population.
+    pub fn speciate_incremental(&mut self, warriors: &[NeuralWarrior], batch: usize) -> usize {
+        if !self.round_in_progress {
+            self.begin_round();
         }
         
-        // Assign each warrior to a species
-        for warrior in warriors {
-            let species_id = self.find_compatible_species(warrior)
-                .unwrap_or_else(|| self.create_new_species(warrior));
-            
-            if let Some(species) = self.species.get_mut(&species_id) {
-                species.members.push(warrior.id);
-            }
+        let pending: Vec<&NeuralWarrior> = warriors.iter()
+            .filter(|warrior| !self.warrior_species.contains_key(&warrior.id))
+            .take(batch)
+            .collect();
+        for warrior in &pending {
+            self.assign_species(warrior);
         }
+        pending.len()
+    }
+    
+    /// Whether a round started by `speciate_incremental` is awaiting
+    /// `finish_speciation`.
+    pub fn is_speciation_pending(&self) -> bool {
+        self.round_in_progress
+    }
+    
+    /// Completes the current round: assigns anyone still unplaced, drops
+    /// members no longer in `warriors`, then updates species statistics and
+    /// the compatibility threshold.
+    pub fn finish_speciation(&mut self, warriors: &[NeuralWarrior]) {
+        self.speciate_incremental(warriors, usize::MAX);
+        
+        let present: HashSet<u32> = warriors.iter().map(|warrior| warrior.id).collect();
+        self.warrior_species.retain(|id, _| present.contains(id));
+        for species in self.species.values_mut() {
+            species.members.retain(|id| present.contains(id));
+        }
+        self.previous_assignments.clear();
+        self.round_in_progress = false;
         
         // Update species statistics
         self.update_species_statistics(warriors);
@@ -79,42 +280,94 @@ impl SpeciationManager {
         self.adjust_compatibility_threshold();
     }
     
-    pub fn perform_species_selection(&self, warriors: &[NeuralWarrior]) -> Vec<NeuralWarrior> {
+    /// Breeds a next generation the size of `warriors`. Returns an empty
+    /// vec when there are no warriors or no populated species to select
+    /// from, e.g. before `speciate` has run.
+    pub fn perform_species_selection(&mut self, warriors: &[NeuralWarrior]) -> Vec<NeuralWarrior> {
         let mut selected = Vec::new();
+        if warriors.is_empty() || self.species.values().all(|species| species.members.is_empty()) {
+            return selected;
+        }
+        
+        let policy = self.species_policy.clone();
+        for species in self.species.values_mut() {
+            species.params = policy.params_for(species);
+        }
+        
         let total_fitness = self.calculate_total_adjusted_fitness(warriors);
+        let parsimony = self.parsimony_coefficient;
+        let tournament = (self.tournament_size, self.tournament_replacement);
+        let max_per_species = self.max_offspring_per_species(warriors.len());
         
-        for species in self.species.values() {
-            if species.members.is_empty() {
-                continue;
+        let plans: Vec<(f32, f32, EvolutionParams, Vec<&NeuralWarrior>)> = self.species.values()
+            .filter(|species| !species.members.is_empty())
+            .map(|species| {
+                let species_fitness = self.calculate_species_fitness(species, warriors);
+                let species_warriors = warriors.iter()
+                    .filter(|w| species.members.contains(&w.id))
+                    .collect();
+                (species_fitness, self.calculate_species_mutation_rate(species), species.params, species_warriors)
+            })
+            .collect();
+        
+        let species_fitness: Vec<f32> = plans.iter().map(|(fitness, _, _, _)| *fitness).collect();
+        let offspring_counts = allocate_offspring(&species_fitness, total_fitness, warriors.len(), max_per_species);
+        
+        // Slots left over after proportional allocation go to the best
+        // performers, skipping species that have already hit the cap
+        let mut species_totals = offspring_counts.clone();
+        let mut allocated: usize = offspring_counts.iter().sum();
+        let mut ranked: Vec<(usize, &NeuralWarrior)> = plans.iter().enumerate()
+            .flat_map(|(idx, (_, _, _, members))| members.iter().map(move |&warrior| (idx, warrior)))
+            .collect();
+        ranked.sort_by(|a, b| self.selection_fitness(b.1).total_cmp(&self.selection_fitness(a.1)));
+        let mut fill_parents = Vec::new();
+        for (idx, warrior) in ranked {
+            while allocated < warriors.len() && species_totals[idx] < max_per_species {
+                species_totals[idx] += 1;
+                allocated += 1;
+                fill_parents.push((warrior, plans[idx].2.mutation_rate_multiplier));
             }
-            
-            let species_fitness = self.calculate_species_fitness(species, warriors);
-            let offspring_count = ((species_fitness / total_fitness) * warriors.len() as f32) as usize;
-            
-            let species_warriors: Vec<&NeuralWarrior> = warriors.iter()
-                .filter(|w| species.members.contains(&w.id))
-                .collect();
+        }
+        
+        // Offspring ids avoid the parents' and each other's
+        let mut issued_ids: HashSet<u32> = warriors.iter().map(|warrior| warrior.id).collect();
+        for ((_, mutation_rate, params, species_warriors), offspring_count) in plans.iter().zip(offspring_counts) {
+            let outsiders: Vec<&NeuralWarrior> = if params.interspecies_rate > 0.0 {
+                warriors.iter().filter(|w| !species_warriors.iter().any(|member| member.id == w.id)).collect()
+            } else {
+                Vec::new()
+            };
             
             // Tournament selection within species
             for _ in 0..offspring_count {
-                if let Some(parent1) = self.tournament_selection_within_species(&species_warriors, 3) {
-                    let parent2 = self.tournament_selection_within_species(&species_warriors, 3)
-                        .unwrap_or(parent1);
+                if let Some(parent1) = Self::tournament_selection_within_species(species_warriors, tournament, parsimony, &mut self.rng) {
+                    let parent2 = match params.crossover_strategy {
+                        CrossoverStrategy::Asexual => parent1,
+                        CrossoverStrategy::SinglePoint => {
+                            // Only roll for an outsider when it can happen, so
+                            // the default policy leaves the RNG stream alone
+                            let pool = if !outsiders.is_empty() && self.rng.gen::<f32>() < params.interspecies_rate {
+                                &outsiders
+                            } else {
+                                species_warriors
+                            };
+                            Self::tournament_selection_within_species(pool, tournament, parsimony, &mut self.rng)
+                                .unwrap_or(parent1)
+                        },
+                    };
                     
+                    let child_id = self.generate_warrior_id(&mut issued_ids);
                     let mut child = if parent1.id != parent2.id {
-                        NeuralWarrior::from_parents(parent1, parent2, self.generate_warrior_id())
+                        NeuralWarrior::from_parents_with_rng(parent1, parent2, child_id, &mut self.rng)
                     } else {
                         // Asexual reproduction with mutation
-                        let mut child = parent1.clone();
-                        child.id = self.generate_warrior_id();
-                        child.genome.mutate(0.1);
-                        child.network = child.genome.to_network();
-                        child
+                        parent1.spawn_child_with_operators(child_id, 0.1 * params.mutation_rate_multiplier, 0.0, &self.mutation_weights, &mut self.rng)
                     };
                     
                     // Species-specific mutation rates
-                    let mutation_rate = self.calculate_species_mutation_rate(species);
-                    child.genome.mutate(mutation_rate);
+                    child.genome.mutate_with_operators(mutation_rate * params.mutation_rate_multiplier, &self.mutation_weights, &mut self.rng);
+                    child.genome.mutate_deletion_with_rng(self.deletion_rate * params.mutation_rate_multiplier, &mut self.rng);
                     child.network = child.genome.to_network();
                     
                     selected.push(child);
@@ -122,16 +375,33 @@ impl SpeciationManager {
             }
         }
         
-        // Fill remaining slots with best performers
-        while selected.len() < warriors.len() {
-            if let Some(best) = self.get_best_warrior(warriors) {
-                let mut child = best.clone();
-                child.id = self.generate_warrior_id();
-                child.genome.mutate(0.05);
-                child.network = child.genome.to_network();
+        for (parent, mutation_multiplier) in fill_parents {
+            let child_id = self.generate_warrior_id(&mut issued_ids);
+            let child = parent.spawn_child_with_operators(
+                child_id,
+                0.05 * mutation_multiplier,
+                self.deletion_rate * mutation_multiplier,
+                &self.mutation_weights,
+                &mut self.rng,
+            );
+            selected.push(child);
+        }
+        
+        // Every species is at the cap; fall back to the best performer
+        if let Some(best) = self.get_best_warrior(warriors) {
+            let mutation_multiplier = self.species_of(best.id)
+                .and_then(|species_id| self.species.get(&species_id))
+                .map_or(1.0, |species| species.params.mutation_rate_multiplier);
+            while selected.len() < warriors.len() {
+                let child_id = self.generate_warrior_id(&mut issued_ids);
+                let child = best.spawn_child_with_operators(
+                    child_id,
+                    0.05 * mutation_multiplier,
+                    self.deletion_rate * mutation_multiplier,
+                    &self.mutation_weights,
+                    &mut self.rng,
+                );
                 selected.push(child);
-            } else {
-                break;
             }
         }
         
@@ -151,9 +421,72 @@ impl SpeciationManager {
                 .filter(|s| s.generations_since_improvement > s.stagnation_threshold)
                 .count(),
             compatibility_threshold: self.compatibility_threshold,
+            species: self.species_summaries(),
+            lineage: LineageSummary::default(),
+        }
+    }
+    
+    /// Per-species summaries sorted by species id, so consumers see a stable
+    /// ordering across frames.
+    pub fn species_summaries(&self) -> Vec<SpeciesSummary> {
+        let mut summaries: Vec<SpeciesSummary> = self.species.values()
+            .map(|species| SpeciesSummary {
+                id: species.id,
+                palette_index: species.palette_index,
+                parent_species: species.parent_species,
+                member_count: species.members.len(),
+                average_fitness: species.average_fitness,
+                best_fitness: species.best_fitness,
+                params: species.params,
+                archetype: None,
+            })
+            .collect();
+        summaries.sort_by_key(|summary| summary.id);
+        summaries
+    }
+    
+    /// Species the warrior was assigned to in the most recent speciation.
+    pub fn species_of(&self, warrior_id: u32) -> Option<u32> {
+        self.warrior_species.get(&warrior_id).copied()
+    }
+    
+    fn begin_round(&mut self) {
+        self.speciation_round += 1;
+        self.previous_assignments = std::mem::take(&mut self.warrior_species);
+        self.round_in_progress = true;
+        
+        // Clear existing species memberships
+        for species in self.species.values_mut() {
+            species.members.clear();
         }
     }
     
+    fn assign_species(&mut self, warrior: &NeuralWarrior) {
+        let species_id = match self.find_compatible_species(warrior) {
+            Some(species_id) => {
+                if let Some(species) = self.species.get_mut(&species_id) {
+                    species.members.push(warrior.id);
+                }
+                species_id
+            },
+            None => {
+                let parent_species = self.parent_species_of(warrior);
+                self.create_new_species(warrior, parent_species)
+            },
+        };
+        
+        self.warrior_species.insert(warrior.id, species_id);
+    }
+    
+    /// Species a newly founded species split from: the warrior's own previous
+    /// species if it survived, otherwise the first parent's.
+    fn parent_species_of(&self, warrior: &NeuralWarrior) -> Option<u32> {
+        std::iter::once(&warrior.id)
+            .chain(&warrior.parent_ids)
+            .find_map(|id| self.previous_assignments.get(id).or_else(|| self.warrior_species.get(id)))
+            .copied()
+    }
+    
     fn find_compatible_species(&self, warrior: &NeuralWarrior) -> Option<u32> {
         for (species_id, species) in &self.species {
             if self.calculate_compatibility_distance(warrior, &species.representative) < self.compatibility_threshold {
@@ -163,9 +496,11 @@ impl SpeciationManager {
         None
     }
     
-    fn create_new_species(&mut self, warrior: &NeuralWarrior) -> u32 {
+    fn create_new_species(&mut self, warrior: &NeuralWarrior, parent_species: Option<u32>) -> u32 {
+        // Ids come from a monotonic counter and are never reused within a run
         self.species_counter += 1;
         let species_id = self.species_counter;
+        let palette_index = self.allocate_palette_index();
         
         let species = Species {
             id: species_id,
@@ -176,12 +511,30 @@ impl SpeciationManager {
             best_fitness: warrior.fitness_score,
             fitness_history: vec![warrior.fitness_score],
             stagnation_threshold: 15,
+            palette_index,
+            parent_species,
+            params: EvolutionParams::default(),
         };
         
         self.species.insert(species_id, species);
+        self.species_lineage.insert(species_id, parent_species);
         species_id
     }
     
+    fn allocate_palette_index(&mut self) -> usize {
+        let round = self.speciation_round;
+        let (ready, cooling): (Vec<_>, Vec<_>) = self.retired_palette.drain(..)
+            .partition(|&(_, released_at)| round >= released_at + PALETTE_COOLDOWN_ROUNDS);
+        self.retired_palette = cooling;
+        self.free_palette.extend(ready.into_iter().map(|(index, _)| index));
+        
+        self.free_palette.pop_front().unwrap_or_else(|| {
+            let index = self.next_palette_index;
+            self.next_palette_index += 1;
+            index
+        })
+    }
+    
     fn calculate_compatibility_distance(&self, warrior: &NeuralWarrior, representative: &Genome) -> f32 {
         let weights = &self.compatibility_weights;
         
@@ -203,11 +556,11 @@ impl SpeciationManager {
                 .filter(|w| species.members.contains(&w.id))
                 .collect();
             
-            let total_fitness: f32 = species_warriors.iter().map(|w| w.fitness_score).sum();
+            let total_fitness: f32 = species_warriors.iter().map(|w| finite_fitness(w)).sum();
             species.average_fitness = total_fitness / species_warriors.len() as f32;
             
             let max_fitness = species_warriors.iter()
-                .map(|w| w.fitness_score)
+                .map(|w| finite_fitness(w))
                 .fold(f32::NEG_INFINITY, f32::max);
             
             if max_fitness > species.best_fitness {
@@ -216,7 +569,7 @@ impl SpeciationManager {
                 
                 // Update representative to best member
                 if let Some(best_warrior) = species_warriors.iter()
-                    .max_by(|a, b| a.fitness_score.partial_cmp(&b.fitness_score).unwrap()) {
+                    .max_by(|a, b| finite_fitness(a).total_cmp(&finite_fitness(b))) {
                     species.representative = best_warrior.genome.clone();
                 }
             } else {
@@ -237,11 +590,17 @@ impl SpeciationManager {
             .collect();
         
         for species_id in empty_species {
-            self.species.remove(&species_id);
+            if let Some(species) = self.species.remove(&species_id) {
+                self.retired_palette.push((species.palette_index, self.speciation_round));
+            }
         }
     }
     
     fn adjust_compatibility_threshold(&mut self) {
+        if !self.auto_adjust_threshold {
+            return;
+        }
+        
         let current_count = self.species.len();
         
         if current_count < self.target_species_count {
@@ -268,7 +627,9 @@ impl SpeciationManager {
             return 0.0;
         }
         
-        let total_fitness: f32 = species_warriors.iter().map(|w| w.fitness_score).sum();
+        let total_fitness: f32 = species_warriors.iter()
+            .map(|w| self.fitness_floor.apply(self.selection_fitness(w)))
+            .sum();
         let adjusted_fitness = total_fitness / species_warriors.len() as f32;
         
         // Apply stagnation penalty
@@ -279,20 +640,33 @@ impl SpeciationManager {
         }
     }
     
-    fn tournament_selection_within_species<'a>(&self, species_warriors: &[&'a NeuralWarrior], tournament_size: usize) -> Option<&'a NeuralWarrior> {
+    /// Best of `tournament_size` candidates by selection fitness, drawn with
+    /// or without replacement as `(tournament_size, replacement)` says.
+    fn tournament_selection_within_species<'a>(
+        species_warriors: &[&'a NeuralWarrior],
+        (tournament_size, replacement): (usize, bool),
+        parsimony_coefficient: f32,
+        rng: &mut StdRng,
+    ) -> Option<&'a NeuralWarrior> {
         if species_warriors.is_empty() {
             return None;
         }
         
-        let mut rng = rand::thread_rng();
         let mut best: Option<&NeuralWarrior> = None;
         let mut best_fitness = f32::NEG_INFINITY;
         
-        for _ in 0..tournament_size.min(species_warriors.len()) {
-            let candidate = species_warriors[rng.gen_range(0..species_warriors.len())];
-            if candidate.fitness_score > best_fitness {
+        let rounds = tournament_size.max(1).min(species_warriors.len());
+        let candidates: Vec<usize> = if replacement {
+            (0..rounds).map(|_| rng.gen_range(0..species_warriors.len())).collect()
+        } else {
+            rand::seq::index::sample(rng, species_warriors.len(), rounds).into_vec()
+        };
+        for index in candidates {
+            let candidate = species_warriors[index];
+            let candidate_fitness = selection_fitness(candidate, parsimony_coefficient);
+            if candidate_fitness > best_fitness {
                 best = Some(candidate);
-                best_fitness = candidate.fitness_score;
+                best_fitness = candidate_fitness;
             }
         }
         
@@ -300,26 +674,137 @@ impl SpeciationManager {
     }
     
     fn calculate_species_mutation_rate(&self, species: &Species) -> f32 {
-        let base_rate = 0.05;
+        let base_rate = self.base_mutation_rate;
         let stagnation_bonus = (species.generations_since_improvement as f32 / species.stagnation_threshold as f32) * 0.1;
         let diversity_penalty = if species.members.len() < 5 { 0.02 } else { 0.0 };
         
-        (base_rate + stagnation_bonus + diversity_penalty).min(0.5)
+        ((base_rate + stagnation_bonus + diversity_penalty) * self.mutation_boost).min(0.5)
+    }
+    
+    /// Like `species_summaries`, also filling in each species' archetype
+    /// from the action histories of its members found in `warriors`.
+    pub fn species_summaries_for(&self, warriors: &[NeuralWarrior]) -> Vec<SpeciesSummary> {
+        let by_id: HashMap<u32, &NeuralWarrior> = warriors.iter().map(|warrior| (warrior.id, warrior)).collect();
+        let mut summaries = self.species_summaries();
+        for summary in &mut summaries {
+            let mut totals = [0.0f32; ActionKind::ALL.len()];
+            for warrior in self.species[&summary.id].members.iter().filter_map(|id| by_id.get(id)) {
+                for (total, frequency) in totals.iter_mut().zip(warrior.action_frequencies()) {
+                    *total += frequency;
+                }
+            }
+            // Ties go to the earlier kind in `ActionKind::ALL`
+            summary.archetype = ActionKind::ALL.iter().zip(totals)
+                .filter(|&(_, total)| total > 0.0)
+                .fold(None, |best: Option<(ActionKind, f32)>, (&kind, total)| match best {
+                    Some((_, best_total)) if best_total >= total => best,
+                    _ => Some((kind, total)),
+                })
+                .map(|(kind, _)| kind);
+        }
+        summaries
     }
     
     fn get_best_warrior<'a>(&self, warriors: &'a [NeuralWarrior]) -> Option<&'a NeuralWarrior> {
-        warriors.iter().max_by(|a, b| a.fitness_score.partial_cmp(&b.fitness_score).unwrap())
+        warriors.iter().max_by(|a, b| self.selection_fitness(a).total_cmp(&self.selection_fitness(b)))
+    }
+    
+    fn max_offspring_per_species(&self, population: usize) -> usize {
+        ((self.max_offspring_fraction.clamp(0.0, 1.0) * population as f32) as usize).max(1)
+    }
+    
+    fn selection_fitness(&self, warrior: &NeuralWarrior) -> f32 {
+        selection_fitness(warrior, self.parsimony_coefficient)
     }
     
-    fn generate_warrior_id(&self) -> u32 {
-        rand::random()
+    /// An id outside `issued_ids`, which it then joins.
+    fn generate_warrior_id(&mut self, issued_ids: &mut HashSet<u32>) -> u32 {
+        let id = unused_id(&mut self.rng, |id| issued_ids.contains(&id));
+        issued_ids.insert(id);
+        id
     }
 }
 
-#[derive(Debug, Clone)]
+/// Offspring per species proportional to fitness, with no species above
+/// `max_per_species`. Offspring a capped species would have received are
+/// shared among the remaining species in proportion to their fitness.
+fn allocate_offspring(species_fitness: &[f32], total_fitness: f32, population: usize, max_per_species: usize) -> Vec<usize> {
+    let mut counts = vec![0; species_fitness.len()];
+    // Without positive fitness there is nothing to be proportional to;
+    // every slot is left to the ranked fill
+    if total_fitness <= 0.0 || !total_fitness.is_finite() {
+        return counts;
+    }
+    
+    let mut capped = vec![false; species_fitness.len()];
+    let mut budget = population as f32;
+    let mut open_fitness = total_fitness;
+    
+    loop {
+        let newly_capped: Vec<usize> = (0..species_fitness.len())
+            .filter(|&idx| !capped[idx] && species_fitness[idx] / open_fitness * budget > max_per_species as f32)
+            .collect();
+        if newly_capped.is_empty() {
+            break;
+        }
+        
+        for idx in newly_capped {
+            capped[idx] = true;
+            counts[idx] = max_per_species;
+            budget -= max_per_species as f32;
+            open_fitness -= species_fitness[idx];
+        }
+        if budget <= 0.0 || open_fitness <= 0.0 {
+            return counts;
+        }
+    }
+    
+    for (idx, &fitness) in species_fitness.iter().enumerate() {
+        if !capped[idx] {
+            counts[idx] = ((fitness / open_fitness) * budget) as usize;
+        }
+    }
+    counts
+}
+
+fn selection_fitness(warrior: &NeuralWarrior, parsimony_coefficient: f32) -> f32 {
+    let excess_size = warrior.genome.size().saturating_sub(Genome::MIN_SIZE) as f32;
+    finite_fitness(warrior) - parsimony_coefficient * excess_size
+}
+
+/// A warrior's fitness with NaN and infinities read as zero, so one bad
+/// score can't poison species averages or rankings.
+fn finite_fitness(warrior: &NeuralWarrior) -> f32 {
+    if warrior.fitness_score.is_finite() { warrior.fitness_score } else { 0.0 }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpeciesStats {
     pub species_count: usize,
     pub average_species_size: f32,
     pub stagnant_species: usize,
     pub compatibility_threshold: f32,
-}
\ No newline at end of file
+    pub species: Vec<SpeciesSummary>,
+    /// Founder lineages across the whole population. Filled in by
+    /// `NeuralArenaSimulation::get_species_stats`; the speciation manager
+    /// alone leaves it empty.
+    #[serde(default)]
+    pub lineage: LineageSummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeciesSummary {
+    pub id: u32,
+    pub palette_index: usize,
+    pub parent_species: Option<u32>,
+    pub member_count: usize,
+    pub average_fitness: f32,
+    pub best_fitness: f32,
+    /// Breeding parameters used in the most recent selection.
+    #[serde(default)]
+    pub params: EvolutionParams,
+    /// The members' most common action over their recorded histories;
+    /// `None` when no member histories were available.
+    #[serde(default)]
+    pub archetype: Option<ActionKind>,
+}
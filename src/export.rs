@@ -0,0 +1,197 @@
+//! Exporters from run data to formats external tools read: Newick for
+//! phylogenies, Graphviz DOT for the species tree and, with the `arrow`
+//! feature, Arrow record batches and Parquet files for statistics.
+
+use crate::evolution::{LineageNode, LineageTracker, SpeciationManager};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// The recorded genealogy as a Newick tree.
+///
+/// Schema: every label is a warrior id. A warrior's children are the
+/// warriors whose first parent it is; second crossover parents are not
+/// represented. Branch lengths are lineage-depth differences (generations
+/// of descent). Warriors whose first parent was never recorded are roots;
+/// several roots hang off an unlabeled root with branch lengths equal to
+/// their lineage depth. An empty tracker exports as `;`.
+pub fn to_newick(lineage: &LineageTracker) -> String {
+    let nodes = lineage.nodes();
+    let known: HashSet<u32> = nodes.iter().map(|node| node.id).collect();
+    let mut children: BTreeMap<u32, Vec<&LineageNode>> = BTreeMap::new();
+    let mut roots = Vec::new();
+    for node in &nodes {
+        match node.parent_ids.first() {
+            Some(parent) if known.contains(parent) && *parent != node.id => {
+                children.entry(*parent).or_default().push(node);
+            },
+            _ => roots.push(*node),
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut subtrees: Vec<String> = roots.iter()
+        .map(|root| newick_subtree(root, &children, &mut visited))
+        .collect();
+    // Ids can repeat across a long run, closing a parent cycle with no
+    // root; export whatever the roots didn't reach as extra roots
+    for node in &nodes {
+        if !visited.contains(&node.id) {
+            subtrees.push(newick_subtree(node, &children, &mut visited));
+            roots.push(*node);
+        }
+    }
+
+    match subtrees.len() {
+        0 => ";".to_string(),
+        1 => format!("{};", subtrees[0]),
+        _ => {
+            let branches: Vec<String> = subtrees.iter().zip(&roots)
+                .map(|(subtree, root)| format!("{}:{}", subtree, root.lineage_depth))
+                .collect();
+            format!("({});", branches.join(","))
+        },
+    }
+}
+
+fn newick_subtree(node: &LineageNode, children: &BTreeMap<u32, Vec<&LineageNode>>, visited: &mut HashSet<u32>) -> String {
+    visited.insert(node.id);
+    let mut branches = Vec::new();
+    for child in children.get(&node.id).into_iter().flatten() {
+        if !visited.contains(&child.id) {
+            let length = child.lineage_depth.saturating_sub(node.lineage_depth);
+            branches.push(format!("{}:{}", newick_subtree(child, children, visited), length));
+        }
+    }
+
+    if branches.is_empty() {
+        node.id.to_string()
+    } else {
+        format!("({}){}", branches.join(","), node.id)
+    }
+}
+
+/// The species tree as a Graphviz DOT digraph.
+///
+/// Schema: one node per species ever created, `"s<id>"`, labeled with its
+/// id and, while alive, its member count and average fitness; extinct
+/// species are drawn dashed. One `parent -> child` edge per species founded
+/// from another.
+pub fn to_dot(speciation: &SpeciationManager) -> String {
+    let species_ids: BTreeSet<u32> = speciation.species_lineage.keys()
+        .chain(speciation.species.keys())
+        .copied()
+        .collect();
+
+    let mut dot = String::from("digraph species {\n");
+    for id in &species_ids {
+        match speciation.species.get(id) {
+            Some(species) => dot.push_str(&format!(
+                "    \"s{}\" [label=\"species {}\\nmembers={}\\nfitness={:.2}\"];\n",
+                id, id, species.members.len(), species.average_fitness
+            )),
+            None => dot.push_str(&format!("    \"s{}\" [label=\"species {}\", style=dashed];\n", id, id)),
+        }
+    }
+    for id in &species_ids {
+        let parent = speciation.species_lineage.get(id).copied().flatten()
+            .or_else(|| speciation.species.get(id).and_then(|species| species.parent_species));
+        if let Some(parent) = parent {
+            dot.push_str(&format!("    \"s{}\" -> \"s{}\";\n", parent, id));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(feature = "arrow")]
+pub use self::columnar::{statistics_schema, statistics_to_arrow, statistics_to_parquet};
+
+#[cfg(feature = "arrow")]
+mod columnar {
+    use crate::simulation::SimulationStatistics;
+    use arrow::array::{ArrayRef, Float32Array, UInt32Array, UInt64Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::error::ArrowError;
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+    use parquet::errors::ParquetError;
+    use std::io::Write;
+    use std::sync::Arc;
+
+    /// How a column reads its value from a snapshot.
+    #[derive(Clone, Copy)]
+    enum Column {
+        U32(fn(&SimulationStatistics) -> u32),
+        U64(fn(&SimulationStatistics) -> u64),
+        F32(fn(&SimulationStatistics) -> f32),
+    }
+
+    const COLUMNS: [(&str, Column); 26] = [
+        ("generation", Column::U32(|s| s.generation)),
+        ("tick", Column::U64(|s| s.tick)),
+        ("population_size", Column::U64(|s| s.population_size as u64)),
+        ("species_count", Column::U64(|s| s.species_count as u64)),
+        ("average_fitness", Column::F32(|s| s.average_fitness)),
+        ("max_fitness", Column::F32(|s| s.max_fitness)),
+        ("diversity_score", Column::F32(|s| s.diversity_score)),
+        ("survival_rate", Column::F32(|s| s.survival_rate)),
+        ("average_age", Column::F32(|s| s.average_age)),
+        ("max_lineage_depth", Column::U32(|s| s.max_lineage_depth)),
+        ("computational_efficiency", Column::F32(|s| s.computational_efficiency)),
+        ("rounds_per_second", Column::F32(|s| s.rounds_per_second)),
+        ("resource_utilization", Column::F32(|s| s.resource_utilization)),
+        ("environmental_pressure", Column::F32(|s| s.environmental_pressure)),
+        ("mean_genome_size", Column::F32(|s| s.mean_genome_size)),
+        ("dominant_action_share", Column::F32(|s| s.dominant_action_share)),
+        ("behavioral_entropy", Column::F32(|s| s.behavioral_entropy)),
+        ("behavior_variance", Column::F32(|s| s.behavior_variance)),
+        ("largest_lineage_share", Column::F32(|s| s.largest_lineage_share)),
+        ("surviving_lineages", Column::U64(|s| s.surviving_lineages as u64)),
+        ("oldest_lineage_age", Column::U32(|s| s.oldest_lineage_age)),
+        ("warrior_energy", Column::F32(|s| s.warrior_energy)),
+        ("resource_energy", Column::F32(|s| s.resource_energy)),
+        ("energy_injected", Column::F32(|s| s.energy_injected)),
+        ("energy_destroyed", Column::F32(|s| s.energy_destroyed)),
+        ("biomass_turnover", Column::F32(|s| s.biomass_turnover)),
+    ];
+
+    /// One non-nullable column per `SimulationStatistics` field, named
+    /// after it and in declaration order: `u32` fields as UInt32, `u64` and
+    /// `usize` fields as UInt64 and `f32` fields as Float32.
+    pub fn statistics_schema() -> Schema {
+        let fields: Vec<Field> = COLUMNS.iter()
+            .map(|(name, column)| {
+                let data_type = match column {
+                    Column::U32(_) => DataType::UInt32,
+                    Column::U64(_) => DataType::UInt64,
+                    Column::F32(_) => DataType::Float32,
+                };
+                Field::new(*name, data_type, false)
+            })
+            .collect();
+        Schema::new(fields)
+    }
+
+    /// One row per snapshot, in `statistics_schema` layout.
+    pub fn statistics_to_arrow(statistics: &[SimulationStatistics]) -> Result<RecordBatch, ArrowError> {
+        let columns: Vec<ArrayRef> = COLUMNS.iter()
+            .map(|(_, column)| -> ArrayRef {
+                match *column {
+                    Column::U32(value) => Arc::new(UInt32Array::from_iter_values(statistics.iter().map(value))),
+                    Column::U64(value) => Arc::new(UInt64Array::from_iter_values(statistics.iter().map(value))),
+                    Column::F32(value) => Arc::new(Float32Array::from_iter_values(statistics.iter().map(value))),
+                }
+            })
+            .collect();
+        RecordBatch::try_new(Arc::new(statistics_schema()), columns)
+    }
+
+    /// Writes the snapshots to `writer` as an uncompressed Parquet file with
+    /// the `statistics_schema` columns.
+    pub fn statistics_to_parquet<W: Write + Send>(statistics: &[SimulationStatistics], writer: W) -> Result<(), ParquetError> {
+        let batch = statistics_to_arrow(statistics)?;
+        let mut parquet = ArrowWriter::try_new(writer, batch.schema(), None)?;
+        parquet.write(&batch)?;
+        parquet.close()?;
+        Ok(())
+    }
+}
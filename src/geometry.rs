@@ -0,0 +1,53 @@
+//! Arena features shared by the environment and the warriors sensing it.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryBarrier {
+    pub position: (f32, f32),
+    pub width: f32,
+    pub height: f32,
+    pub strength: f32,
+}
+
+impl MemoryBarrier {
+    /// Whether `point` lies inside the barrier, edges included.
+    pub fn contains(&self, point: (f32, f32)) -> bool {
+        (self.position.0..=self.position.0 + self.width).contains(&point.0)
+            && (self.position.1..=self.position.1 + self.height).contains(&point.1)
+    }
+    
+    /// Whether the segment from `from` to `to` touches the barrier, edges
+    /// included.
+    pub fn intersects_segment(&self, from: (f32, f32), to: (f32, f32)) -> bool {
+        let axes = [
+            (from.0, to.0 - from.0, self.position.0, self.position.0 + self.width),
+            (from.1, to.1 - from.1, self.position.1, self.position.1 + self.height),
+        ];
+        // Clip the segment's parameter range against each slab in turn
+        let (mut enter, mut exit) = (0.0f32, 1.0f32);
+        for (origin, delta, low, high) in axes {
+            if delta == 0.0 {
+                if origin < low || origin > high {
+                    return false;
+                }
+                continue;
+            }
+            let (t0, t1) = ((low - origin) / delta, (high - origin) / delta);
+            enter = enter.max(t0.min(t1));
+            exit = exit.min(t0.max(t1));
+            if enter > exit {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafeZone {
+    pub center: (f32, f32),
+    pub radius: f32,
+    pub protection_level: f32,
+    pub resource_bonus: f32,
+}
@@ -3,9 +3,25 @@ pub mod memory;
 pub mod neural;
 pub mod vm;
 pub mod environment;
+pub mod geometry;
+pub mod delta;
 pub mod simulation;
+pub mod presets;
+pub mod diagnostics;
+pub mod statistics;
+pub mod report;
+pub mod duel;
 pub mod wasm_api;
+pub mod state_diff;
+pub mod export;
+pub mod rng;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "async")]
+pub mod driver;
 
 pub use vm::VirtualMachine;
 pub use environment::Environment;
-pub use simulation::{NeuralArenaSimulation, SimulationConfig};
+pub use simulation::{ConfigError, MutationSchedule, NeuralArenaSimulation, ObjectiveKind, SimulationConfig, SimulationOutcome, TickObserverFn};
+pub use report::RunReport;
+pub use diagnostics::SimulationWarning;
@@ -1,10 +1,15 @@
 use super::territory::{Territory, TerritoryError};
-use std::collections::HashMap;
-
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Splits `total_size` bytes into equal territories of `territory_size`
+/// bytes. When the size doesn't divide evenly, the leftover bytes at the
+/// end stay unpartitioned: no territory covers them, so nobody can own or
+/// access them. `unpartitioned_bytes` reports how many there are.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryAllocator {
-    #[allow(dead_code)]
     total_size: usize,
+    territory_size: usize,
     territories: Vec<Territory>,
     owner_territories: HashMap<u32, Vec<usize>>,
     free_territories: Vec<usize>,
@@ -18,11 +23,27 @@ pub enum AllocationError {
     Territory(#[from] TerritoryError),
     #[error("Invalid territory ID: {id}")]
     InvalidTerritory { id: usize },
+    #[error("Territory size {territory_size} must be between 1 and the total size {total_size}")]
+    InvalidTerritorySize { territory_size: usize, total_size: usize },
+    #[error("Inconsistent allocator state: {reason}")]
+    Inconsistent { reason: String },
+    #[error("Invalid allocator snapshot: {0}")]
+    Snapshot(#[from] serde_json::Error),
 }
 
 type AllocationResult<T> = Result<T, AllocationError>;
 
 impl MemoryAllocator {
+    /// Validating constructor: rejects a zero territory size and one larger
+    /// than `total_size`, which would leave no territories at all.
+    pub fn try_new(total_size: usize, territory_size: usize) -> AllocationResult<Self> {
+        if territory_size == 0 || territory_size > total_size {
+            return Err(AllocationError::InvalidTerritorySize { territory_size, total_size });
+        }
+        Ok(Self::new(total_size, territory_size))
+    }
+
+    /// Panics if `territory_size` is zero; see `try_new`.
     pub fn new(total_size: usize, territory_size: usize) -> Self {
         let territory_count = total_size / territory_size;
         let mut territories = Vec::with_capacity(territory_count);
@@ -35,12 +56,72 @@ impl MemoryAllocator {
 
         Self {
             total_size,
+            territory_size,
             territories,
             owner_territories: HashMap::new(),
             free_territories,
         }
     }
 
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Restores an allocator saved with `to_json`, rejecting snapshots that
+    /// fail `validate_invariants`.
+    pub fn from_json(json: &str) -> AllocationResult<Self> {
+        let allocator: Self = serde_json::from_str(json)?;
+        allocator.validate_invariants()?;
+        Ok(allocator)
+    }
+
+    /// Checks that the free list, owner map and territories agree: every
+    /// territory is either free and unowned or owned and listed under its
+    /// owner, never both, and nothing is listed twice.
+    pub fn validate_invariants(&self) -> AllocationResult<()> {
+        let inconsistent = |reason: String| Err(AllocationError::Inconsistent { reason });
+        if self.partitioned_size() > self.total_size {
+            return inconsistent(format!(
+                "{} territories of {} bytes exceed the total size {}",
+                self.territories.len(), self.territory_size, self.total_size
+            ));
+        }
+
+        let mut free = HashSet::new();
+        for &id in &self.free_territories {
+            match self.territories.get(id) {
+                None => return inconsistent(format!("free territory {} does not exist", id)),
+                Some(territory) if territory.owner().is_some() => {
+                    return inconsistent(format!("territory {} is both free and owned", id));
+                },
+                Some(_) if !free.insert(id) => return inconsistent(format!("territory {} is free twice", id)),
+                Some(_) => {},
+            }
+        }
+
+        let mut listed = HashSet::new();
+        for (&owner_id, ids) in &self.owner_territories {
+            for &id in ids {
+                if self.territories.get(id).and_then(Territory::owner) != Some(owner_id) {
+                    return inconsistent(format!("territory {} is listed under {} but not owned by it", id, owner_id));
+                }
+                if !listed.insert(id) {
+                    return inconsistent(format!("territory {} is listed twice", id));
+                }
+            }
+        }
+
+        for (id, territory) in self.territories.iter().enumerate() {
+            if territory.owner().is_none() && !free.contains(&id) {
+                return inconsistent(format!("territory {} is neither free nor owned", id));
+            }
+            if territory.owner().is_some() && !listed.contains(&id) {
+                return inconsistent(format!("territory {} is owned but missing from its owner's list", id));
+            }
+        }
+        Ok(())
+    }
+
     pub fn allocate_territory(&mut self, owner_id: u32) -> AllocationResult<usize> {
         let territory_id =
             self.free_territories
@@ -101,19 +182,66 @@ impl MemoryAllocator {
             .unwrap_or_default()
     }
 
+    /// Every territory as `(id, owner, resource density, size)`, in
+    /// address order.
+    pub fn territory_map(&self) -> Vec<(usize, Option<u32>, f32, usize)> {
+        self.territories.iter()
+            .enumerate()
+            .map(|(id, territory)| (id, territory.owner(), territory.resource_density(), territory.size()))
+            .collect()
+    }
+
     pub fn available_territories(&self) -> usize {
         self.free_territories.len()
     }
 
+    /// True when no territory is left to allocate.
+    pub fn is_saturated(&self) -> bool {
+        self.free_territories.is_empty()
+    }
+
     pub fn total_territories(&self) -> usize {
         self.territories.len()
     }
 
+    pub fn total_size(&self) -> usize {
+        self.total_size
+    }
+
+    /// Bytes in each territory; territory `i` starts at `i * territory_size`.
+    pub fn territory_size(&self) -> usize {
+        self.territory_size
+    }
+
+    /// Bytes covered by territories.
+    pub fn partitioned_size(&self) -> usize {
+        self.territories.len() * self.territory_size
+    }
+
+    /// Leftover bytes past the last territory.
+    pub fn unpartitioned_bytes(&self) -> usize {
+        self.total_size - self.partitioned_size()
+    }
+
+    /// Share of territories that are owned.
     pub fn memory_utilization(&self) -> f32 {
+        if self.territories.is_empty() {
+            return 0.0;
+        }
         let used = self.territories.len() - self.free_territories.len();
         used as f32 / self.territories.len() as f32
     }
 
+    /// Owned bytes as a share of `total_size`, so unpartitioned bytes
+    /// count as unused.
+    pub fn byte_utilization(&self) -> f32 {
+        if self.total_size == 0 {
+            return 0.0;
+        }
+        let used = self.territories.len() - self.free_territories.len();
+        (used * self.territory_size) as f32 / self.total_size as f32
+    }
+
     fn find_territory_for_address(&self, address: usize) -> Option<&Territory> {
         self.territories
             .iter()
@@ -1,5 +1,5 @@
 pub mod allocator;
 pub mod territory;
 
-pub use allocator::MemoryAllocator;
+pub use allocator::{AllocationError, MemoryAllocator};
 pub use territory::Territory;
@@ -0,0 +1,88 @@
+//! Schemes for turning genome bytes into network parameters.
+//!
+//! A network's parameters are numbered weights first, then biases, in the
+//! order `NeuralNetwork::from_parameter_fn` fills them. Codecs only decide
+//! the value of each numbered parameter; the topology is fixed by the
+//! caller. They read `Genome::network_bytes`, leaving the temperature gene
+//! out of the network.
+
+use super::genome::Genome;
+use super::network::NeuralNetwork;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+pub trait GenomeCodec {
+    /// Builds a `layer_sizes` network from `genome`. Must be deterministic:
+    /// the same genome bytes always give the same network.
+    fn decode(&self, genome: &Genome, layer_sizes: &[usize]) -> NeuralNetwork;
+}
+
+/// Ignores the genome; every parameter is zero.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlankCodec;
+
+impl GenomeCodec for BlankCodec {
+    fn decode(&self, _genome: &Genome, layer_sizes: &[usize]) -> NeuralNetwork {
+        NeuralNetwork::new(layer_sizes.to_vec())
+    }
+}
+
+/// Parameter `i` is network byte `i % byte count`, mapped linearly onto
+/// `[-1, 1]`. Networks with more parameters than the genome has bytes
+/// reuse the bytes cyclically.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectCodec;
+
+impl GenomeCodec for DirectCodec {
+    fn decode(&self, genome: &Genome, layer_sizes: &[usize]) -> NeuralNetwork {
+        let bytes = genome.network_bytes();
+        NeuralNetwork::from_parameter_fn(layer_sizes.to_vec(), |index| match bytes.len() {
+            0 => 0.0,
+            len => bytes[index % len] as f32 / 127.5 - 1.0,
+        })
+    }
+}
+
+/// Treats the genome as a seed: its network bytes are hashed into a PRNG
+/// seed and every parameter is drawn uniformly from `[-1, 1]`. Any network
+/// byte change yields an unrelated network.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SeedCodec;
+
+impl SeedCodec {
+    /// FNV-1a over the genome's network bytes.
+    pub fn seed(genome: &Genome) -> u64 {
+        genome.network_bytes().iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+}
+
+impl GenomeCodec for SeedCodec {
+    fn decode(&self, genome: &Genome, layer_sizes: &[usize]) -> NeuralNetwork {
+        let mut rng = StdRng::seed_from_u64(Self::seed(genome));
+        NeuralNetwork::from_parameter_fn(layer_sizes.to_vec(), |_| rng.gen_range(-1.0..=1.0))
+    }
+}
+
+/// Serializable choice of built-in codec, carried by each genome so
+/// offspring decode the same way as their parents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GenomeCodecKind {
+    /// `BlankCodec`, the original behavior.
+    #[default]
+    Blank,
+    Direct,
+    Seed,
+}
+
+impl GenomeCodecKind {
+    pub fn codec(&self) -> &'static dyn GenomeCodec {
+        match self {
+            GenomeCodecKind::Blank => &BlankCodec,
+            GenomeCodecKind::Direct => &DirectCodec,
+            GenomeCodecKind::Seed => &SeedCodec,
+        }
+    }
+}
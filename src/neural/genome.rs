@@ -1,5 +1,87 @@
+use super::codec::GenomeCodecKind;
 use super::network::NeuralNetwork;
+use super::warrior::{Action, EnvironmentSensors};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Ways a single genome byte can be mutated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MutationOperator {
+    /// Overwrite the byte with a fresh random value.
+    Replace,
+    /// Flip one random bit.
+    BitFlip,
+    /// Nudge the value by 1..=8 up or down, wrapping.
+    Creep,
+    /// Exchange the byte with another random position.
+    Swap,
+    /// Reverse the segment between the byte and another random position.
+    Reverse,
+}
+
+impl MutationOperator {
+    pub const ALL: [MutationOperator; 5] = [
+        MutationOperator::Replace,
+        MutationOperator::BitFlip,
+        MutationOperator::Creep,
+        MutationOperator::Swap,
+        MutationOperator::Reverse,
+    ];
+}
+
+/// Relative probability of each `MutationOperator`; weights need not sum
+/// to 1. The default only replaces bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MutationWeights {
+    pub replace: f32,
+    pub bit_flip: f32,
+    pub creep: f32,
+    pub swap: f32,
+    pub reverse: f32,
+}
+
+impl Default for MutationWeights {
+    fn default() -> Self {
+        Self {
+            replace: 1.0,
+            bit_flip: 0.0,
+            creep: 0.0,
+            swap: 0.0,
+            reverse: 0.0,
+        }
+    }
+}
+
+impl MutationWeights {
+    pub fn weight(&self, operator: MutationOperator) -> f32 {
+        let weight = match operator {
+            MutationOperator::Replace => self.replace,
+            MutationOperator::BitFlip => self.bit_flip,
+            MutationOperator::Creep => self.creep,
+            MutationOperator::Swap => self.swap,
+            MutationOperator::Reverse => self.reverse,
+        };
+        weight.max(0.0)
+    }
+
+    /// Draws an operator in proportion to the weights, falling back to
+    /// `Replace` when every weight is zero.
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> MutationOperator {
+        let total: f32 = MutationOperator::ALL.iter().map(|&operator| self.weight(operator)).sum();
+        let mut roll = rng.gen::<f32>() * total;
+        for operator in MutationOperator::ALL {
+            let weight = self.weight(operator);
+            if weight > 0.0 && roll < weight {
+                return operator;
+            }
+            roll -= weight;
+        }
+        MutationOperator::Replace
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Genome {
@@ -7,14 +89,28 @@ pub struct Genome {
     fitness: f32,
     generation: u32,
     lineage_id: u32,
+    /// Decoded network, filled on first use and cleared whenever `data`
+    /// changes.
+    #[serde(skip)]
+    network: OnceLock<NeuralNetwork>,
+    /// How `to_network` decodes the bytes; inherited through crossover.
+    #[serde(default)]
+    codec: GenomeCodecKind,
 }
 
 impl Genome {
     pub const MAX_SIZE: usize = 64;
+    pub const MIN_SIZE: usize = 32;
+    /// Topology of the warrior network every genome decodes to.
+    pub const NETWORK_LAYER_SIZES: [usize; 3] = [EnvironmentSensors::INPUT_SIZE, 16, Action::OUTPUT_SIZE];
 
     pub fn new_random() -> Self {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        Self::random_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Like `new_random`, drawing from the supplied RNG so seeded runs are
+    /// reproducible.
+    pub fn random_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
         let size = rng.gen_range(32..=Self::MAX_SIZE);
         let data = (0..size).map(|_| rng.gen()).collect();
 
@@ -23,36 +119,76 @@ impl Genome {
             fitness: 0.0,
             generation: 0,
             lineage_id: rng.gen(),
+            network: OnceLock::new(),
+            codec: GenomeCodecKind::default(),
         }
     }
 
+    /// Seed `canonical_set` draws from. Changing it invalidates benchmark
+    /// baselines.
+    const CANONICAL_SEED: u64 = 0xa2e4_a5ee;
+
+    /// `n` random genomes that are identical on every call, for benchmark
+    /// populations whose results compare across runs. Smaller sets are
+    /// prefixes of larger ones.
+    pub fn canonical_set(n: usize) -> Vec<Self> {
+        let mut rng = StdRng::seed_from_u64(Self::CANONICAL_SEED);
+        (0..n).map(|_| Self::random_with_rng(&mut rng)).collect()
+    }
+
     pub fn from_network(network: &NeuralNetwork, generation: u32, lineage_id: u32) -> Self {
-        let mut data = Vec::new();
+        let mut data = Vec::with_capacity(Self::MIN_SIZE);
         data.extend_from_slice(&(network.parameter_count() as u16).to_le_bytes());
+        data.resize(Self::MIN_SIZE, 0);
 
         Self {
             data,
             fitness: 0.0,
             generation,
             lineage_id,
+            network: OnceLock::new(),
+            codec: GenomeCodecKind::Blank,
         }
     }
 
     pub fn to_network(&self) -> NeuralNetwork {
-        let layer_sizes = vec![8, 16, 4];
-        NeuralNetwork::new(layer_sizes)
+        self.network().clone()
+    }
+    
+    /// The decoded network, decoded at most once per genome state.
+    pub fn network(&self) -> &NeuralNetwork {
+        self.network.get_or_init(|| self.codec.codec().decode(self, &Self::NETWORK_LAYER_SIZES))
+    }
+
+    pub fn codec(&self) -> GenomeCodecKind {
+        self.codec
+    }
+
+    pub fn set_codec(&mut self, codec: GenomeCodecKind) {
+        self.codec = codec;
+        self.network.take();
     }
 
     pub fn crossover(&self, other: &Self) -> Self {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let crossover_point = rng.gen_range(1..self.data.len().min(other.data.len()));
+        self.crossover_with_rng(other, &mut rand::thread_rng())
+    }
+
+    pub fn crossover_with_rng<R: Rng + ?Sized>(&self, other: &Self, rng: &mut R) -> Self {
+        let shortest = self.data.len().min(other.data.len());
 
-        let mut child_data = self.data[..crossover_point].to_vec();
-        child_data.extend_from_slice(&other.data[crossover_point..]);
+        let mut child_data = if shortest < 2 {
+            // No interior crossover point exists; inherit the longer parent
+            if self.data.len() >= other.data.len() { self.data.clone() } else { other.data.clone() }
+        } else {
+            let crossover_point = rng.gen_range(1..shortest);
+            let mut data = self.data[..crossover_point].to_vec();
+            data.extend_from_slice(&other.data[crossover_point..]);
+            data
+        };
 
-        if child_data.len() > Self::MAX_SIZE {
-            child_data.truncate(Self::MAX_SIZE);
+        child_data.truncate(Self::MAX_SIZE);
+        if child_data.len() < Self::MIN_SIZE {
+            child_data.resize(Self::MIN_SIZE, 0);
         }
 
         Self {
@@ -60,20 +196,69 @@ impl Genome {
             fitness: 0.0,
             generation: self.generation.max(other.generation) + 1,
             lineage_id: rng.gen(),
+            network: OnceLock::new(),
+            codec: self.codec,
         }
     }
 
     pub fn mutate(&mut self, rate: f32) {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        self.mutate_with_rng(rate, &mut rand::thread_rng());
+    }
+
+    /// Byte-replacement mutation: each byte is replaced with probability `rate`.
+    pub fn mutate_with_rng<R: Rng + ?Sized>(&mut self, rate: f32, rng: &mut R) {
+        self.mutate_with_operators(rate, &MutationWeights::default(), rng);
+    }
 
-        for byte in &mut self.data {
+    /// Mutates each byte with probability `rate`, using an operator drawn
+    /// from `weights` for every mutation.
+    pub fn mutate_with_operators<R: Rng + ?Sized>(&mut self, rate: f32, weights: &MutationWeights, rng: &mut R) {
+        self.network.take();
+        for index in 0..self.data.len() {
             if rng.gen::<f32>() < rate {
-                *byte = rng.gen();
+                self.apply_mutation(index, weights.choose(rng), rng);
             }
         }
     }
 
+    fn apply_mutation<R: Rng + ?Sized>(&mut self, index: usize, operator: MutationOperator, rng: &mut R) {
+        match operator {
+            MutationOperator::Replace => self.data[index] = rng.gen(),
+            MutationOperator::BitFlip => self.data[index] ^= 1 << rng.gen_range(0..8),
+            MutationOperator::Creep => {
+                let step = rng.gen_range(1..=8);
+                self.data[index] = if rng.gen() {
+                    self.data[index].wrapping_add(step)
+                } else {
+                    self.data[index].wrapping_sub(step)
+                };
+            },
+            MutationOperator::Swap => {
+                let other = rng.gen_range(0..self.data.len());
+                self.data.swap(index, other);
+            },
+            MutationOperator::Reverse => {
+                let other = rng.gen_range(0..self.data.len());
+                self.data[index.min(other)..=index.max(other)].reverse();
+            },
+        }
+    }
+
+    /// Deletion mutation: with the given probability removes one random
+    /// byte, never shrinking the genome below `MIN_SIZE`. A zero probability
+    /// leaves the RNG untouched.
+    pub fn mutate_deletion(&mut self, probability: f32) {
+        self.mutate_deletion_with_rng(probability, &mut rand::thread_rng());
+    }
+
+    pub fn mutate_deletion_with_rng<R: Rng + ?Sized>(&mut self, probability: f32, rng: &mut R) {
+        if probability > 0.0 && self.data.len() > Self::MIN_SIZE && rng.gen::<f32>() < probability {
+            let index = rng.gen_range(0..self.data.len());
+            self.data.remove(index);
+            self.network.take();
+        }
+    }
+
     pub fn fitness(&self) -> f32 {
         self.fitness
     }
@@ -90,7 +275,22 @@ impl Genome {
         self.lineage_id
     }
 
+    /// Exploration temperature gene in `[0, 1]`, read from the last byte,
+    /// which codecs never decode.
+    pub fn temperature_gene(&self) -> f32 {
+        self.data.last().map(|&byte| byte as f32 / 255.0).unwrap_or(1.0)
+    }
+
+    /// The bytes codecs decode: everything but the temperature gene.
+    pub fn network_bytes(&self) -> &[u8] {
+        &self.data[..self.data.len().saturating_sub(1)]
+    }
+
     pub fn size(&self) -> usize {
         self.data.len()
     }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.data
+    }
 }
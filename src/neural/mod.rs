@@ -1,7 +1,10 @@
+pub mod codec;
 pub mod genome;
+pub mod naming;
 pub mod network;
 pub mod warrior;
 
-pub use genome::Genome;
-pub use network::NeuralNetwork;
-pub use warrior::{NeuralWarrior, Action, EnvironmentSensors, EnvironmentState, Resource, Territory};
+pub use codec::{BlankCodec, DirectCodec, GenomeCodec, GenomeCodecKind, SeedCodec};
+pub use genome::{Genome, MutationOperator, MutationWeights};
+pub use network::{LayerView, NetworkError, NetworkExport, NeuralNetwork};
+pub use warrior::{NeuralWarrior, Action, ActionKind, BehaviorStats, EnvironmentSensors, EnvironmentState, ReproductionConfig, SensorConfig, Resource, ResourceClaim, ResourceGrid, Territory};
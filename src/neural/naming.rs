@@ -0,0 +1,54 @@
+//! Human-readable warrior names.
+//!
+//! Founders get `Adjective-Noun-xxxx`, derived from a hash of their id. The
+//! part after the given name (`Noun-xxxx`) is the surname: descendants keep
+//! it and add their own given name and a generation suffix, e.g.
+//! `Amber-Forager-1a2b-G3`, so a dynasty can be followed by eye.
+
+const ADJECTIVES: [&str; 16] = [
+    "Crimson", "Amber", "Azure", "Silent", "Swift", "Iron", "Golden", "Pale",
+    "Feral", "Bright", "Hollow", "Jade", "Rusty", "Lunar", "Ashen", "Wild",
+];
+
+const NOUNS: [&str; 16] = [
+    "Forager", "Hunter", "Warden", "Drifter", "Sentinel", "Raider", "Weaver", "Stalker",
+    "Nomad", "Brawler", "Seeker", "Herald", "Scout", "Tinker", "Shade", "Ranger",
+];
+
+/// Deterministic 32-bit mix of `id`, so neighbouring ids get unrelated names.
+fn name_hash(id: u32) -> u32 {
+    let mut hash = id.wrapping_add(0x9E37_79B9);
+    hash = (hash ^ (hash >> 16)).wrapping_mul(0x85EB_CA6B);
+    hash = (hash ^ (hash >> 13)).wrapping_mul(0xC2B2_AE35);
+    hash ^ (hash >> 16)
+}
+
+fn given_name(hash: u32) -> &'static str {
+    ADJECTIVES[(hash % ADJECTIVES.len() as u32) as usize]
+}
+
+/// Name for a warrior with no parents.
+pub fn founder_name(id: u32) -> String {
+    let hash = name_hash(id);
+    let noun = NOUNS[((hash >> 8) % NOUNS.len() as u32) as usize];
+    format!("{}-{}-{:04x}", given_name(hash), noun, hash >> 16)
+}
+
+/// Name for a descendant carrying `surname`, `generation` steps from its founder.
+pub fn descendant_name(id: u32, surname: &str, generation: u32) -> String {
+    format!("{}-{}-G{}", given_name(name_hash(id)), surname, generation)
+}
+
+/// The inherited part of `name`: everything but the given name and any
+/// generation suffix. Names without a given name are surnames themselves.
+pub fn surname_of(name: &str) -> &str {
+    let without_suffix = match name.rsplit_once("-G") {
+        Some((rest, generation)) if !generation.is_empty() && generation.bytes().all(|b| b.is_ascii_digit()) => rest,
+        _ => name,
+    };
+
+    match without_suffix.split_once('-') {
+        Some((_, surname)) if !surname.is_empty() => surname,
+        _ => without_suffix,
+    }
+}
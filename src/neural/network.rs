@@ -7,7 +7,90 @@ pub struct NeuralNetwork {
     layer_sizes: Vec<usize>,
 }
 
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum NetworkError {
+    #[error("Network topology has no layers")]
+    EmptyTopology,
+    #[error("Layer {layer} has no neurons")]
+    EmptyLayer { layer: usize },
+    #[error("Expected {expected} inputs, got {actual}")]
+    InputSize { expected: usize, actual: usize },
+    #[error("Network JSON parse error: {0}")]
+    Parse(String),
+    #[error("Unsupported network format version {version}")]
+    UnsupportedVersion { version: u32 },
+    #[error("Unsupported activation function '{name}'")]
+    UnsupportedActivation { name: String },
+    #[error("Expected {expected} {kind}, got {actual}")]
+    ParameterCount { kind: &'static str, expected: usize, actual: usize },
+    #[error("Network topology has more parameters than fit in memory")]
+    TopologyTooLarge,
+}
+
+/// Portable description of a trained network, produced by
+/// `NeuralNetwork::export_json`.
+///
+/// Evaluate it layer by layer: for each layer `l > 0` and neuron `j`,
+/// `out[j] = activation(biases[b + j] + sum_i in[i] * weights[w + i * n_l + j])`,
+/// where `n_l` is `layer_sizes[l]` and `w`/`b` are the running offsets of
+/// the weights and biases of the layers before it. `activation` is
+/// `"tanh"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetworkExport {
+    pub format_version: u32,
+    pub activation: String,
+    pub layer_sizes: Vec<usize>,
+    pub weights: Vec<f32>,
+    pub biases: Vec<f32>,
+}
+
+impl NetworkExport {
+    pub const FORMAT_VERSION: u32 = 1;
+    pub const ACTIVATION: &'static str = "tanh";
+}
+
+/// Parameters feeding one non-input layer, as returned by
+/// `NeuralNetwork::layers`. `weights[from][to]` connects neuron `from` of
+/// the previous layer to neuron `to` of this one, so the layer computes
+/// `tanh(inputs @ weights + biases)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerView {
+    pub input_size: usize,
+    pub output_size: usize,
+    pub weights: Vec<Vec<f32>>,
+    pub biases: Vec<f32>,
+}
+
+impl LayerView {
+    /// `(input_size, output_size)`, the shape of `weights`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.input_size, self.output_size)
+    }
+}
+
 impl NeuralNetwork {
+    /// Validating constructor. Zero-width hidden layers are dropped, so a
+    /// topology like `[8, 0, 4]` connects inputs straight to outputs; an
+    /// empty topology or an empty input/output layer is rejected.
+    pub fn try_new(layer_sizes: Vec<usize>) -> Result<Self, NetworkError> {
+        let last = match layer_sizes.len() {
+            0 => return Err(NetworkError::EmptyTopology),
+            len => len - 1,
+        };
+        if layer_sizes[0] == 0 {
+            return Err(NetworkError::EmptyLayer { layer: 0 });
+        }
+        if layer_sizes[last] == 0 {
+            return Err(NetworkError::EmptyLayer { layer: last });
+        }
+
+        let layer_sizes = layer_sizes.iter().enumerate()
+            .filter(|&(idx, &size)| size > 0 || idx == 0 || idx == last)
+            .map(|(_, &size)| size)
+            .collect();
+        Ok(Self::new(layer_sizes))
+    }
+
     pub fn new(layer_sizes: Vec<usize>) -> Self {
         let total_weights = layer_sizes.windows(2).map(|pair| pair[0] * pair[1]).sum();
         let total_biases = layer_sizes.iter().skip(1).sum();
@@ -19,32 +102,194 @@ impl NeuralNetwork {
         }
     }
 
+    /// A `layer_sizes` network whose parameters are numbered weights first,
+    /// then biases, each layer in order, and set to `value(index)`.
+    pub fn from_parameter_fn(layer_sizes: Vec<usize>, mut value: impl FnMut(usize) -> f32) -> Self {
+        let mut network = Self::new(layer_sizes);
+        let weight_count = network.weights.len();
+        for (index, weight) in network.weights.iter_mut().enumerate() {
+            *weight = value(index);
+        }
+        for (index, bias) in network.biases.iter_mut().enumerate() {
+            *bias = value(weight_count + index);
+        }
+        network
+    }
+
+    pub fn export_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&NetworkExport {
+            format_version: NetworkExport::FORMAT_VERSION,
+            activation: NetworkExport::ACTIVATION.to_string(),
+            layer_sizes: self.layer_sizes.clone(),
+            weights: self.weights.clone(),
+            biases: self.biases.clone(),
+        })
+    }
+
+    /// Rebuilds a network from `export_json` output. Every layer must be
+    /// non-empty and the weight and bias counts must match the topology.
+    /// The counts are checked before anything is allocated, so an untrusted
+    /// topology can't exhaust memory.
+    pub fn import_json(json: &str) -> Result<Self, NetworkError> {
+        let export: NetworkExport = serde_json::from_str(json)
+            .map_err(|error| NetworkError::Parse(error.to_string()))?;
+        if export.format_version != NetworkExport::FORMAT_VERSION {
+            return Err(NetworkError::UnsupportedVersion { version: export.format_version });
+        }
+        if export.activation != NetworkExport::ACTIVATION {
+            return Err(NetworkError::UnsupportedActivation { name: export.activation });
+        }
+        if export.layer_sizes.is_empty() {
+            return Err(NetworkError::EmptyTopology);
+        }
+        if let Some(layer) = export.layer_sizes.iter().position(|&size| size == 0) {
+            return Err(NetworkError::EmptyLayer { layer });
+        }
+
+        let total_weights = export.layer_sizes.windows(2)
+            .try_fold(0usize, |total, pair| total.checked_add(pair[0].checked_mul(pair[1])?))
+            .ok_or(NetworkError::TopologyTooLarge)?;
+        let total_biases = export.layer_sizes.iter().skip(1)
+            .try_fold(0usize, |total, &size| total.checked_add(size))
+            .ok_or(NetworkError::TopologyTooLarge)?;
+        for (kind, expected, actual) in [
+            ("weights", total_weights, export.weights.len()),
+            ("biases", total_biases, export.biases.len()),
+        ] {
+            if expected != actual {
+                return Err(NetworkError::ParameterCount { kind, expected, actual });
+            }
+        }
+
+        Ok(Self {
+            weights: export.weights,
+            biases: export.biases,
+            layer_sizes: export.layer_sizes,
+        })
+    }
+
+    /// Like `forward`, but rejects inputs that don't match the input layer
+    /// instead of padding or truncating them.
+    pub fn infer(&self, inputs: &[f32]) -> Result<Vec<f32>, NetworkError> {
+        let expected = self.layer_sizes.first().copied().unwrap_or(0);
+        if inputs.len() != expected {
+            return Err(NetworkError::InputSize { expected, actual: inputs.len() });
+        }
+        Ok(self.forward(inputs))
+    }
+
+    /// Output layer for `inputs`. Inputs are fitted to the input layer:
+    /// missing ones read as zero and extra ones are ignored, so callers
+    /// built for a smaller sensor array keep working. Use `infer` to reject
+    /// a mismatched length instead.
     pub fn forward(&self, inputs: &[f32]) -> Vec<f32> {
-        let mut activations = inputs.to_vec();
+        let mut outputs = Vec::new();
+        self.forward_into(inputs, &mut outputs);
+        outputs
+    }
+
+    /// Like `forward`, but writes the output layer into `out`. `out` also
+    /// serves as scratch space for the hidden layers, so once it has grown
+    /// to fit the widest pair of adjacent layers, reusing it allocates
+    /// nothing.
+    pub fn forward_into(&self, inputs: &[f32], out: &mut Vec<f32>) {
+        let input_size = self.layer_sizes.first().copied().unwrap_or(inputs.len());
+        out.clear();
+        out.extend(inputs.iter().take(input_size).copied().map(finite_or_zero));
+        out.resize(input_size, 0.0);
         let mut weight_idx = 0;
         let mut bias_idx = 0;
 
         for layer_idx in 1..self.layer_sizes.len() {
             let prev_size = self.layer_sizes[layer_idx - 1];
             let curr_size = self.layer_sizes[layer_idx];
-            let mut next_activations = vec![0.0; curr_size];
+            // Previous activations first, the layer being computed after them
+            out.resize(prev_size + curr_size, 0.0);
+            let (activations, next_activations) = out.split_at_mut(prev_size);
+            self.compute_layer(weight_idx, bias_idx, activations, next_activations);
 
-            #[allow(clippy::needless_range_loop)]
-            for j in 0..curr_size {
-                let mut sum = self.biases[bias_idx + j];
-                #[allow(clippy::needless_range_loop)]
-                for i in 0..prev_size {
-                    sum += activations[i] * self.weights[weight_idx + i * curr_size + j];
-                }
-                next_activations[j] = self.activation_function(sum);
-            }
+            weight_idx += prev_size * curr_size;
+            bias_idx += curr_size;
+            out.copy_within(prev_size.., 0);
+            out.truncate(curr_size);
+        }
+    }
+
+    /// Activations of every layer, input layer first. Missing inputs read
+    /// as zero and extra inputs are ignored. Non-finite activations (from
+    /// NaN or infinite inputs, weights or biases) read as zero.
+    pub fn layer_activations(&self, inputs: &[f32]) -> Vec<Vec<f32>> {
+        let input_size = self.layer_sizes.first().copied().unwrap_or(inputs.len());
+        let mut activations: Vec<f32> = inputs.iter().copied().map(finite_or_zero).collect();
+        activations.resize(input_size, 0.0);
+        let mut layers = Vec::with_capacity(self.layer_sizes.len());
+        let mut weight_idx = 0;
+        let mut bias_idx = 0;
+
+        for layer_idx in 1..self.layer_sizes.len() {
+            let prev_size = self.layer_sizes[layer_idx - 1];
+            let curr_size = self.layer_sizes[layer_idx];
+            let mut next_activations = vec![0.0; curr_size];
+            self.compute_layer(weight_idx, bias_idx, &activations, &mut next_activations);
 
             weight_idx += prev_size * curr_size;
             bias_idx += curr_size;
-            activations = next_activations;
+            layers.push(std::mem::replace(&mut activations, next_activations));
+        }
+
+        layers.push(activations);
+        layers
+    }
+
+    /// Fills `next` with the layer fed by `activations`, reading its weights
+    /// from `weight_idx` and its biases from `bias_idx`. Shared by
+    /// `forward_into` and `layer_activations` so they can't drift apart.
+    fn compute_layer(&self, weight_idx: usize, bias_idx: usize, activations: &[f32], next: &mut [f32]) {
+        let curr_size = next.len();
+        for (j, slot) in next.iter_mut().enumerate() {
+            let mut sum = self.biases[bias_idx + j];
+            for (i, activation) in activations.iter().enumerate() {
+                sum += activation * self.weights[weight_idx + i * curr_size + j];
+            }
+            *slot = finite_or_zero(self.activation_function(sum));
+        }
+    }
+
+    pub fn layer_sizes(&self) -> &[usize] {
+        &self.layer_sizes
+    }
+
+    /// Weight of the connection from neuron `from` in layer `layer` to
+    /// neuron `to` in layer `layer + 1`, if such a connection exists.
+    pub fn weight(&self, layer: usize, from: usize, to: usize) -> Option<f32> {
+        let prev_size = *self.layer_sizes.get(layer)?;
+        let curr_size = *self.layer_sizes.get(layer + 1)?;
+        if from >= prev_size || to >= curr_size {
+            return None;
         }
 
-        activations
+        let offset: usize = self.layer_sizes.windows(2).take(layer).map(|pair| pair[0] * pair[1]).sum();
+        self.weights.get(offset + from * curr_size + to).copied()
+    }
+
+    /// Weight matrices and bias vectors of every layer after the input
+    /// layer, in order.
+    pub fn layers(&self) -> Vec<LayerView> {
+        let mut weight_offset = 0;
+        let mut bias_offset = 0;
+        self.layer_sizes.windows(2)
+            .map(|pair| {
+                let (input_size, output_size) = (pair[0], pair[1]);
+                let weights = self.weights[weight_offset..weight_offset + input_size * output_size]
+                    .chunks(output_size)
+                    .map(<[f32]>::to_vec)
+                    .collect();
+                let biases = self.biases[bias_offset..bias_offset + output_size].to_vec();
+                weight_offset += input_size * output_size;
+                bias_offset += output_size;
+                LayerView { input_size, output_size, weights, biases }
+            })
+            .collect()
     }
 
     pub fn parameter_count(&self) -> usize {
@@ -74,3 +319,7 @@ impl NeuralNetwork {
         (2.0 / (1.0 + (-2.0 * x).exp())) - 1.0
     }
 }
+
+fn finite_or_zero(value: f32) -> f32 {
+    if value.is_finite() { value } else { 0.0 }
+}
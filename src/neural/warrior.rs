@@ -1,20 +1,80 @@
-use super::{Genome, NeuralNetwork};
+use super::{naming, Genome, MutationWeights, NeuralNetwork};
+use crate::geometry::{MemoryBarrier, SafeZone};
 use crate::vm::{Instruction, OpCode, VirtualMachine};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NeuralWarrior {
     pub id: u32,
+    /// Display name; see `naming` for the scheme. Descendants inherit the
+    /// surname.
+    #[serde(default)]
+    pub name: String,
     pub genome: Genome,
     pub network: NeuralNetwork,
     pub position: (f32, f32),
+    /// Facing in radians, in `[0, 2π)`; set by the last move.
+    #[serde(default)]
+    pub heading: f32,
+    /// Whether Move outputs steer relative to `heading` rather than in
+    /// absolute arena directions. Stamped by the environment.
+    #[serde(default)]
+    pub relative_steering: bool,
+    /// Consecutive moves that ran into a barrier; reset by an unobstructed
+    /// move.
+    #[serde(default)]
+    pub blocked_moves: u32,
     pub energy: f32,
     pub age: u32,
     pub territory_id: Option<usize>,
+    /// Positions at the start of recent ticks, oldest first; holds at most
+    /// `POSITION_HISTORY_CAPACITY` entries.
+    #[serde(default)]
+    pub position_history: VecDeque<(f32, f32)>,
+    /// Arena territories owned as of the last territory update.
+    #[serde(default)]
+    pub territories_owned: u32,
     pub action_history: VecDeque<Action>,
+    /// Most recent actions kept in `action_history`; 0 disables recording.
+    #[serde(default = "default_action_history_capacity")]
+    pub action_history_capacity: usize,
+    /// Who recently damaged this warrior, oldest first, as
+    /// `(attacker id, tick of the hit)`. Holds at most
+    /// `RECENT_ATTACKER_CAPACITY` distinct attackers.
+    #[serde(default)]
+    pub recent_attackers: VecDeque<(u32, u64)>,
+    /// Replication rules in effect for this warrior, stamped by the
+    /// environment it lives in.
+    #[serde(default)]
+    pub reproduction: ReproductionConfig,
     pub fitness_score: f32,
     pub lineage_depth: u32,
+    /// Id of the founder this warrior descends from; founders are their
+    /// own. Crossover children follow their first parent.
+    #[serde(default)]
+    pub founder_id: u32,
+    pub damage_dealt: f32,
+    pub behavior: BehaviorStats,
+    pub parent_ids: Vec<u32>,
+}
+
+/// Running per-warrior behavior counters, reset for offspring.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorStats {
+    /// Report-only; stays 0 in fast mode.
+    pub compute_spent: u64,
+    pub compute_saved: u64,
+    /// Report-only; stays 0 in fast mode.
+    pub compute_overdraft: u64,
+    /// Territory-ticks held: one per owned arena territory per tick.
+    #[serde(default)]
+    pub territory_ticks: u64,
+    /// Resting ticks (Rest, or a tick of DeepRest) in a row; reset by any
+    /// other action.
+    #[serde(default)]
+    pub consecutive_rests: u32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -25,9 +85,80 @@ pub enum Action {
     Replicate { mutation_rate: f32 },
     Sense { sensor_type: SensorType },
     Rest,
+    /// Winds up for a tick, then strikes for double damage.
+    ChargedAttack { target_direction: f32, strength: f32 },
+    /// Three ticks of triple-rate recovery.
+    DeepRest,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Payload-free discriminant of an `Action`, for tallies and reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ActionKind {
+    Move,
+    Attack,
+    Defend,
+    Replicate,
+    Sense,
+    Rest,
+    ChargedAttack,
+    DeepRest,
+}
+
+impl ActionKind {
+    pub const ALL: [ActionKind; 8] = [
+        ActionKind::Move,
+        ActionKind::Attack,
+        ActionKind::Defend,
+        ActionKind::Replicate,
+        ActionKind::Sense,
+        ActionKind::Rest,
+        ActionKind::ChargedAttack,
+        ActionKind::DeepRest,
+    ];
+}
+
+/// Layout of the network's output layer: one selection score per
+/// selectable action, followed by dedicated parameter outputs so an
+/// action's parameters don't depend on how strongly it was chosen.
+impl Action {
+    /// Scores for Move, Attack, Defend, Replicate, ChargedAttack and
+    /// DeepRest, in that order.
+    pub const SELECTION_OUTPUTS: usize = 6;
+    pub const MOVE_DIRECTION_OUTPUT: usize = 6;
+    pub const MOVE_INTENSITY_OUTPUT: usize = 7;
+    /// Shared by Attack and ChargedAttack.
+    pub const ATTACK_DIRECTION_OUTPUT: usize = 8;
+    pub const ATTACK_STRENGTH_OUTPUT: usize = 9;
+    pub const SHIELD_STRENGTH_OUTPUT: usize = 10;
+    pub const MUTATION_RATE_OUTPUT: usize = 11;
+    /// Number of values produced by the network's output layer.
+    pub const OUTPUT_SIZE: usize = 12;
+    
+    pub fn kind(&self) -> ActionKind {
+        match self {
+            Action::Move { .. } => ActionKind::Move,
+            Action::Attack { .. } => ActionKind::Attack,
+            Action::Defend { .. } => ActionKind::Defend,
+            Action::Replicate { .. } => ActionKind::Replicate,
+            Action::Sense { .. } => ActionKind::Sense,
+            Action::Rest => ActionKind::Rest,
+            Action::ChargedAttack { .. } => ActionKind::ChargedAttack,
+            Action::DeepRest => ActionKind::DeepRest,
+        }
+    }
+    
+    /// Ticks the warrior stays committed to this action, including the
+    /// tick it was chosen.
+    pub fn duration(&self) -> u32 {
+        match self {
+            Action::ChargedAttack { .. } => 2,
+            Action::DeepRest => 3,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum SensorType {
     Energy,
     NeighborProximity,
@@ -37,6 +168,42 @@ pub enum SensorType {
     Threat,
     Age,
     LineageDepth,
+    BarrierProximity,
+    SafeZoneProximity,
+    EdgeProximity,
+    NeighborGrudge,
+    ResourceContested,
+    Crowding,
+    ResourceDistance,
+    ResourceBearing,
+    Speed,
+    Straightness,
+    ResourceGradient,
+}
+
+impl SensorType {
+    /// Every sensor, in network input order.
+    pub const ALL: [SensorType; EnvironmentSensors::INPUT_SIZE] = [
+        SensorType::Energy,
+        SensorType::NeighborProximity,
+        SensorType::ResourceDensity,
+        SensorType::TerritoryPressure,
+        SensorType::Population,
+        SensorType::Threat,
+        SensorType::Age,
+        SensorType::LineageDepth,
+        SensorType::BarrierProximity,
+        SensorType::SafeZoneProximity,
+        SensorType::EdgeProximity,
+        SensorType::NeighborGrudge,
+        SensorType::ResourceContested,
+        SensorType::Crowding,
+        SensorType::ResourceDistance,
+        SensorType::ResourceBearing,
+        SensorType::Speed,
+        SensorType::Straightness,
+        SensorType::ResourceGradient,
+    ];
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,78 +216,499 @@ pub struct EnvironmentSensors {
     pub threat_level: f32,
     pub age_normalized: f32,
     pub lineage_depth_normalized: f32,
+    pub barrier_proximity: f32,
+    pub safe_zone_proximity: f32,
+    pub edge_proximity: f32,
+    /// 1.0 when the nearest neighbor recently attacked this warrior.
+    pub neighbor_grudge: f32,
+    /// 1.0 when the nearest resource is claimed by another warrior.
+    pub resource_contested: f32,
+    /// Warriors inside the personal radius, saturating at 1.0 for five.
+    pub crowding: f32,
+    /// Distance to the nearest resource within `resource_radius` as a
+    /// fraction of that radius; 1.0 when none is in range.
+    pub resource_distance: f32,
+    /// Angle from the heading to that resource mapped to `[0, 1]`, with
+    /// 0.5 dead ahead (and when none is in range).
+    pub resource_bearing: f32,
+    /// Path length over the position history as a fraction of the
+    /// longest possible move each tick.
+    pub speed: f32,
+    /// Net displacement over the position history divided by the path
+    /// length: near 1 traveling straight, near 0 oscillating or stuck.
+    pub straightness: f32,
+    /// Direction in which resources grow denser within
+    /// `resource_gradient_radius`, relative to the heading and mapped to
+    /// `[0, 1]` like `resource_bearing`; 0.5 when the field is flat.
+    pub resource_gradient: f32,
+}
+
+impl EnvironmentSensors {
+    /// Number of values fed to the network's input layer.
+    pub const INPUT_SIZE: usize = 19;
+    
+    pub fn to_inputs(&self) -> Vec<f32> {
+        self.to_input_array().to_vec()
+    }
+    
+    /// `to_inputs` without the heap allocation.
+    pub fn to_input_array(&self) -> [f32; Self::INPUT_SIZE] {
+        [
+            self.energy_level,
+            self.neighbor_proximity,
+            self.resource_density,
+            self.territory_pressure,
+            self.population_density,
+            self.threat_level,
+            self.age_normalized,
+            self.lineage_depth_normalized,
+            self.barrier_proximity,
+            self.safe_zone_proximity,
+            self.edge_proximity,
+            self.neighbor_grudge,
+            self.resource_contested,
+            self.crowding,
+            self.resource_distance,
+            self.resource_bearing,
+            self.speed,
+            self.straightness,
+            self.resource_gradient,
+        ]
+    }
+    
+    /// Adds zero-mean Gaussian noise at each sensor's
+    /// `SensorConfig::noise_for` level, clamping noisy readings to
+    /// `[0, 1]`. Noiseless sensors draw nothing from `rng`.
+    pub fn apply_noise<R: Rng + ?Sized>(&mut self, config: &SensorConfig, rng: &mut R) {
+        for (sensor, value) in SensorType::ALL.into_iter().zip(self.values_mut()) {
+            let deviation = config.noise_for(sensor);
+            if deviation > 0.0 {
+                // Box-Muller transform
+                let radius = (-2.0 * (1.0 - rng.gen::<f32>()).ln()).sqrt();
+                let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+                *value = (*value + deviation * radius * angle.cos()).clamp(0.0, 1.0);
+            }
+        }
+    }
+    
+    /// Mutable readings in `to_inputs` order.
+    fn values_mut(&mut self) -> [&mut f32; Self::INPUT_SIZE] {
+        [
+            &mut self.energy_level,
+            &mut self.neighbor_proximity,
+            &mut self.resource_density,
+            &mut self.territory_pressure,
+            &mut self.population_density,
+            &mut self.threat_level,
+            &mut self.age_normalized,
+            &mut self.lineage_depth_normalized,
+            &mut self.barrier_proximity,
+            &mut self.safe_zone_proximity,
+            &mut self.edge_proximity,
+            &mut self.neighbor_grudge,
+            &mut self.resource_contested,
+            &mut self.crowding,
+            &mut self.resource_distance,
+            &mut self.resource_bearing,
+            &mut self.speed,
+            &mut self.straightness,
+            &mut self.resource_gradient,
+        ]
+    }
+}
+
+/// See `NeuralWarrior::can_see`.
+pub fn within_vision_cone(origin: (f32, f32), heading: f32, point: (f32, f32), vision_cone: Option<f32>) -> bool {
+    let Some(cone) = vision_cone else {
+        return true;
+    };
+    let (dx, dy) = (point.0 - origin.0, point.1 - origin.1);
+    if dx == 0.0 && dy == 0.0 {
+        return true;
+    }
+    let offset = (dy.atan2(dx) - heading).rem_euclid(std::f32::consts::TAU);
+    offset.min(std::f32::consts::TAU - offset) <= cone / 2.0
+}
+
+/// Perception ranges used by the spatial sensors, in world units, and the
+/// maxima the age and lineage sensors saturate at. Scale the ranges with
+/// the arena so readings keep their meaning.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensorConfig {
+    pub neighbor_proximity_scale: f32,
+    pub resource_radius: f32,
+    pub population_radius: f32,
+    pub barrier_radius: f32,
+    pub safe_zone_radius: f32,
+    pub edge_radius: f32,
+    /// Ticks an attacker is remembered after its last hit.
+    pub grudge_memory_ticks: u64,
+    /// Full field-of-view angle in radians centered on the warrior's
+    /// heading. Neighbor, threat and grudge sensing and attack targeting
+    /// ignore warriors outside it; `None` sees in every direction.
+    pub vision_cone: Option<f32>,
+    /// Personal space around each warrior. The crowding sensor counts
+    /// warriors inside it, and enabled collisions keep it clear.
+    pub personal_radius: f32,
+    /// Age in ticks at which the age sensor reads 1.0.
+    pub max_age: f32,
+    /// Lineage depth at which the lineage sensor reads 1.0.
+    pub max_lineage_depth: f32,
+    /// Resources within this range shape the resource gradient sensor, each
+    /// weighted by the inverse of its distance.
+    pub resource_gradient_radius: f32,
+    /// Standard deviation of the Gaussian noise added to every reading the
+    /// simulation feeds a network; 0 keeps sensing exact.
+    pub sensor_noise: f32,
+    /// Per-sensor deviations that replace `sensor_noise`, for targeted
+    /// ablations.
+    pub sensor_noise_overrides: BTreeMap<SensorType, f32>,
+}
+
+impl SensorConfig {
+    /// Noise deviation applied to `sensor`'s readings.
+    pub fn noise_for(&self, sensor: SensorType) -> f32 {
+        self.sensor_noise_overrides.get(&sensor).copied().unwrap_or(self.sensor_noise)
+    }
+}
+
+impl Default for SensorConfig {
+    fn default() -> Self {
+        Self {
+            neighbor_proximity_scale: 100.0,
+            resource_radius: 50.0,
+            population_radius: 100.0,
+            barrier_radius: 100.0,
+            safe_zone_radius: 100.0,
+            edge_radius: 50.0,
+            grudge_memory_ticks: 50,
+            vision_cone: None,
+            personal_radius: 10.0,
+            max_age: 1000.0,
+            max_lineage_depth: 50.0,
+            resource_gradient_radius: 200.0,
+            sensor_noise: 0.0,
+            sensor_noise_overrides: BTreeMap::new(),
+        }
+    }
+}
+
+/// When a warrior may replicate and what replication costs it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReproductionConfig {
+    /// Energy the parent must exceed (before pressure scaling).
+    pub min_energy: f32,
+    /// Minimum age in ticks; 0 lets newborns replicate.
+    pub min_age: u32,
+    /// Energy the parent pays per offspring.
+    pub energy_cost: f32,
+    /// Fraction of the parent's energy the offspring start with, split
+    /// evenly across the litter.
+    pub child_energy_fraction: f32,
+    /// Distance from the parent at which offspring are placed.
+    pub offspring_spawn_radius: f32,
+    /// Offspring per Replicate action, fewer when carrying capacity runs out
+    /// or the parent can't pay `energy_cost` for every one.
+    pub litter_size: usize,
+}
+
+impl Default for ReproductionConfig {
+    fn default() -> Self {
+        Self {
+            min_energy: NeuralWarrior::REPLICATION_MIN_ENERGY,
+            min_age: 11,
+            energy_cost: 40.0,
+            child_energy_fraction: 0.6,
+            offspring_spawn_radius: 20.0,
+            litter_size: 1,
+        }
+    }
+}
+
+fn default_action_history_capacity() -> usize {
+    NeuralWarrior::DEFAULT_ACTION_HISTORY_CAPACITY
 }
 
 impl NeuralWarrior {
-    pub fn new(genome: Genome, id: u32) -> Self {
+    pub const REPLICATION_MIN_ENERGY: f32 = 80.0;
+    /// Energy a warrior can hold; gains beyond it are lost.
+    pub const MAX_ENERGY: f32 = 100.0;
+    pub const DEFAULT_ACTION_HISTORY_CAPACITY: usize = 10;
+    pub const RECENT_ATTACKER_CAPACITY: usize = 5;
+    pub const POSITION_HISTORY_CAPACITY: usize = 8;
+    /// Distance covered by a full-intensity move.
+    pub const MAX_MOVE_DISTANCE: f32 = 10.0;
+    
+    pub fn with_position(genome: Genome, id: u32, position: (f32, f32)) -> Self {
         let network = genome.to_network();
-        let position = (
-            rand::random::<f32>() * 1000.0,
-            rand::random::<f32>() * 1000.0,
-        );
         
         Self {
             id,
+            name: naming::founder_name(id),
             genome,
             network,
             position,
+            heading: 0.0,
+            relative_steering: false,
+            blocked_moves: 0,
             energy: 100.0,
             age: 0,
             territory_id: None,
-            action_history: VecDeque::with_capacity(10),
+            position_history: VecDeque::with_capacity(Self::POSITION_HISTORY_CAPACITY),
+            territories_owned: 0,
+            action_history: VecDeque::with_capacity(Self::DEFAULT_ACTION_HISTORY_CAPACITY),
+            action_history_capacity: Self::DEFAULT_ACTION_HISTORY_CAPACITY,
+            recent_attackers: VecDeque::new(),
+            reproduction: ReproductionConfig::default(),
             fitness_score: 0.0,
             lineage_depth: 0,
+            founder_id: id,
+            damage_dealt: 0.0,
+            behavior: BehaviorStats::default(),
+            parent_ids: Vec::new(),
         }
     }
     
     pub fn from_parents(parent1: &Self, parent2: &Self, id: u32) -> Self {
-        let child_genome = parent1.genome.crossover(&parent2.genome);
+        Self::from_parents_with_rng(parent1, parent2, id, &mut rand::thread_rng())
+    }
+    
+    /// Like `from_parents`, drawing crossover from `rng`. The child starts
+    /// at `parent1`'s position, for the caller to place in its arena.
+    pub fn from_parents_with_rng<R: Rng + ?Sized>(parent1: &Self, parent2: &Self, id: u32, rng: &mut R) -> Self {
+        let child_genome = parent1.genome.crossover_with_rng(&parent2.genome, rng);
         let lineage_depth = parent1.lineage_depth.max(parent2.lineage_depth) + 1;
         
-        let mut warrior = Self::new(child_genome, id);
+        let mut warrior = Self::with_position(child_genome, id, parent1.position);
         warrior.lineage_depth = lineage_depth;
+        warrior.founder_id = parent1.founder_id;
+        warrior.parent_ids = vec![parent1.id, parent2.id];
+        warrior.inherit_name(parent1);
         warrior
     }
     
+    /// An asexual offspring: a copy of this warrior with a new id and name,
+    /// one more generation of lineage, a mutated genome and a network
+    /// rebuilt from it. Per-life state (age, fitness, histories, grudges,
+    /// territory) starts fresh; energy and position are the parent's, for
+    /// the caller to adjust.
+    pub fn spawn_child<R: Rng + ?Sized>(&self, id: u32, mutation_rate: f32, rng: &mut R) -> Self {
+        self.spawn_child_with_operators(id, mutation_rate, 0.0, &MutationWeights::default(), rng)
+    }
+    
+    /// Like `spawn_child`, mutating with the given operator weights and then
+    /// deleting one genome byte with probability `deletion_rate`.
+    pub fn spawn_child_with_operators<R: Rng + ?Sized>(
+        &self,
+        id: u32,
+        mutation_rate: f32,
+        deletion_rate: f32,
+        weights: &MutationWeights,
+        rng: &mut R,
+    ) -> Self {
+        let mut child = self.clone();
+        child.id = id;
+        child.lineage_depth = self.lineage_depth + 1;
+        child.parent_ids = vec![self.id];
+        child.inherit_name(self);
+        child.age = 0;
+        child.fitness_score = 0.0;
+        child.damage_dealt = 0.0;
+        child.behavior = BehaviorStats::default();
+        child.blocked_moves = 0;
+        child.territory_id = None;
+        child.territories_owned = 0;
+        child.position_history.clear();
+        child.action_history.clear();
+        child.recent_attackers.clear();
+        child.genome.mutate_with_operators(mutation_rate, weights, rng);
+        child.genome.mutate_deletion_with_rng(deletion_rate, rng);
+        child.network = child.genome.to_network();
+        child
+    }
+    
+    /// The part of `name` descendants inherit.
+    pub fn surname(&self) -> &str {
+        naming::surname_of(&self.name)
+    }
+    
+    /// Names this warrior as a descendant of `parent`. Call after `id` and
+    /// `lineage_depth` are set.
+    pub fn inherit_name(&mut self, parent: &NeuralWarrior) {
+        self.name = naming::descendant_name(self.id, parent.surname(), self.lineage_depth);
+    }
+    
     pub fn sense_environment(&self, environment: &EnvironmentState) -> EnvironmentSensors {
         EnvironmentSensors {
-            energy_level: self.energy / 100.0,
+            energy_level: self.energy / Self::MAX_ENERGY,
             neighbor_proximity: self.calculate_neighbor_proximity(environment),
             resource_density: self.calculate_resource_density(environment),
             territory_pressure: self.calculate_territory_pressure(environment),
             population_density: self.calculate_population_density(environment),
             threat_level: self.calculate_threat_level(environment),
-            age_normalized: (self.age as f32).min(1000.0) / 1000.0,
-            lineage_depth_normalized: (self.lineage_depth as f32).min(50.0) / 50.0,
+            age_normalized: self.calculate_age(environment),
+            lineage_depth_normalized: self.calculate_lineage_depth(environment),
+            barrier_proximity: self.calculate_barrier_proximity(environment),
+            safe_zone_proximity: self.calculate_safe_zone_proximity(environment),
+            edge_proximity: self.calculate_edge_proximity(environment),
+            neighbor_grudge: self.calculate_neighbor_grudge(environment),
+            resource_contested: self.calculate_resource_contested(environment),
+            crowding: self.calculate_crowding(environment),
+            resource_distance: self.calculate_resource_distance(environment),
+            resource_bearing: self.calculate_resource_bearing(environment),
+            speed: self.calculate_speed(),
+            straightness: self.calculate_straightness(),
+            resource_gradient: self.calculate_resource_gradient(environment),
         }
     }
     
     pub fn decide_action(&mut self, sensors: &EnvironmentSensors) -> Action {
-        let sensor_inputs = vec![
-            sensors.energy_level,
-            sensors.neighbor_proximity,
-            sensors.resource_density,
-            sensors.territory_pressure,
-            sensors.population_density,
-            sensors.threat_level,
-            sensors.age_normalized,
-            sensors.lineage_depth_normalized,
-        ];
-        
-        let outputs = self.network.forward(&sensor_inputs);
-        let action = self.interpret_neural_output(&outputs);
-        
-        if self.action_history.len() >= 10 {
+        self.decide_action_with_temperature(sensors, 0.0, &mut rand::thread_rng())
+    }
+
+    /// Like `decide_action`, but samples the action from a softmax over the
+    /// action logits. A temperature of 0 reproduces the argmax path exactly.
+    pub fn decide_action_with_temperature<R: Rng + ?Sized>(
+        &mut self,
+        sensors: &EnvironmentSensors,
+        temperature: f32,
+        rng: &mut R,
+    ) -> Action {
+        let action = self.choose_action(sensors, temperature, rng);
+        self.record_action(action);
+        action
+    }
+    
+    /// Appends to the action history, dropping the oldest entries beyond
+    /// `action_history_capacity`.
+    pub fn record_action(&mut self, action: Action) {
+        if self.action_history_capacity == 0 {
+            return;
+        }
+        while self.action_history.len() >= self.action_history_capacity {
             self.action_history.pop_front();
         }
         self.action_history.push_back(action);
+    }
+    
+    /// Appends the current position to `position_history`, dropping the
+    /// oldest beyond `POSITION_HISTORY_CAPACITY`.
+    pub fn record_position(&mut self) {
+        if self.position_history.len() >= Self::POSITION_HISTORY_CAPACITY {
+            self.position_history.pop_front();
+        }
+        self.position_history.push_back(self.position);
+    }
+    
+    /// Path length through the position history and the current position.
+    fn recent_path(&self) -> (f32, usize) {
+        let points: Vec<(f32, f32)> = self.position_history.iter().copied().chain(std::iter::once(self.position)).collect();
+        let length = points.windows(2)
+            .map(|step| ((step[1].0 - step[0].0).powi(2) + (step[1].1 - step[0].1).powi(2)).sqrt())
+            .sum();
+        (length, points.len() - 1)
+    }
+    
+    fn calculate_speed(&self) -> f32 {
+        match self.recent_path() {
+            (_, 0) => 0.0,
+            (length, steps) => (length / (steps as f32 * Self::MAX_MOVE_DISTANCE)).min(1.0),
+        }
+    }
+    
+    fn calculate_straightness(&self) -> f32 {
+        let (length, _) = self.recent_path();
+        match self.position_history.front() {
+            Some(&start) if length > f32::EPSILON => (self.distance_to_point(start) / length).min(1.0),
+            _ => 0.0,
+        }
+    }
+    
+    /// Records a hit from `attacker_id`. Repeat attackers move to the back
+    /// instead of taking another slot.
+    pub fn remember_attacker(&mut self, attacker_id: u32, tick: u64) {
+        self.recent_attackers.retain(|&(id, _)| id != attacker_id);
+        if self.recent_attackers.len() >= Self::RECENT_ATTACKER_CAPACITY {
+            self.recent_attackers.pop_front();
+        }
+        self.recent_attackers.push_back((attacker_id, tick));
+    }
+    
+    /// Whether `warrior_id` hit this warrior within the last `memory_ticks`.
+    pub fn is_recent_attacker(&self, warrior_id: u32, tick: u64, memory_ticks: u64) -> bool {
+        self.recent_attackers.iter()
+            .any(|&(id, hit_tick)| id == warrior_id && tick.saturating_sub(hit_tick) < memory_ticks)
+    }
+    
+    pub fn forget_expired_attackers(&mut self, tick: u64, memory_ticks: u64) {
+        self.recent_attackers.retain(|&(_, hit_tick)| tick.saturating_sub(hit_tick) < memory_ticks);
+    }
+    
+    pub fn set_action_history_capacity(&mut self, capacity: usize) {
+        self.action_history_capacity = capacity;
+        while self.action_history.len() > capacity {
+            self.action_history.pop_front();
+        }
+    }
+    
+    /// Fraction of each action kind in the recorded history, indexed in
+    /// `ActionKind::ALL` order. All zeros when the history is empty.
+    pub fn action_frequencies(&self) -> [f32; ActionKind::ALL.len()] {
+        let mut frequencies = [0.0; ActionKind::ALL.len()];
+        if self.action_history.is_empty() {
+            return frequencies;
+        }
         
-        action
+        for action in &self.action_history {
+            frequencies[action.kind() as usize] += 1.0;
+        }
+        let total = self.action_history.len() as f32;
+        for frequency in &mut frequencies {
+            *frequency /= total;
+        }
+        frequencies
     }
     
-    pub fn execute_vm_instructions(&mut self, vm: &mut VirtualMachine) -> Result<Vec<Instruction>, String> {
+    /// Picks an action without recording it in the action history.
+    pub fn choose_action<R: Rng + ?Sized>(
+        &self,
+        sensors: &EnvironmentSensors,
+        temperature: f32,
+        rng: &mut R,
+    ) -> Action {
+        self.choose_action_into(sensors, temperature, rng, &mut Vec::new())
+    }
+    
+    /// Like `choose_action`, with `outputs` as a reusable buffer for the
+    /// network's forward pass.
+    pub fn choose_action_into<R: Rng + ?Sized>(
+        &self,
+        sensors: &EnvironmentSensors,
+        temperature: f32,
+        rng: &mut R,
+        outputs: &mut Vec<f32>,
+    ) -> Action {
+        self.network.forward_into(&sensors.to_input_array(), outputs);
+        self.interpret_neural_output(outputs, temperature, rng)
+    }
+    
+    /// Generates this tick's VM program, leaving out opcodes `vm` has disabled.
+    /// The sensor values it stages are normalized against `environment`.
+    pub fn execute_vm_instructions(&self, vm: &mut VirtualMachine, environment: &EnvironmentState) -> Result<Vec<Instruction>, String> {
+        self.execute_vm_instructions_with_rng(vm, environment, &mut rand::thread_rng())
+    }
+    
+    /// Like `execute_vm_instructions`, drawing the occasional Mutate from `rng`.
+    pub fn execute_vm_instructions_with_rng<R: Rng + ?Sized>(
+        &self,
+        vm: &mut VirtualMachine,
+        environment: &EnvironmentState,
+        rng: &mut R,
+    ) -> Result<Vec<Instruction>, String> {
         let mut instructions = Vec::new();
-        let sensor_data = self.get_vm_sensor_data();
+        let sensor_data = self.get_vm_sensor_data(environment);
         
         // Load sensor data into VM memory
         for (i, &value) in sensor_data.iter().enumerate() {
@@ -137,25 +725,34 @@ impl NeuralWarrior {
         instructions.push(Instruction::new(OpCode::Activate, 3, 11, 0.0));
         
         // Add mutation for evolution
-        if rand::random::<f32>() < 0.01 {
+        if rng.gen::<f32>() < 0.01 {
             instructions.push(Instruction::new(OpCode::Mutate, 8, 8, 0.1));
         }
         
+        instructions.retain(|instruction| vm.is_opcode_enabled(instruction.opcode));
         Ok(instructions)
     }
     
+    /// Non-finite or negative resource and combat inputs count as zero, so
+    /// the score is always finite.
     pub fn update_fitness(&mut self, survival_time: u32, resources_acquired: f32, combat_success: f32) {
+        let finite_non_negative = |value: f32| if value.is_finite() { value.max(0.0) } else { 0.0 };
         let survival_component = (survival_time as f32).ln().max(0.0);
-        let resource_component = resources_acquired.sqrt();
-        let combat_component = combat_success * 2.0;
+        let resource_component = finite_non_negative(resources_acquired).sqrt();
+        let combat_component = finite_non_negative(combat_success) * 2.0;
         let age_bonus = if self.age > 100 { 10.0 } else { 0.0 };
         let lineage_bonus = (self.lineage_depth as f32) * 0.5;
         
         self.fitness_score = survival_component + resource_component + combat_component + age_bonus + lineage_bonus;
+        debug_assert!(self.fitness_score.is_finite(), "Fitness must be finite, got {}", self.fitness_score);
     }
     
     pub fn can_replicate(&self) -> bool {
-        self.energy > 80.0 && self.age > 10
+        self.can_replicate_with_min_energy(self.reproduction.min_energy)
+    }
+    
+    pub fn can_replicate_with_min_energy(&self, min_energy: f32) -> bool {
+        self.energy > min_energy && self.age >= self.reproduction.min_age
     }
     
     pub fn consume_energy(&mut self, amount: f32) {
@@ -163,7 +760,7 @@ impl NeuralWarrior {
     }
     
     pub fn gain_energy(&mut self, amount: f32) {
-        self.energy = (self.energy + amount).min(100.0);
+        self.energy = (self.energy + amount).min(Self::MAX_ENERGY);
     }
     
     pub fn is_alive(&self) -> bool {
@@ -171,15 +768,19 @@ impl NeuralWarrior {
     }
     
     pub fn age_tick(&mut self) {
+        self.age_tick_with_cost(0.1);
+    }
+    
+    pub fn age_tick_with_cost(&mut self, energy_cost: f32) {
         self.age += 1;
-        self.consume_energy(0.1); // Aging costs energy
+        self.consume_energy(energy_cost); // Aging costs energy
     }
     
     fn calculate_neighbor_proximity(&self, environment: &EnvironmentState) -> f32 {
         let mut closest_distance = f32::INFINITY;
         
         for other_warrior in &environment.warriors {
-            if other_warrior.id != self.id {
+            if other_warrior.id != self.id && self.can_see(other_warrior.position, environment.sensor_config.vision_cone) {
                 let distance = self.distance_to(other_warrior);
                 if distance < closest_distance {
                     closest_distance = distance;
@@ -190,34 +791,142 @@ impl NeuralWarrior {
         if closest_distance == f32::INFINITY {
             0.0
         } else {
-            (100.0 / (closest_distance + 1.0)).min(1.0)
+            (environment.sensor_config.neighbor_proximity_scale / (closest_distance + 1.0)).min(1.0)
+        }
+    }
+    
+    fn calculate_neighbor_grudge(&self, environment: &EnvironmentState) -> f32 {
+        let nearest = environment.warriors.iter()
+            .filter(|other| other.id != self.id && self.can_see(other.position, environment.sensor_config.vision_cone))
+            .min_by(|a, b| self.distance_to(a).total_cmp(&self.distance_to(b)));
+        
+        match nearest {
+            Some(other) if self.is_recent_attacker(other.id, environment.tick, environment.sensor_config.grudge_memory_ticks) => 1.0,
+            _ => 0.0,
+        }
+    }
+    
+    fn calculate_resource_contested(&self, environment: &EnvironmentState) -> f32 {
+        let nearest = environment.resources.iter()
+            .min_by(|a, b| self.distance_to_point(a.position).total_cmp(&self.distance_to_point(b.position)));
+        
+        match nearest.and_then(|resource| resource.claim) {
+            Some(claim) if claim.warrior_id != self.id => 1.0,
+            _ => 0.0,
         }
     }
     
+    fn nearest_resource_in_range<'a>(&self, environment: &'a EnvironmentState) -> Option<&'a Resource> {
+        environment.resources.iter()
+            .filter(|resource| self.distance_to_point(resource.position) < environment.sensor_config.resource_radius)
+            .min_by(|a, b| self.distance_to_point(a.position).total_cmp(&self.distance_to_point(b.position)))
+    }
+    
+    fn calculate_resource_distance(&self, environment: &EnvironmentState) -> f32 {
+        match self.nearest_resource_in_range(environment) {
+            Some(resource) => self.distance_to_point(resource.position) / environment.sensor_config.resource_radius,
+            None => 1.0,
+        }
+    }
+    
+    fn calculate_resource_bearing(&self, environment: &EnvironmentState) -> f32 {
+        match self.nearest_resource_in_range(environment) {
+            Some(resource) => self.relative_bearing(resource.position.0 - self.position.0, resource.position.1 - self.position.1),
+            None => 0.5,
+        }
+    }
+    
+    /// Bearing of the sum of offsets to the resources within
+    /// `resource_gradient_radius`, each divided by its squared distance `d²`:
+    /// a unit vector toward the resource weighted by `1 / d`, so nearer
+    /// resources pull harder. A resource underfoot has no direction and is
+    /// skipped. Only the `resource_grid` cells the radius overlaps are read.
+    fn calculate_resource_gradient(&self, environment: &EnvironmentState) -> f32 {
+        let radius = environment.sensor_config.resource_gradient_radius;
+        let (dx, dy) = environment.resource_grid.candidates(environment.resources.len(), self.position, radius)
+            .into_iter()
+            .map(|index| &environment.resources[index])
+            .map(|resource| (resource.position, self.distance_to_point(resource.position)))
+            .filter(|&(_, distance)| distance > 0.0 && distance < radius)
+            .fold((0.0, 0.0), |(dx, dy), (position, distance)| {
+                let weight = distance * distance;
+                (dx + (position.0 - self.position.0) / weight, dy + (position.1 - self.position.1) / weight)
+            });
+        self.relative_bearing(dx, dy)
+    }
+    
+    /// Angle from the heading to the offset `(dx, dy)` mapped to `[0, 1]`,
+    /// with 0.5 dead ahead and for a zero offset.
+    fn relative_bearing(&self, dx: f32, dy: f32) -> f32 {
+        if dx == 0.0 && dy == 0.0 {
+            return 0.5;
+        }
+        // Signed offset in [-π, π), so a target dead ahead reads 0.5
+        let offset = (dy.atan2(dx) - self.heading + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU)
+            - std::f32::consts::PI;
+        offset / std::f32::consts::TAU + 0.5
+    }
+    
     fn calculate_resource_density(&self, environment: &EnvironmentState) -> f32 {
         let nearby_resources = environment.resources.iter()
-            .filter(|resource| self.distance_to_point(resource.position) < 50.0)
+            .filter(|resource| self.distance_to_point(resource.position) < environment.sensor_config.resource_radius)
             .count();
         
         (nearby_resources as f32 / 10.0).min(1.0)
     }
     
-    fn calculate_territory_pressure(&self, _environment: &EnvironmentState) -> f32 {
-        // Simplified territory pressure based on energy and position
-        let boundary_distance = self.position.0.min(self.position.1)
-            .min(1000.0 - self.position.0)
-            .min(1000.0 - self.position.1);
+    /// Pressure from the arena territory the warrior stands in: 0 when the
+    /// territory is unowned or owned by the warrior itself, otherwise the
+    /// foreign owner's share of the combined energy of owner and intruder.
+    fn calculate_territory_pressure(&self, environment: &EnvironmentState) -> f32 {
+        let containing = environment.territories.iter()
+            .filter(|territory| self.distance_to_point(territory.center) < territory.radius)
+            .min_by(|a, b| self.distance_to_point(a.center).total_cmp(&self.distance_to_point(b.center)));
         
-        if boundary_distance < 50.0 {
-            1.0 - (boundary_distance / 50.0)
-        } else {
-            0.0
+        let owner_id = match containing.and_then(|territory| territory.owner_id) {
+            Some(owner_id) if owner_id != self.id => owner_id,
+            _ => return 0.0,
+        };
+        
+        match environment.warriors.iter().find(|warrior| warrior.id == owner_id) {
+            Some(owner) => {
+                let combined = owner.energy + self.energy;
+                if combined > 0.0 { (owner.energy / combined).clamp(0.0, 1.0) } else { 0.0 }
+            },
+            None => 0.0,
         }
     }
     
+    /// 1.0 at the arena boundary, falling linearly to 0.0 at `edge_radius`.
+    fn calculate_edge_proximity(&self, environment: &EnvironmentState) -> f32 {
+        let (width, height) = environment.bounds;
+        let boundary_distance = self.position.0.min(self.position.1)
+            .min(width - self.position.0)
+            .min(height - self.position.1)
+            .max(0.0);
+        
+        Self::proximity(boundary_distance, environment.sensor_config.edge_radius)
+    }
+    
+    fn calculate_age(&self, environment: &EnvironmentState) -> f32 {
+        (self.age as f32 / environment.sensor_config.max_age).min(1.0)
+    }
+    
+    fn calculate_lineage_depth(&self, environment: &EnvironmentState) -> f32 {
+        (self.lineage_depth as f32 / environment.sensor_config.max_lineage_depth).min(1.0)
+    }
+    
+    fn calculate_crowding(&self, environment: &EnvironmentState) -> f32 {
+        let crowd = environment.warriors.iter()
+            .filter(|warrior| warrior.id != self.id && self.distance_to(warrior) < environment.sensor_config.personal_radius)
+            .count();
+        
+        (crowd as f32 / 5.0).min(1.0)
+    }
+    
     fn calculate_population_density(&self, environment: &EnvironmentState) -> f32 {
         let nearby_population = environment.warriors.iter()
-            .filter(|warrior| warrior.id != self.id && self.distance_to(warrior) < 100.0)
+            .filter(|warrior| warrior.id != self.id && self.distance_to(warrior) < environment.sensor_config.population_radius)
             .count();
         
         (nearby_population as f32 / 20.0).min(1.0)
@@ -227,7 +936,7 @@ impl NeuralWarrior {
         let mut max_threat = 0.0;
         
         for other_warrior in &environment.warriors {
-            if other_warrior.id != self.id {
+            if other_warrior.id != self.id && self.can_see(other_warrior.position, environment.sensor_config.vision_cone) {
                 let distance = self.distance_to(other_warrior);
                 let energy_ratio = other_warrior.energy / (self.energy + 1.0);
                 let threat = (energy_ratio / (distance + 1.0)).min(1.0);
@@ -247,84 +956,180 @@ impl NeuralWarrior {
         (dx * dx + dy * dy).sqrt()
     }
     
+    /// 1.0 touching or inside the nearest barrier, falling linearly to 0.0
+    /// at `barrier_radius` from its edge.
+    fn calculate_barrier_proximity(&self, environment: &EnvironmentState) -> f32 {
+        let range = environment.sensor_config.barrier_radius;
+        let nearest = environment.barriers.iter()
+            .map(|barrier| {
+                let dx = (barrier.position.0 - self.position.0).max(self.position.0 - (barrier.position.0 + barrier.width)).max(0.0);
+                let dy = (barrier.position.1 - self.position.1).max(self.position.1 - (barrier.position.1 + barrier.height)).max(0.0);
+                (dx * dx + dy * dy).sqrt()
+            })
+            .fold(f32::INFINITY, f32::min);
+        
+        Self::proximity(nearest, range)
+    }
+    
+    /// 1.0 inside any safe zone, otherwise falls linearly to 0.0 at
+    /// `safe_zone_radius` from the nearest zone boundary.
+    fn calculate_safe_zone_proximity(&self, environment: &EnvironmentState) -> f32 {
+        let range = environment.sensor_config.safe_zone_radius;
+        let nearest = environment.safe_zones.iter()
+            .map(|zone| self.distance_to_point(zone.center) - zone.radius)
+            .fold(f32::INFINITY, f32::min);
+        
+        Self::proximity(nearest.max(0.0), range)
+    }
+    
+    fn proximity(distance: f32, range: f32) -> f32 {
+        if range <= 0.0 || !distance.is_finite() {
+            return 0.0;
+        }
+        (1.0 - distance / range).clamp(0.0, 1.0)
+    }
+    
     fn distance_to_point(&self, point: (f32, f32)) -> f32 {
         let dx = self.position.0 - point.0;
         let dy = self.position.1 - point.1;
         (dx * dx + dy * dy).sqrt()
     }
     
-    fn interpret_neural_output(&self, outputs: &[f32]) -> Action {
+    /// Whether `point` lies within a `vision_cone`-wide field of view around
+    /// the heading. Everything is visible without a cone, and so is a point
+    /// on top of the warrior.
+    pub fn can_see(&self, point: (f32, f32), vision_cone: Option<f32>) -> bool {
+        within_vision_cone(self.position, self.heading, point, vision_cone)
+    }
+    
+    /// Picks an action from the first `Action::SELECTION_OUTPUTS` outputs
+    /// and reads its parameters from the dedicated outputs after them (see
+    /// the constants on `Action`). Networks without parameter outputs fall
+    /// back to reusing the selection score. With `relative_steering` the
+    /// Move direction output turns away from the current heading, so 0
+    /// moves straight ahead.
+    pub fn interpret_neural_output<R: Rng + ?Sized>(&self, outputs: &[f32], temperature: f32, rng: &mut R) -> Action {
         if outputs.len() < 4 {
             return Action::Rest;
         }
         
-        let action_type = outputs.iter().enumerate()
-            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
-            .map(|(idx, _)| idx)
-            .unwrap_or(0);
+        // Older four-output networks can only pick the single-tick actions
+        let scores = &outputs[..outputs.len().min(Action::SELECTION_OUTPUTS)];
+        let action_type = if temperature > 0.0 {
+            Self::sample_softmax(scores, temperature, rng)
+        } else {
+            // Ties (including 0.0 against -0.0) go to the last index, as
+            // they always have; activations are sanitized, so no NaN
+            scores.iter().enumerate()
+                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|(idx, _)| idx)
+                .unwrap_or(0)
+        };
+        let parameter = |output: usize| outputs.get(output).copied().unwrap_or(scores[action_type]);
         
         match action_type {
             0 => Action::Move {
-                direction: outputs[0] * std::f32::consts::PI * 2.0,
-                intensity: outputs[0].abs().min(1.0),
+                direction: self.steering_origin() + parameter(Action::MOVE_DIRECTION_OUTPUT) * std::f32::consts::PI * 2.0,
+                intensity: parameter(Action::MOVE_INTENSITY_OUTPUT).abs().min(1.0),
             },
             1 => Action::Attack {
-                target_direction: outputs[1] * std::f32::consts::PI * 2.0,
-                strength: outputs[1].abs().min(1.0),
+                target_direction: parameter(Action::ATTACK_DIRECTION_OUTPUT) * std::f32::consts::PI * 2.0,
+                strength: parameter(Action::ATTACK_STRENGTH_OUTPUT).abs().min(1.0),
             },
             2 => Action::Defend {
-                shield_strength: outputs[2].abs().min(1.0),
+                shield_strength: parameter(Action::SHIELD_STRENGTH_OUTPUT).abs().min(1.0),
             },
             3 => {
                 if self.can_replicate() {
                     Action::Replicate {
-                        mutation_rate: (outputs[3].abs() * 0.2).min(0.5),
+                        mutation_rate: (parameter(Action::MUTATION_RATE_OUTPUT).abs() * 0.2).min(0.5),
                     }
                 } else {
                     Action::Rest
                 }
             },
+            4 => Action::ChargedAttack {
+                target_direction: parameter(Action::ATTACK_DIRECTION_OUTPUT) * std::f32::consts::PI * 2.0,
+                strength: parameter(Action::ATTACK_STRENGTH_OUTPUT).abs().min(1.0),
+            },
+            5 => Action::DeepRest,
             _ => Action::Rest,
         }
     }
     
-    fn get_vm_sensor_data(&self) -> Vec<f32> {
+    fn steering_origin(&self) -> f32 {
+        if self.relative_steering { self.heading } else { 0.0 }
+    }
+    
+    /// Effective exploration temperature for this warrior. When the
+    /// temperature is evolvable, the genome's temperature gene scales the
+    /// global value so lineages can drift toward deterministic behavior.
+    pub fn action_temperature(&self, base_temperature: f32, evolvable: bool) -> f32 {
+        if evolvable {
+            base_temperature * self.genome.temperature_gene()
+        } else {
+            base_temperature
+        }
+    }
+    
+    fn sample_softmax<R: Rng + ?Sized>(logits: &[f32], temperature: f32, rng: &mut R) -> usize {
+        let max_logit = logits.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        // Recomputed rather than collected, so sampling doesn't allocate
+        let weight = |logit: f32| ((logit - max_logit) / temperature).exp();
+        let total: f32 = logits.iter().map(|&logit| weight(logit)).sum();
+        
+        let mut roll = rng.gen::<f32>() * total;
+        for (idx, &logit) in logits.iter().enumerate() {
+            roll -= weight(logit);
+            if roll <= 0.0 {
+                return idx;
+            }
+        }
+        
+        logits.len() - 1
+    }
+    
+    /// Energy, position, age and lineage scaled like the network's sensors:
+    /// by the energy cap, the arena bounds and the `sensor_config` maxima.
+    /// Fitness has no maximum, so it is squashed into `(-1, 1)` instead.
+    fn get_vm_sensor_data(&self, environment: &EnvironmentState) -> Vec<f32> {
+        let (width, height) = environment.bounds;
         vec![
-            self.energy / 100.0,
-            self.position.0 / 1000.0,
-            self.position.1 / 1000.0,
-            self.age as f32 / 1000.0,
-            self.fitness_score / 100.0,
-            self.lineage_depth as f32 / 50.0,
+            self.energy / Self::MAX_ENERGY,
+            self.position.0 / width,
+            self.position.1 / height,
+            self.calculate_age(environment),
+            self.fitness_score / (1.0 + self.fitness_score.abs()),
+            self.calculate_lineage_depth(environment),
             if self.territory_id.is_some() { 1.0 } else { 0.0 },
-            self.action_history.len() as f32 / 10.0,
+            self.action_history.len() as f32 / self.action_history_capacity.max(1) as f32,
         ]
     }
 
     pub fn get_sensor_reading(&self, sensor_type: SensorType, environment: &crate::environment::Environment) -> f32 {
         // Convert Environment to EnvironmentState for sensor calculations
-        let env_state = EnvironmentState {
-            warriors: environment.warriors.values().cloned().collect(),
-            resources: Vec::new(), // Environment has resources but different structure
-            territories: Vec::new(), // Environment has territories but different structure  
-            tick: 0,
-        };
+        let env_state = environment.get_environment_state();
 
         match sensor_type {
-            SensorType::Energy => self.energy / 100.0,
+            SensorType::Energy => self.energy / Self::MAX_ENERGY,
             SensorType::NeighborProximity => self.calculate_neighbor_proximity(&env_state),
             SensorType::ResourceDensity => self.calculate_resource_density(&env_state),
             SensorType::TerritoryPressure => self.calculate_territory_pressure(&env_state),
             SensorType::Population => self.calculate_population_density(&env_state),
             SensorType::Threat => self.calculate_threat_level(&env_state),
-            SensorType::Age => {
-                // Normalize age to 0.0-1.0 range, assuming max age of 1000
-                (self.age as f32 / 1000.0).min(1.0)
-            },
-            SensorType::LineageDepth => {
-                // Normalize lineage depth to 0.0-1.0 range, assuming max depth of 50
-                (self.lineage_depth as f32 / 50.0).min(1.0)
-            },
+            SensorType::Age => self.calculate_age(&env_state),
+            SensorType::LineageDepth => self.calculate_lineage_depth(&env_state),
+            SensorType::BarrierProximity => self.calculate_barrier_proximity(&env_state),
+            SensorType::SafeZoneProximity => self.calculate_safe_zone_proximity(&env_state),
+            SensorType::EdgeProximity => self.calculate_edge_proximity(&env_state),
+            SensorType::NeighborGrudge => self.calculate_neighbor_grudge(&env_state),
+            SensorType::ResourceContested => self.calculate_resource_contested(&env_state),
+            SensorType::Crowding => self.calculate_crowding(&env_state),
+            SensorType::ResourceDistance => self.calculate_resource_distance(&env_state),
+            SensorType::ResourceBearing => self.calculate_resource_bearing(&env_state),
+            SensorType::Speed => self.calculate_speed(),
+            SensorType::Straightness => self.calculate_straightness(),
+            SensorType::ResourceGradient => self.calculate_resource_gradient(&env_state),
         }
     }
 }
@@ -334,14 +1139,86 @@ pub struct EnvironmentState {
     pub warriors: Vec<NeuralWarrior>,
     pub resources: Vec<Resource>,
     pub territories: Vec<Territory>,
+    /// Arena width and height.
+    pub bounds: (f32, f32),
+    pub barriers: Vec<MemoryBarrier>,
+    pub safe_zones: Vec<SafeZone>,
     pub tick: u64,
+    pub sensor_config: SensorConfig,
+    /// `resources` bucketed by `sensor_config.resource_gradient_radius`.
+    /// Not serialized; a deserialized state scans every resource instead.
+    #[serde(skip)]
+    pub resource_grid: ResourceGrid,
+}
+
+/// Indices into a resource list bucketed into square cells, so a radius
+/// query only reads the cells the radius overlaps. The default grid
+/// indexes nothing and its queries return every resource.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceGrid {
+    cell_size: f32,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl ResourceGrid {
+    /// Buckets `resources` into cells `cell_size` wide. A cell size that
+    /// isn't positive and finite gives the default, unindexed grid.
+    pub fn new(resources: &[Resource], cell_size: f32) -> Self {
+        if cell_size <= 0.0 || !cell_size.is_finite() {
+            return Self::default();
+        }
+        let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (index, resource) in resources.iter().enumerate() {
+            cells.entry(Self::cell_of(resource.position, cell_size)).or_default().push(index);
+        }
+        Self { cell_size, cells }
+    }
+    
+    fn cell_of(point: (f32, f32), cell_size: f32) -> (i64, i64) {
+        ((point.0 / cell_size).floor() as i64, (point.1 / cell_size).floor() as i64)
+    }
+    
+    /// Ascending indices of the resources in every cell that overlaps the
+    /// square of half-width `radius` around `center`: a superset of those
+    /// within `radius`, for the caller to filter by distance. Reads every
+    /// one of `resource_count` resources when unindexed or when `radius`
+    /// spans more than the grid's neighbouring cells.
+    pub fn candidates(&self, resource_count: usize, center: (f32, f32), radius: f32) -> Vec<usize> {
+        if self.cell_size == 0.0 || radius.is_nan() || radius > self.cell_size {
+            return (0..resource_count).collect();
+        }
+        let (min_x, min_y) = Self::cell_of((center.0 - radius, center.1 - radius), self.cell_size);
+        let (max_x, max_y) = Self::cell_of((center.0 + radius, center.1 + radius), self.cell_size);
+        let mut indices: Vec<usize> = (min_x..=max_x)
+            .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect();
+        // List order keeps sums over the candidates identical to a full scan
+        indices.sort_unstable();
+        indices
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Resource {
+    /// Unique within an environment; used to address resources in deltas.
+    #[serde(default)]
+    pub id: u64,
     pub position: (f32, f32),
     pub energy_value: f32,
     pub resource_type: ResourceType,
+    #[serde(default)]
+    pub claim: Option<ResourceClaim>,
+}
+
+/// A warrior's temporary hold on a resource it is approaching.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResourceClaim {
+    pub warrior_id: u32,
+    /// First tick at which the claim no longer holds.
+    pub expires_at: u64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,123 @@
+use crate::environment::{EventConfig, EventType, PressureConfig, ResourceConfig};
+use crate::simulation::{ConfigError, SimulationConfig};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PresetError {
+    #[error("Unknown preset '{name}' (expected one of: {})", SimulationConfig::PRESET_NAMES.join(", "))]
+    UnknownPreset { name: String },
+    #[error("Config parse error: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("Invalid config: {0}")]
+    Invalid(#[from] ConfigError),
+}
+
+/// Named starting points for common kinds of runs. Every preset passes
+/// `SimulationConfig::validate`.
+impl SimulationConfig {
+    pub const PRESET_NAMES: [&'static str; 4] = ["quick_demo", "benchmark", "coevolution_combat", "scarcity"];
+
+    /// Small arena and short generations, so something visible happens
+    /// within seconds.
+    pub fn preset_quick_demo() -> Self {
+        Self {
+            max_population: 50,
+            arena_width: 500.0,
+            arena_height: 500.0,
+            generation_ticks: 200,
+            target_species_count: 4,
+            ..Self::default()
+        }
+    }
+
+    /// Seeded, large population with optional bookkeeping disabled, for
+    /// comparable performance measurements.
+    pub fn preset_benchmark() -> Self {
+        Self {
+            max_population: 500,
+            seed: Some(42),
+            fast_mode: true,
+            ..Self::default()
+        }
+    }
+
+    /// Attacks pay for themselves and there is nowhere to hide, so
+    /// predators and prey co-evolve.
+    pub fn preset_coevolution_combat() -> Self {
+        Self {
+            combat_energy_transfer: 0.8,
+            safe_zones: false,
+            ..Self::default()
+        }
+    }
+
+    /// Few resources that respawn slowly, with pressure biting hard on
+    /// spawning, aging and replication.
+    pub fn preset_scarcity() -> Self {
+        Self {
+            resource_config: ResourceConfig {
+                spawn_rate: 0.02,
+                max_resources: 40,
+                ..ResourceConfig::default()
+            },
+            event_config: EventConfig {
+                enabled_events: EventType::ALL.into_iter()
+                    .filter(|&event| event != EventType::ResourceAbundance)
+                    .collect(),
+                ..EventConfig::default()
+            },
+            pressure_config: PressureConfig {
+                aging_drain_coefficient: 2.0,
+                spawn_rate_reduction: 0.9,
+                replication_energy_coefficient: 0.5,
+            },
+            ..Self::default()
+        }
+    }
+
+    pub fn preset(name: &str) -> Result<Self, PresetError> {
+        match name {
+            "quick_demo" => Ok(Self::preset_quick_demo()),
+            "benchmark" => Ok(Self::preset_benchmark()),
+            "coevolution_combat" => Ok(Self::preset_coevolution_combat()),
+            "scarcity" => Ok(Self::preset_scarcity()),
+            _ => Err(PresetError::UnknownPreset { name: name.to_string() }),
+        }
+    }
+
+    /// Parses either a plain config object or `{"preset": name, "overrides": {...}}`.
+    /// Overrides are merged field by field into the preset, recursing into
+    /// nested objects, and the result is validated.
+    pub fn from_json(json: &str) -> Result<Self, PresetError> {
+        let value: Value = serde_json::from_str(json)?;
+
+        let config = match value.get("preset").and_then(Value::as_str) {
+            Some(name) => {
+                let mut merged = serde_json::to_value(Self::preset(name)?)?;
+                if let Some(overrides) = value.get("overrides") {
+                    merge_json(&mut merged, overrides);
+                }
+                Self::deserialize(merged)?
+            },
+            None => Self::deserialize(value)?,
+        };
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+fn merge_json(base: &mut Value, overrides: &Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => { base.insert(key.clone(), value.clone()); },
+                }
+            }
+        },
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
@@ -0,0 +1,197 @@
+use crate::simulation::{GenerationResult, SimulationStatistics};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// One generation's row in a `RunReport`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationPoint {
+    pub generation: u32,
+    /// Highest fitness among the generation's survivors (0 with none).
+    pub best_fitness: f32,
+    pub population: usize,
+    pub species_count: usize,
+    /// How the generation's best genome differs from the previous one's.
+    #[serde(default)]
+    pub best_genome_delta: Option<BestGenomeDelta>,
+}
+
+/// A byte that differs at the same position in both genomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteChange {
+    pub index: usize,
+    pub before: u8,
+    pub after: u8,
+}
+
+/// Position-aligned difference between one generation's best genome and
+/// the previous generation's.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BestGenomeDelta {
+    /// Positions present in both genomes whose bytes differ, ascending.
+    pub changed: Vec<ByteChange>,
+    /// Bytes past the end of the previous genome.
+    pub inserted: Vec<u8>,
+    /// How many bytes the previous genome had past the end of this one.
+    pub truncated: usize,
+}
+
+impl BestGenomeDelta {
+    /// Changes shown by `Display` before the rest are summarized.
+    const DISPLAYED_CHANGES: usize = 8;
+
+    pub fn between(previous: &[u8], current: &[u8]) -> Self {
+        let changed = previous.iter().zip(current)
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(index, (&before, &after))| ByteChange { index, before, after })
+            .collect();
+        Self {
+            changed,
+            inserted: current.get(previous.len()..).unwrap_or_default().to_vec(),
+            truncated: previous.len().saturating_sub(current.len()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.inserted.is_empty() && self.truncated == 0
+    }
+}
+
+impl fmt::Display for BestGenomeDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "unchanged");
+        }
+        write!(f, "{} changed", self.changed.len())?;
+        if !self.changed.is_empty() {
+            let shown: Vec<String> = self.changed.iter()
+                .take(Self::DISPLAYED_CHANGES)
+                .map(|change| format!("{}: {:02x}->{:02x}", change.index, change.before, change.after))
+                .collect();
+            let more = self.changed.len().saturating_sub(Self::DISPLAYED_CHANGES);
+            write!(f, " [{}{}]", shown.join(", "), if more > 0 { format!(", +{} more", more) } else { String::new() })?;
+        }
+        if !self.inserted.is_empty() {
+            write!(f, ", {} inserted", self.inserted.len())?;
+        }
+        if self.truncated > 0 {
+            write!(f, ", {} truncated", self.truncated)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtinctionEvent {
+    pub generation: u32,
+    pub species: Vec<u32>,
+}
+
+/// The surname most common among the final survivors; see
+/// `neural::naming`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DominantLineage {
+    pub surname: String,
+    pub members: usize,
+}
+
+/// Summary of a finished run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunReport {
+    pub trajectory: Vec<GenerationPoint>,
+    pub peak_fitness: f32,
+    /// First generation that reached `peak_fitness`.
+    pub peak_generation: Option<u32>,
+    pub mean_population: f32,
+    pub max_population: usize,
+    pub extinction_events: Vec<ExtinctionEvent>,
+    /// Sum of the generations' simulation times.
+    pub total_time_ms: u128,
+    pub mean_rounds_per_second: f32,
+    pub dominant_lineage: Option<DominantLineage>,
+    pub final_generation: u32,
+    pub final_population: usize,
+}
+
+impl RunReport {
+    pub fn from_results(results: &[GenerationResult], statistics: &SimulationStatistics) -> Self {
+        let trajectory: Vec<GenerationPoint> = results.iter()
+            .map(|result| GenerationPoint {
+                generation: result.generation,
+                best_fitness: result.survivors.iter().map(|warrior| warrior.fitness_score).fold(0.0, f32::max),
+                population: result.survivors.len(),
+                species_count: result.species_count,
+                best_genome_delta: result.best_genome_delta.clone(),
+            })
+            .collect();
+
+        let peak = trajectory.iter()
+            .reduce(|best, point| if point.best_fitness > best.best_fitness { point } else { best });
+        let mean = |total: f32| if results.is_empty() { 0.0 } else { total / results.len() as f32 };
+
+        let extinction_events = results.iter()
+            .filter(|result| !result.extinct_species.is_empty())
+            .map(|result| ExtinctionEvent {
+                generation: result.generation,
+                species: result.extinct_species.clone(),
+            })
+            .collect();
+
+        // Ties go to the alphabetically first surname so reports are stable
+        let mut surname_counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for warrior in results.last().map_or(&[][..], |result| &result.survivors) {
+            *surname_counts.entry(warrior.surname()).or_insert(0) += 1;
+        }
+        let dominant_lineage = surname_counts.into_iter()
+            .reduce(|best, entry| if entry.1 > best.1 { entry } else { best })
+            .map(|(surname, members)| DominantLineage { surname: surname.to_string(), members });
+
+        Self {
+            peak_fitness: peak.map_or(0.0, |point| point.best_fitness),
+            peak_generation: peak.map(|point| point.generation),
+            mean_population: mean(trajectory.iter().map(|point| point.population as f32).sum()),
+            max_population: trajectory.iter().map(|point| point.population).max().unwrap_or(0),
+            extinction_events,
+            total_time_ms: results.iter().map(|result| result.performance_metrics.simulation_time_ms).sum(),
+            mean_rounds_per_second: mean(results.iter().map(|result| result.performance_metrics.rounds_per_second).sum()),
+            dominant_lineage,
+            final_generation: statistics.generation,
+            final_population: statistics.population_size,
+            trajectory,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl fmt::Display for RunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Run summary: {} generations", self.trajectory.len())?;
+        match self.peak_generation {
+            Some(generation) => writeln!(f, "  Peak fitness:     {:.2} (generation {})", self.peak_fitness, generation)?,
+            None => writeln!(f, "  Peak fitness:     n/a")?,
+        }
+        let peak_delta = self.trajectory.iter()
+            .find(|point| Some(point.generation) == self.peak_generation)
+            .and_then(|point| point.best_genome_delta.as_ref());
+        if let Some(delta) = peak_delta {
+            writeln!(f, "  Peak genome:      {}", delta)?;
+        }
+        writeln!(f, "  Population:       mean {:.1}, max {}", self.mean_population, self.max_population)?;
+        if let (Some(first), Some(last)) = (self.trajectory.first(), self.trajectory.last()) {
+            writeln!(f, "  Species:          {} -> {}", first.species_count, last.species_count)?;
+        }
+        writeln!(f, "  Extinctions:      {} species in {} generations",
+            self.extinction_events.iter().map(|event| event.species.len()).sum::<usize>(),
+            self.extinction_events.len())?;
+        writeln!(f, "  Wall-clock time:  {} ms ({:.0} rounds/s)", self.total_time_ms, self.mean_rounds_per_second)?;
+        match &self.dominant_lineage {
+            Some(lineage) => writeln!(f, "  Dominant lineage: {} ({} survivors)", lineage.surname, lineage.members)?,
+            None => writeln!(f, "  Dominant lineage: none")?,
+        }
+        write!(f, "  Final state:      generation {}, population {}", self.final_generation, self.final_population)
+    }
+}
@@ -0,0 +1,75 @@
+//! Independent random streams derived from a single master seed.
+//!
+//! Each subsystem draws from its own stream, so an extra draw in one of
+//! them (say, a new terrain feature) leaves every other sequence unchanged.
+//! A stream's seed is `splitmix64(parent_seed ^ tag)`. The layout:
+//!
+//! - master seed (`SimulationConfig::seed`)
+//!   - `Simulation`: decisions, sensor noise, spawn positions, new ids and
+//!     the seeds of rebuilt environments
+//!   - `Environment`: seed of the initial environment, which splits it into
+//!     - `Terrain`: barriers, safe zones and territories
+//!     - `Resources`: spawning and decay
+//!     - `Events`: event rolls and their effects
+//!     - `Mutation`: offspring created by the Replicate action
+//!   - `Speciation`: selection, crossover and mutation between generations
+//!   - `Vm`: the Mutate and Sense instructions
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RngStream {
+    Simulation,
+    Environment,
+    Terrain,
+    Resources,
+    Events,
+    Mutation,
+    Speciation,
+    Vm,
+}
+
+impl RngStream {
+    /// Fixed per-stream constant; changing one reshuffles that stream.
+    pub const fn tag(self) -> u64 {
+        u64::from_be_bytes(*match self {
+            RngStream::Simulation => b"simulate",
+            RngStream::Environment => b"environ\0",
+            RngStream::Terrain => b"terrain\0",
+            RngStream::Resources => b"resource",
+            RngStream::Events => b"events\0\0",
+            RngStream::Mutation => b"mutation",
+            RngStream::Speciation => b"speciate",
+            RngStream::Vm => b"vm\0\0\0\0\0\0",
+        })
+    }
+
+    /// Seed of this stream under `parent_seed`.
+    pub fn seed(self, parent_seed: u64) -> u64 {
+        splitmix64(parent_seed ^ self.tag())
+    }
+
+    pub fn rng(self, parent_seed: u64) -> StdRng {
+        StdRng::seed_from_u64(self.seed(parent_seed))
+    }
+}
+
+/// One step of the SplitMix64 generator, used as a seed mixer.
+pub fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Draws ids from `rng` until one isn't `taken`, so a newborn can't replace
+/// a warrior that already holds its id.
+pub fn unused_id<R: Rng + ?Sized>(rng: &mut R, taken: impl Fn(u32) -> bool) -> u32 {
+    loop {
+        let id = rng.gen();
+        if !taken(id) {
+            return id;
+        }
+    }
+}
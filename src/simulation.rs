@@ -1,57 +1,349 @@
-use crate::environment::{Environment, EnvironmentUpdate, ActionResults};
-use crate::evolution::{SpeciationManager, SpeciesStats};
-use crate::neural::{Genome, NeuralWarrior, Action};
-use crate::vm::VirtualMachine;
-use crate::memory::MemoryAllocator;
+use crate::diagnostics::{ActionHistogram, ConvergenceMonitor, DegenerateActionMonitor, SimulationWarning};
+use crate::environment::{ActionMap, BoundaryMode, CollisionConfig, EnergyFlows, Environment, EnvironmentUpdate, EventConfig, ActionResults, PressureConfig, ResourceConfig, SpawnStrategy, TerritoryConfig};
+use crate::evolution::{FitnessFloor, LineageSummary, LineageTracker, SpeciationManager, SpeciesStats, SpeciesSummary};
+use crate::neural::{EnvironmentState, Genome, GenomeCodecKind, NeuralWarrior, MutationWeights, ReproductionConfig, SensorConfig};
+use crate::vm::{Instruction, OpCode, VirtualMachine};
+use crate::memory::{AllocationError, MemoryAllocator};
+use crate::statistics::{behavior_variance, PopulationAccumulator};
+use crate::report::{BestGenomeDelta, RunReport};
+use crate::rng::{unused_id, RngStream};
+use rand::rngs::StdRng;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{Duration, Instant};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NeuralArenaSimulation {
     pub environment: Environment,
     pub vm: VirtualMachine,
     pub memory_allocator: MemoryAllocator,
     pub speciation_manager: SpeciationManager,
+    pub lineage: LineageTracker,
     pub simulation_config: SimulationConfig,
     pub statistics: SimulationStatistics,
     pub generation: u32,
     pub tick: u64,
     pub is_running: bool,
+    /// Actions chosen since the last generation boundary.
+    pub action_histogram: ActionHistogram,
+    /// Every warning raised so far in this run.
+    pub warnings: Vec<SimulationWarning>,
+    /// Running sums over the warriors alive after the most recent tick.
+    pub population_stats: PopulationAccumulator,
+    action_monitor: DegenerateActionMonitor,
+    convergence_monitor: ConvergenceMonitor,
+    tick_observer: TickObserver,
+    /// Generation each living founder lineage was founded in.
+    lineage_origins: BTreeMap<u32, u32>,
+    /// Best `max_fitness` seen so far and how many generations ago it was
+    /// last beaten, for `MutationSchedule::StagnationAdaptive`.
+    best_fitness: f32,
+    stagnant_generations: u32,
+    /// Fittest warrior seen at any generation's end, for `global_elitism`.
+    hall_of_fame: Option<NeuralWarrior>,
+    /// Genome of the previous generation's fittest survivor.
+    previous_best_genome: Option<Genome>,
+    /// Environment energy flows when the current generation started.
+    generation_energy_flows: EnergyFlows,
+    /// Instruction cost each warrior spent this generation, by id.
+    compute_usage: HashMap<u32, u32>,
+    /// Reused each tick so deciding doesn't reallocate the action map.
+    action_map: ActionMap,
+    /// Reused output buffer for the warriors' forward passes.
+    network_outputs: Vec<f32>,
+    rng: StdRng,
+}
+
+/// Callback installed with `NeuralArenaSimulation::set_tick_observer`.
+pub type TickObserverFn = Box<dyn FnMut(&SimulationStatistics) + Send>;
+
+/// Holds the optional tick observer. Clones of a simulation start without
+/// one, since boxed closures can't be cloned.
+#[derive(Default)]
+struct TickObserver(Option<TickObserverFn>);
+
+impl TickObserver {
+    fn is_set(&self) -> bool {
+        self.0.is_some()
+    }
+    
+    fn notify(&mut self, statistics: &SimulationStatistics) {
+        if let Some(observer) = self.0.as_mut() {
+            observer(statistics);
+        }
+    }
+}
+
+impl Clone for TickObserver {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl std::fmt::Debug for TickObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.is_set() { "TickObserver(Some(..))" } else { "TickObserver(None)" })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SimulationConfig {
     pub max_population: usize,
+    pub arena_width: f32,
+    pub arena_height: f32,
+    pub generation_ticks: u32,
     pub vm_memory_size: usize,
     pub territory_size: usize,
     pub target_species_count: usize,
+    /// Base per-byte mutation rate; see `mutation_schedule`.
     pub mutation_rate: f32,
+    /// How the base mutation rate changes over the run.
+    pub mutation_schedule: MutationSchedule,
     pub survival_threshold: f32,
     pub fitness_sharing: bool,
     pub elitism_rate: f32,
+    /// Reinject the all-time best warrior, genome unchanged, into every
+    /// new generation.
+    pub global_elitism: bool,
     pub tournament_size: usize,
+    /// Draw parent-selection tournament candidates with replacement.
+    pub tournament_replacement: bool,
     pub max_generations: u32,
     pub performance_target_rps: u32, // rounds per second
+    pub seed: Option<u64>,
+    pub action_temperature: f32, // 0.0 = deterministic argmax
+    pub evolvable_temperature: bool,
+    pub auto_adjust_threshold: bool,
+    pub vm_budget_per_tick: u32, // 0 = warriors share the global VM pool
+    pub vm_overdraft_energy_penalty: f32,
+    pub vm_savings_fitness_bonus: f32,
+    /// Fitness per territory-tick held (`BehaviorStats::territory_ticks`).
+    pub territory_fitness_bonus: f32,
+    /// Weight of an efficiency term added to fitness: energy held per
+    /// unit of compute spent this generation (plus one). 0 disables it.
+    pub compute_efficiency_fitness_bonus: f32,
+    /// Probability that an offspring loses one random genome byte, both
+    /// between generations and on Replicate.
+    pub genome_deletion_rate: f32,
+    pub parsimony_coefficient: f32,
+    /// Keeps the fitness offspring are allocated by non-negative.
+    pub fitness_floor: FitnessFloor,
+    /// How a collapsed population (10 survivors or fewer) is restocked.
+    pub recovery_config: RecoveryConfig,
+    /// Relative frequency of each genome mutation operator.
+    pub mutation_weights: MutationWeights,
+    /// Whether the arena edges are walls or wrap around.
+    pub boundary_mode: BoundaryMode,
+    /// How randomly created genomes decode into networks. Offspring
+    /// inherit their parent's codec.
+    pub genome_codec: GenomeCodecKind,
+    /// Instructions evolved programs may use; others are rejected by the VM.
+    pub enabled_opcodes: Vec<OpCode>,
+    /// Cap on any single species' share of the next generation.
+    pub max_offspring_fraction: f32,
+    /// Spread speciation over this many final ticks of each generation
+    /// instead of doing it all at the boundary; 0 keeps it one-shot.
+    /// Warriors that die inside the window can still seed species, so
+    /// memberships may differ slightly from one-shot speciation.
+    pub speciation_slice_ticks: u32,
+    pub sensor_config: SensorConfig,
+    pub pressure_config: PressureConfig,
+    pub resource_config: ResourceConfig,
+    pub reproduction_config: ReproductionConfig,
+    pub collision_config: CollisionConfig,
+    pub territory_config: TerritoryConfig,
+    /// Generate safe zones when building the arena terrain.
+    pub safe_zones: bool,
+    /// Where new warriors are placed in the arena.
+    pub spawn_strategy: SpawnStrategy,
+    /// Random environmental events.
+    pub event_config: EventConfig,
+    /// Move outputs steer relative to each warrior's heading instead of
+    /// in absolute directions.
+    pub relative_steering: bool,
+    /// Ticks per unit of warrior age; see `Environment::ticks_per_age_unit`.
+    pub ticks_per_age_unit: u32,
+    /// Recent actions kept per warrior (capped at 1 in fast mode).
+    pub action_history_capacity: usize,
+    /// Fraction of combat damage the attacker absorbs as energy.
+    pub combat_energy_transfer: f32,
+    /// Skip optional bookkeeping (environment history, change log, lineage
+    /// tracking, offspring records, the report-only `BehaviorStats` compute
+    /// counters; action histories capped at 1) for benchmarks and sweeps. Never changes simulation outcomes.
+    pub fast_mode: bool,
+    /// Copy every installed offspring into `GenerationResult::next_generation`.
+    pub record_offspring: bool,
+    /// Share of all decisions above which a single action counts as dominant.
+    pub degenerate_action_threshold: f32,
+    /// Consecutive dominated generations before a warning is raised.
+    pub degenerate_action_generations: u32,
+    /// Normalized action entropy below which a generation counts as
+    /// behaviorally converged; 0 disables convergence detection.
+    pub behavioral_entropy_floor: f32,
+    /// Consecutive converged generations before a warning is raised.
+    pub behavioral_convergence_generations: u32,
+    /// Mutation rate multiplier applied while the population stays
+    /// converged (1 = no response beyond the warning).
+    pub convergence_mutation_boost: f32,
 }
 
 impl Default for SimulationConfig {
     fn default() -> Self {
         Self {
             max_population: 200,
+            arena_width: 1000.0,
+            arena_height: 1000.0,
+            generation_ticks: 1000,
             vm_memory_size: 2048,
             territory_size: 64,
             target_species_count: 8,
             mutation_rate: 0.05,
+            mutation_schedule: MutationSchedule::Constant,
             survival_threshold: 0.3,
             fitness_sharing: true,
             elitism_rate: 0.1,
+            global_elitism: false,
             tournament_size: 3,
+            tournament_replacement: true,
             max_generations: 1000,
             performance_target_rps: 1000,
+            seed: None,
+            action_temperature: 0.0,
+            evolvable_temperature: false,
+            auto_adjust_threshold: true,
+            vm_budget_per_tick: 0,
+            vm_overdraft_energy_penalty: 0.05,
+            vm_savings_fitness_bonus: 0.001,
+            territory_fitness_bonus: 0.0,
+            compute_efficiency_fitness_bonus: 0.0,
+            genome_deletion_rate: 0.0,
+            parsimony_coefficient: 0.0,
+            fitness_floor: FitnessFloor::default(),
+            recovery_config: RecoveryConfig::default(),
+            mutation_weights: MutationWeights::default(),
+            boundary_mode: BoundaryMode::default(),
+            genome_codec: GenomeCodecKind::default(),
+            enabled_opcodes: OpCode::ALL.to_vec(),
+            max_offspring_fraction: 1.0,
+            speciation_slice_ticks: 0,
+            sensor_config: SensorConfig::default(),
+            pressure_config: PressureConfig::default(),
+            resource_config: ResourceConfig::default(),
+            reproduction_config: ReproductionConfig::default(),
+            collision_config: CollisionConfig::default(),
+            territory_config: TerritoryConfig::default(),
+            safe_zones: true,
+            spawn_strategy: SpawnStrategy::default(),
+            event_config: EventConfig::default(),
+            relative_steering: false,
+            ticks_per_age_unit: 1,
+            action_history_capacity: NeuralWarrior::DEFAULT_ACTION_HISTORY_CAPACITY,
+            combat_energy_transfer: 0.0,
+            fast_mode: false,
+            record_offspring: true,
+            degenerate_action_threshold: 0.95,
+            degenerate_action_generations: 3,
+            behavioral_entropy_floor: 0.0,
+            behavioral_convergence_generations: 3,
+            convergence_mutation_boost: 1.0,
         }
     }
 }
 
+/// Emergency restocking used when a generation ends with too few
+/// survivors to select from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecoveryConfig {
+    /// Share of `max_population` the emergency population is sized to.
+    pub fraction: f32,
+    /// Per-byte mutation rate applied to clones of the best survivors.
+    pub mutation_rate: f32,
+    /// Energy every emergency warrior starts with.
+    pub spawn_energy: f32,
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            fraction: 0.25,
+            mutation_rate: 0.2,
+            spawn_energy: 100.0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ConfigError {
+    #[error("{field} must be positive")]
+    NotPositive { field: &'static str },
+    #[error("{field} must be within [0, 1], got {value}")]
+    OutOfUnitRange { field: &'static str, value: f32 },
+    #[error("{field} must be finite, got {value}")]
+    NotFinite { field: &'static str, value: f32 },
+}
+
+impl SimulationConfig {
+    /// Checks the settings that would otherwise produce an empty arena,
+    /// zero-length generations, nonsensical probabilities or non-finite
+    /// resource energies.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let positive = [
+            ("max_population", self.max_population > 0),
+            ("arena_width", self.arena_width > 0.0),
+            ("arena_height", self.arena_height > 0.0),
+            ("generation_ticks", self.generation_ticks > 0),
+            ("ticks_per_age_unit", self.ticks_per_age_unit > 0),
+            ("tournament_size", self.tournament_size > 0),
+            ("vm_memory_size", self.vm_memory_size > 0),
+            ("territory_size", self.territory_size > 0),
+            ("max_offspring_fraction", self.max_offspring_fraction > 0.0),
+            ("recovery_config.spawn_energy", self.recovery_config.spawn_energy > 0.0),
+            ("reproduction_config.litter_size", self.reproduction_config.litter_size > 0),
+        ];
+        if let Some(&(field, _)) = positive.iter().find(|(_, ok)| !ok) {
+            return Err(ConfigError::NotPositive { field });
+        }
+
+        let unit = [
+            ("mutation_rate", self.mutation_rate),
+            ("survival_threshold", self.survival_threshold),
+            ("elitism_rate", self.elitism_rate),
+            ("genome_deletion_rate", self.genome_deletion_rate),
+            ("max_offspring_fraction", self.max_offspring_fraction),
+            ("combat_energy_transfer", self.combat_energy_transfer),
+            ("degenerate_action_threshold", self.degenerate_action_threshold),
+            ("behavioral_entropy_floor", self.behavioral_entropy_floor),
+            ("resource_config.spawn_rate", self.resource_config.spawn_rate),
+            ("reproduction_config.child_energy_fraction", self.reproduction_config.child_energy_fraction),
+            ("recovery_config.fraction", self.recovery_config.fraction),
+            ("recovery_config.mutation_rate", self.recovery_config.mutation_rate),
+        ];
+        if let Some(&(field, value)) = unit.iter().find(|(_, value)| !(0.0..=1.0).contains(value)) {
+            return Err(ConfigError::OutOfUnitRange { field, value });
+        }
+        
+        let schedule_rates: &[(&'static str, f32)] = match self.mutation_schedule {
+            MutationSchedule::Constant => &[],
+            MutationSchedule::Linear { start, end } => &[("mutation_schedule.start", start), ("mutation_schedule.end", end)],
+            MutationSchedule::StagnationAdaptive { max_rate, .. } => &[("mutation_schedule.max_rate", max_rate)],
+        };
+        if let Some(&(field, value)) = schedule_rates.iter().find(|(_, value)| !(0.0..=1.0).contains(value)) {
+            return Err(ConfigError::OutOfUnitRange { field, value });
+        }
+
+        let (low, high) = self.resource_config.energy_range;
+        let finite = [("resource_config.energy_range.0", low), ("resource_config.energy_range.1", high)];
+        if let Some(&(field, value)) = finite.iter().find(|(_, value)| !value.is_finite()) {
+            return Err(ConfigError::NotFinite { field, value });
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimulationStatistics {
     pub generation: u32,
@@ -64,10 +356,88 @@ pub struct SimulationStatistics {
     pub survival_rate: f32,
     pub average_age: f32,
     pub max_lineage_depth: u32,
+    /// Average fitness per unit of compute a warrior spent this generation.
     pub computational_efficiency: f32,
     pub rounds_per_second: f32,
     pub resource_utilization: f32,
     pub environmental_pressure: f32,
+    pub mean_genome_size: f32,
+    pub dominant_action_share: f32,
+    /// Normalized entropy of this generation's action distribution.
+    pub behavioral_entropy: f32,
+    /// Spread of per-warrior action frequencies among the survivors.
+    pub behavior_variance: f32,
+    /// See `LineageSummary`.
+    pub largest_lineage_share: f32,
+    pub surviving_lineages: usize,
+    pub oldest_lineage_age: u32,
+    /// Energy held by living warriors.
+    pub warrior_energy: f32,
+    /// Energy waiting in uncollected resources.
+    pub resource_energy: f32,
+    /// Energy spawned as resources or collection bonuses so far this
+    /// generation; see `EnergyFlows` for what isn't counted.
+    pub energy_injected: f32,
+    /// Energy lost to aging, resource decay and scarcity removals so far
+    /// this generation.
+    pub energy_destroyed: f32,
+    /// `energy_injected` per unit of standing warrior and resource energy;
+    /// 0 when the arena holds none.
+    pub biomass_turnover: f32,
+}
+
+/// Base mutation rate over the course of a run. Species still add their
+/// own stagnation and small-population bonuses on top.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MutationSchedule {
+    /// Always `SimulationConfig::mutation_rate`.
+    Constant,
+    /// Interpolates from `start` at generation 0 to `end` at
+    /// `max_generations`, then holds `end`.
+    Linear { start: f32, end: f32 },
+    /// `mutation_rate` while the best fitness keeps improving, rising
+    /// linearly to `max_rate` once it has stalled for `patience`
+    /// generations.
+    StagnationAdaptive { patience: u32, max_rate: f32 },
+}
+
+impl MutationSchedule {
+    pub fn rate(&self, base_rate: f32, generation: u32, max_generations: u32, stagnant_generations: u32) -> f32 {
+        match *self {
+            MutationSchedule::Constant => base_rate,
+            MutationSchedule::Linear { start, end } => {
+                let progress = (generation as f32 / max_generations.max(1) as f32).min(1.0);
+                start + (end - start) * progress
+            },
+            MutationSchedule::StagnationAdaptive { patience, max_rate } => {
+                let stall = (stagnant_generations as f32 / patience.max(1) as f32).min(1.0);
+                base_rate + (max_rate - base_rate) * stall
+            },
+        }
+    }
+}
+
+/// Objectives that can be traded off against each other in a Pareto
+/// analysis. All objectives are maximized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectiveKind {
+    Survival,
+    Combat,
+    Energy,
+    Fitness,
+    LineageDepth,
+}
+
+impl ObjectiveKind {
+    pub fn evaluate(&self, warrior: &NeuralWarrior) -> f32 {
+        match self {
+            ObjectiveKind::Survival => warrior.age as f32,
+            ObjectiveKind::Combat => warrior.damage_dealt,
+            ObjectiveKind::Energy => warrior.energy,
+            ObjectiveKind::Fitness => warrior.fitness_score,
+            ObjectiveKind::LineageDepth => warrior.lineage_depth as f32,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -76,49 +446,156 @@ pub struct GenerationResult {
     pub survivors: Vec<NeuralWarrior>,
     pub extinct_lineages: Vec<u32>,
     pub new_species: usize,
+    /// Species alive after this generation's speciation.
+    pub species_count: usize,
+    /// Species that existed before this generation's speciation and not after.
+    pub extinct_species: Vec<u32>,
+    /// Every species alive after this generation's speciation, with its
+    /// members drawn from `survivors`.
+    pub species_summary: Vec<SpeciesSummary>,
     pub performance_metrics: PerformanceMetrics,
+    pub warnings: Vec<SimulationWarning>,
+    /// The population installed for the next generation, in installation
+    /// order. Empty unless `record_offspring` is set (and never in fast mode).
+    pub next_generation: Vec<GenomeSummary>,
+    /// How the fittest survivor's genome differs from the previous
+    /// generation's fittest; `None` without survivors or a previous best.
+    pub best_genome_delta: Option<BestGenomeDelta>,
+}
+
+/// One member of a newly installed generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenomeSummary {
+    pub id: u32,
+    pub genome: Genome,
+    /// Species of the first parent, which the offspring was bred in;
+    /// `None` for warriors without a speciated parent.
+    pub species_id: Option<u32>,
+    pub parent_ids: Vec<u32>,
 }
 
+/// Everything `run_simulation` produced: the per-generation results and a
+/// summary of them.
 #[derive(Debug, Clone)]
+pub struct SimulationOutcome {
+    pub results: Vec<GenerationResult>,
+    pub report: RunReport,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct PerformanceMetrics {
     pub simulation_time_ms: u128,
     pub rounds_per_second: f32,
     pub vm_cycles_executed: u64,
     pub memory_allocations: usize,
+    /// Territory allocations that failed because the allocator was full.
+    pub allocation_failures: usize,
+    /// Whether the allocator had no free territory left at the end.
+    pub allocator_saturated: bool,
     pub species_operations: usize,
+    /// Time spent choosing actions: sensing, networks and VM programs.
+    pub decide_time_us: u128,
+    /// Time spent ticking the environment, resolving actions and scoring them.
+    pub act_time_us: u128,
+    /// Time spent speciating, selecting and installing the next generation.
+    pub evolve_time_us: u128,
 }
 
 impl NeuralArenaSimulation {
     pub fn new(config: SimulationConfig) -> Self {
-        let environment = Environment::new(1000.0, 1000.0, config.max_population);
-        let vm = VirtualMachine::new(config.vm_memory_size);
+        // Every subsystem gets its own stream, see `crate::rng`
+        let master_seed = config.seed.unwrap_or_else(rand::random);
+        let rng = RngStream::Simulation.rng(master_seed);
+        let environment = Self::build_environment(&config, RngStream::Environment.seed(master_seed));
+        let vm = Self::build_vm(&config, RngStream::Vm.seed(master_seed));
         let memory_allocator = MemoryAllocator::new(config.vm_memory_size, config.territory_size);
-        let speciation_manager = SpeciationManager::new(config.target_species_count);
+        let speciation_manager = Self::build_speciation_manager(&config, RngStream::Speciation.seed(master_seed));
+        let action_monitor = DegenerateActionMonitor::new(
+            config.degenerate_action_threshold,
+            config.degenerate_action_generations,
+        );
+        let convergence_monitor = ConvergenceMonitor::new(
+            config.behavioral_entropy_floor,
+            config.behavioral_convergence_generations,
+        );
         
         Self {
             environment,
             vm,
             memory_allocator,
             speciation_manager,
+            lineage: LineageTracker::new(),
             simulation_config: config,
             statistics: SimulationStatistics::default(),
             generation: 0,
             tick: 0,
             is_running: false,
+            action_histogram: ActionHistogram::new(),
+            warnings: Vec::new(),
+            population_stats: PopulationAccumulator::new(),
+            action_monitor,
+            convergence_monitor,
+            tick_observer: TickObserver::default(),
+            lineage_origins: BTreeMap::new(),
+            best_fitness: f32::NEG_INFINITY,
+            stagnant_generations: 0,
+            hall_of_fame: None,
+            previous_best_genome: None,
+            generation_energy_flows: EnergyFlows::default(),
+            compute_usage: HashMap::new(),
+            action_map: ActionMap::new(),
+            network_outputs: Vec::new(),
+            rng,
         }
     }
     
     pub fn initialize_population(&mut self, initial_population: usize) {
         for _ in 0..initial_population.min(self.simulation_config.max_population) {
-            let genome = Genome::new_random();
-            let warrior = NeuralWarrior::new(genome, rand::random());
-            self.environment.add_warrior(warrior);
+            let genome = self.random_genome();
+            self.inject_warrior(genome);
         }
         
         self.is_running = true;
     }
     
-    pub fn run_simulation(&mut self, max_ticks: Option<u64>) -> Vec<GenerationResult> {
+    /// Like `initialize_population`, but with `Genome::canonical_set`
+    /// genomes, so seeded benchmark runs start from the same population.
+    pub fn initialize_canonical_population(&mut self, initial_population: usize) {
+        let count = initial_population.min(self.simulation_config.max_population);
+        for mut genome in Genome::canonical_set(count) {
+            genome.set_codec(self.simulation_config.genome_codec);
+            self.inject_warrior(genome);
+        }
+        
+        self.is_running = true;
+    }
+    
+    /// Adds a warrior with `genome` at a position drawn from the seeded
+    /// RNG, so seeded runs lay out identically. Returns its id, or `None`
+    /// when the arena is at capacity.
+    pub fn inject_warrior(&mut self, genome: Genome) -> Option<u32> {
+        if self.environment.warriors.len() >= self.environment.carrying_capacity {
+            return None;
+        }
+        let position = self.random_arena_position();
+        let warrior_id = self.unused_warrior_id();
+        let mut warrior = NeuralWarrior::with_position(genome, warrior_id, position);
+        warrior.set_action_history_capacity(self.action_history_capacity());
+        let founder_id = warrior.founder_id;
+        if !self.environment.add_warrior(warrior) {
+            return None;
+        }
+        self.environment.sync_changes();
+        self.lineage_origins.entry(founder_id).or_insert(self.generation);
+        Some(warrior_id)
+    }
+    
+    /// A random id that no warrior in the arena holds.
+    fn unused_warrior_id(&mut self) -> u32 {
+        unused_id(&mut self.rng, |id| self.environment.warriors.contains_key(&id))
+    }
+    
+    pub fn run_simulation(&mut self, max_ticks: Option<u64>) -> SimulationOutcome {
         let mut generation_results = Vec::new();
         
         while self.is_running {
@@ -147,77 +624,170 @@ impl NeuralArenaSimulation {
             }
         }
         
-        generation_results
+        let report = RunReport::from_results(&generation_results, &self.statistics);
+        SimulationOutcome {
+            results: generation_results,
+            report,
+        }
     }
     
     pub fn run_generation(&mut self) -> GenerationResult {
-        let start_time = std::time::Instant::now();
-        let mut performance_metrics = PerformanceMetrics {
-            simulation_time_ms: 0,
-            rounds_per_second: 0.0,
-            vm_cycles_executed: 0,
-            memory_allocations: 0,
-            species_operations: 0,
-        };
+        let start_time = Instant::now();
+        let mut performance_metrics = PerformanceMetrics::default();
+        let (mut decide_time, mut act_time, mut evolve_time) = (Duration::ZERO, Duration::ZERO, Duration::ZERO);
         
         self.generation += 1;
-        let generation_ticks = 1000; // Each generation lasts 1000 ticks
+        self.generation_energy_flows = self.environment.energy_flows;
+        self.compute_usage.clear();
+        let generation_ticks = self.simulation_config.generation_ticks;
+        self.population_stats = PopulationAccumulator::from_warriors(self.environment.warriors.values());
         
         // Run generation simulation
-        for _ in 0..generation_ticks {
+        let slice_ticks = self.simulation_config.speciation_slice_ticks.min(generation_ticks);
+        for tick_index in 0..generation_ticks {
             self.tick += 1;
             
             // Environment update
+            let act_start = Instant::now();
             let env_update = self.environment.tick();
+            self.forget_dead_warriors(&env_update);
+            act_time += act_start.elapsed();
             
-            // Get current warriors
-            let warriors: Vec<NeuralWarrior> = self.environment.warriors.values().cloned().collect();
-            if warriors.is_empty() {
+            if self.environment.warriors.is_empty() {
+                self.population_stats.clear();
                 break;
             }
             
-            // Execute neural networks and VM instructions
-            let warrior_actions = self.execute_neural_decisions(&warriors, &mut performance_metrics);
+            // Execute neural networks and VM instructions. The state's copy of
+            // the warriors is the only per-tick snapshot of the population.
+            let decide_start = Instant::now();
+            let environment_state = self.environment.get_environment_state();
+            let mut warrior_actions = std::mem::take(&mut self.action_map);
+            self.execute_neural_decisions(&environment_state, &mut performance_metrics, &mut warrior_actions);
+            decide_time += decide_start.elapsed();
             
             // Execute actions in environment
-            let action_results = self.environment.execute_warrior_actions(warrior_actions);
+            let act_start = Instant::now();
+            let action_results = self.environment.execute_action_map(&mut warrior_actions);
+            self.action_map = warrior_actions;
             
             // Update fitness based on survival and performance
             self.update_fitness_scores(&action_results);
+            act_time += act_start.elapsed();
+            
+            // Time-sliced speciation: place a share of the population each tick
+            let remaining_ticks = generation_ticks - tick_index;
+            if remaining_ticks <= slice_ticks {
+                let evolve_start = Instant::now();
+                let warriors = &environment_state.warriors;
+                let batch = warriors.len().div_ceil(remaining_ticks as usize);
+                self.speciation_manager.speciate_incremental(warriors, batch);
+                evolve_time += evolve_start.elapsed();
+            }
             
             performance_metrics.vm_cycles_executed += self.vm.cycle_count();
+            
+            if self.tick_observer.is_set() {
+                self.sync_environment_statistics();
+                self.tick_observer.notify(&self.statistics);
+            }
         }
         
         // Collect survivors
+        let evolve_start = Instant::now();
         let survivors: Vec<NeuralWarrior> = self.environment.warriors.values().cloned().collect();
+        self.update_hall_of_fame(&survivors);
+        let best_genome_delta = self.track_best_genome(&survivors);
         
         // Apply speciation and evolution
+        self.speciation_manager.base_mutation_rate = self.effective_mutation_rate();
         let initial_species_count = self.speciation_manager.species.len();
-        self.speciation_manager.speciate(&survivors);
+        let previous_species: Vec<u32> = self.speciation_manager.species.keys().copied().collect();
+        if self.speciation_manager.is_speciation_pending() {
+            self.speciation_manager.finish_speciation(&survivors);
+        } else {
+            self.speciation_manager.speciate(&survivors);
+        }
         performance_metrics.species_operations += 1;
         
         let new_species = self.speciation_manager.species.len().saturating_sub(initial_species_count);
+        let extinct_species: Vec<u32> = previous_species.into_iter()
+            .filter(|species_id| !self.speciation_manager.species.contains_key(species_id))
+            .collect();
+        let species_count = self.speciation_manager.species.len();
+        let species_summary = self.speciation_manager.species_summaries_for(&survivors);
         
         // Evolve population
-        let next_generation = if survivors.len() > 10 {
+        let mut next_generation = if survivors.len() > 10 {
             self.speciation_manager.perform_species_selection(&survivors)
         } else {
             // Emergency population boost
             self.create_emergency_population(&survivors)
         };
+        if self.simulation_config.global_elitism {
+            self.reinject_champion(&mut next_generation);
+        }
         
         // Replace population
         self.environment.warriors.clear();
+        let history_capacity = self.action_history_capacity();
+        let record_offspring = self.simulation_config.record_offspring && !self.simulation_config.fast_mode;
+        let mut installed = Vec::new();
         for warrior in &next_generation {
-            self.environment.add_warrior(warrior.clone());
+            if !self.simulation_config.fast_mode {
+                self.lineage.record(warrior);
+            }
+            let mut warrior = warrior.clone();
+            warrior.set_action_history_capacity(history_capacity);
+            // Offspring carry a parent's position until the arena places them
+            warrior.position = self.random_arena_position();
+            let summary = record_offspring.then(|| GenomeSummary {
+                id: warrior.id,
+                genome: warrior.genome.clone(),
+                species_id: warrior.parent_ids.first().and_then(|&parent| self.speciation_manager.species_of(parent)),
+                parent_ids: warrior.parent_ids.clone(),
+            });
+            if self.environment.add_warrior(warrior) {
+                installed.extend(summary);
+            }
         }
+        self.environment.sync_changes();
+        evolve_time += evolve_start.elapsed();
         
         // Update statistics
         self.update_statistics(&survivors);
+        if self.statistics.max_fitness > self.best_fitness {
+            self.best_fitness = self.statistics.max_fitness;
+            self.stagnant_generations = 0;
+        } else {
+            self.stagnant_generations += 1;
+        }
+        
+        performance_metrics.allocator_saturated = self.memory_allocator.is_saturated();
+        let saturation = (performance_metrics.allocation_failures > 0).then_some(SimulationWarning::AllocatorSaturated {
+            generation: self.generation,
+            failures: performance_metrics.allocation_failures,
+        });
+        let warnings: Vec<SimulationWarning> = self.action_monitor
+            .observe(self.generation, &self.action_histogram)
+            .into_iter()
+            .chain(self.convergence_monitor.observe(self.generation, self.statistics.behavioral_entropy))
+            .chain(saturation)
+            .collect();
+        self.speciation_manager.mutation_boost = if self.convergence_monitor.is_converged() {
+            self.simulation_config.convergence_mutation_boost
+        } else {
+            1.0
+        };
+        self.warnings.extend(warnings.iter().cloned());
+        self.action_histogram.clear();
         
         // Calculate performance metrics
         let elapsed = start_time.elapsed();
         performance_metrics.simulation_time_ms = elapsed.as_millis();
+        performance_metrics.decide_time_us = decide_time.as_micros();
+        performance_metrics.act_time_us = act_time.as_micros();
+        performance_metrics.evolve_time_us = evolve_time.as_micros();
         performance_metrics.rounds_per_second = (generation_ticks as f32 / elapsed.as_secs_f32())
             .min(self.simulation_config.performance_target_rps as f32);
         
@@ -226,47 +796,195 @@ impl NeuralArenaSimulation {
             survivors,
             extinct_lineages: Vec::new(), // TODO: Track extinct lineages
             new_species,
+            species_count,
+            extinct_species,
+            species_summary,
             performance_metrics,
+            warnings,
+            next_generation: installed,
+            best_genome_delta,
         }
     }
     
+    /// Runs whole generations until `target_generation` is reached or the
+    /// population goes extinct. Returns the last generation's result, or
+    /// `None` if the simulation was already at or past the target.
+    pub fn advance_to_generation(&mut self, target_generation: u32) -> Option<GenerationResult> {
+        let mut last_result = None;
+        
+        while self.generation < target_generation {
+            let result = self.run_generation();
+            let extinct = self.environment.warriors.is_empty();
+            last_result = Some(result);
+            
+            if extinct {
+                break;
+            }
+        }
+        
+        last_result
+    }
+    
+    /// Per-warrior action history length in effect for this run.
+    pub fn action_history_capacity(&self) -> usize {
+        if self.simulation_config.fast_mode {
+            self.simulation_config.action_history_capacity.min(1)
+        } else {
+            self.simulation_config.action_history_capacity
+        }
+    }
+    
+    /// Deep copy of the whole simulation, including RNG state, for branching
+    /// experiments. A fork driven exactly like the original replays the same
+    /// future; anything done to one branch leaves the other untouched.
+    pub fn fork(&self) -> Self {
+        self.clone()
+    }
+    
+    /// Scores `champion` against a fixed set of opponents for `ticks` ticks
+    /// in a fresh arena built from this simulation's config, with no
+    /// selection or reproduction between ticks. Returns the champion's
+    /// fitness at the end of the bout, or when it died. The running
+    /// simulation is left untouched; seeded configs give repeatable scores.
+    pub fn evaluate_against(&self, champion: Genome, opponents: Vec<Genome>, ticks: u64) -> f32 {
+        let config = SimulationConfig {
+            max_population: self.simulation_config.max_population.max(opponents.len() + 1),
+            fast_mode: true,
+            ..self.simulation_config.clone()
+        };
+        let mut arena = Self::new(config);
+        
+        let champion_id = 0;
+        for (id, genome) in std::iter::once(champion).chain(opponents).enumerate() {
+            let position = arena.random_arena_position();
+            let mut warrior = NeuralWarrior::with_position(genome, id as u32, position);
+            warrior.set_action_history_capacity(arena.action_history_capacity());
+            arena.environment.add_warrior(warrior);
+        }
+        
+        let mut fitness = 0.0;
+        for _ in 0..ticks {
+            arena.single_tick();
+            match arena.environment.warriors.get(&champion_id) {
+                Some(warrior) => fitness = warrior.fitness_score,
+                None => break,
+            }
+        }
+        fitness
+    }
+    
     pub fn single_tick(&mut self) -> EnvironmentUpdate {
         self.tick += 1;
         
         // Environment update
         let env_update = self.environment.tick();
+        self.forget_dead_warriors(&env_update);
         
-        // Get current warriors
-        let warriors: Vec<NeuralWarrior> = self.environment.warriors.values().cloned().collect();
-        
-        if !warriors.is_empty() {
-            let mut perf_metrics = PerformanceMetrics {
-                simulation_time_ms: 0,
-                rounds_per_second: 0.0,
-                vm_cycles_executed: 0,
-                memory_allocations: 0,
-                species_operations: 0,
-            };
+        if !self.environment.warriors.is_empty() {
+            let mut perf_metrics = PerformanceMetrics::default();
             
             // Execute neural decisions
-            let warrior_actions = self.execute_neural_decisions(&warriors, &mut perf_metrics);
+            let environment_state = self.environment.get_environment_state();
+            let mut warrior_actions = std::mem::take(&mut self.action_map);
+            self.execute_neural_decisions(&environment_state, &mut perf_metrics, &mut warrior_actions);
             
             // Execute actions
-            let action_results = self.environment.execute_warrior_actions(warrior_actions);
+            let action_results = self.environment.execute_action_map(&mut warrior_actions);
+            self.action_map = warrior_actions;
             
             // Update fitness
             self.update_fitness_scores(&action_results);
         }
         
+        // Record the actions' effects so state deltas reflect the whole tick
+        self.environment.sync_changes();
+        self.sync_environment_statistics();
+        self.tick_observer.notify(&self.statistics);
         env_update
     }
     
+    /// Calls `observer` after every tick, whether driven by `single_tick`
+    /// or by `run_generation`. Environment-level statistics are current;
+    /// population-level ones are refreshed at generation boundaries.
+    /// Replaces any previous observer.
+    pub fn set_tick_observer(&mut self, observer: TickObserverFn) {
+        self.tick_observer = TickObserver(Some(observer));
+    }
+    
+    pub fn clear_tick_observer(&mut self) {
+        self.tick_observer = TickObserver::default();
+    }
+    
     pub fn get_statistics(&self) -> &SimulationStatistics {
         &self.statistics
     }
     
     pub fn get_species_stats(&self) -> SpeciesStats {
-        self.speciation_manager.get_species_stats()
+        SpeciesStats {
+            lineage: self.lineage_summary(),
+            ..self.speciation_manager.get_species_stats()
+        }
+    }
+    
+    /// Founder lineages among the living warriors.
+    pub fn lineage_summary(&self) -> LineageSummary {
+        LineageSummary::from_population(self.environment.warriors.values(), &self.lineage_origins, self.generation)
+    }
+    
+    /// Graphviz DOT rendering of every parent -> child relationship
+    /// observed so far in the run.
+    pub fn lineage_to_dot(&self) -> String {
+        self.lineage.to_dot()
+    }
+    
+    /// Ids of the non-dominated warriors across the given objectives, sorted
+    /// ascending. A warrior is dominated when another is at least as good on
+    /// every objective and strictly better on one.
+    pub fn pareto_front(&self, objectives: &[ObjectiveKind]) -> Vec<u32> {
+        let scored: Vec<(u32, Vec<f32>)> = self.environment.warriors.values()
+            .map(|warrior| {
+                let scores = objectives.iter().map(|objective| objective.evaluate(warrior)).collect();
+                (warrior.id, scores)
+            })
+            .collect();
+        
+        let dominates = |a: &[f32], b: &[f32]| {
+            a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+        };
+        
+        let mut front: Vec<u32> = scored.iter()
+            .filter(|(_, scores)| !scored.iter().any(|(_, other)| dominates(other, scores)))
+            .map(|(id, _)| *id)
+            .collect();
+        front.sort_unstable();
+        front
+    }
+    
+    /// Overrides a living warrior's display name. Its descendants inherit
+    /// the new name as their surname. Returns false if no such warrior.
+    pub fn rename_warrior(&mut self, warrior_id: u32, name: impl Into<String>) -> bool {
+        match self.environment.warriors.get_mut(&warrior_id) {
+            Some(warrior) => {
+                warrior.name = name.into();
+                true
+            },
+            None => false,
+        }
+    }
+    
+    pub fn compatibility_threshold(&self) -> f32 {
+        self.speciation_manager.compatibility_threshold
+    }
+    
+    pub fn set_compatibility_threshold(&mut self, threshold: f32) {
+        self.speciation_manager.compatibility_threshold = threshold;
+    }
+    
+    /// Enables or disables the automatic threshold adjustment that steers
+    /// the species count toward `target_species_count`.
+    pub fn set_auto_adjust_threshold(&mut self, enabled: bool) {
+        self.simulation_config.auto_adjust_threshold = enabled;
+        self.speciation_manager.auto_adjust_threshold = enabled;
     }
     
     pub fn pause(&mut self) {
@@ -278,55 +996,298 @@ impl NeuralArenaSimulation {
     }
     
     pub fn reset(&mut self) {
-        self.environment = Environment::new(1000.0, 1000.0, self.simulation_config.max_population);
-        self.vm = VirtualMachine::new(self.simulation_config.vm_memory_size);
+        self.environment = Self::build_environment(&self.simulation_config, self.rng.gen());
+        self.vm = Self::build_vm(&self.simulation_config, self.rng.gen());
         self.memory_allocator = MemoryAllocator::new(
             self.simulation_config.vm_memory_size, 
             self.simulation_config.territory_size
         );
-        self.speciation_manager = SpeciationManager::new(self.simulation_config.target_species_count);
+        self.speciation_manager = Self::build_speciation_manager(&self.simulation_config, self.rng.gen());
+        self.lineage.clear();
+        self.action_histogram.clear();
+        self.action_monitor.reset();
+        self.convergence_monitor.reset();
+        self.warnings.clear();
+        self.population_stats.clear();
         self.generation = 0;
         self.tick = 0;
         self.statistics = SimulationStatistics::default();
+        self.lineage_origins.clear();
+        self.best_fitness = f32::NEG_INFINITY;
+        self.stagnant_generations = 0;
+        self.hall_of_fame = None;
+        self.previous_best_genome = None;
+        self.generation_energy_flows = self.environment.energy_flows;
+        self.compute_usage.clear();
+    }
+    
+    /// Rebuilds only the environment (new terrain, fresh resources, tick 0)
+    /// and re-adds the current warriors in it. Genomes, ids, names and
+    /// lineage carry over; energy, age, position and other per-life state
+    /// start fresh. Generation count, species and lineage records are kept.
+    pub fn reset_environment_keep_population(&mut self) {
+        let warriors = std::mem::take(&mut self.environment.warriors);
+        self.environment = Self::build_environment(&self.simulation_config, self.rng.gen());
+        self.generation_energy_flows = self.environment.energy_flows;
+        
+        let history_capacity = self.action_history_capacity();
+        for warrior in warriors.into_values() {
+            let position = self.random_arena_position();
+            let mut fresh = Self::reborn(warrior, position);
+            fresh.set_action_history_capacity(history_capacity);
+            self.environment.add_warrior(fresh);
+        }
+        self.environment.sync_changes();
+        self.population_stats = PopulationAccumulator::from_warriors(self.environment.warriors.values());
+    }
+    
+    /// The fittest warrior seen at the end of any generation so far, as it
+    /// was then.
+    pub fn hall_of_fame(&self) -> Option<&NeuralWarrior> {
+        self.hall_of_fame.as_ref()
+    }
+    
+    fn update_hall_of_fame(&mut self, survivors: &[NeuralWarrior]) {
+        let Some(best) = survivors.iter().max_by(|a, b| a.fitness_score.total_cmp(&b.fitness_score)) else {
+            return;
+        };
+        let record = self.hall_of_fame.as_ref().map_or(f32::NEG_INFINITY, |champion| champion.fitness_score);
+        if best.fitness_score > record {
+            self.hall_of_fame = Some(best.clone());
+        }
+    }
+    
+    /// Diffs the fittest survivor's genome (ties to the lowest id) against
+    /// the previous generation's and remembers it for the next call.
+    fn track_best_genome(&mut self, survivors: &[NeuralWarrior]) -> Option<BestGenomeDelta> {
+        let best = survivors.iter()
+            .max_by(|a, b| a.fitness_score.total_cmp(&b.fitness_score).then(b.id.cmp(&a.id)))?;
+        let delta = self.previous_best_genome.as_ref()
+            .map(|previous| BestGenomeDelta::between(previous.bytes(), best.genome.bytes()));
+        self.previous_best_genome = Some(best.genome.clone());
+        delta
     }
     
-    fn execute_neural_decisions(&mut self, warriors: &[NeuralWarrior], performance_metrics: &mut PerformanceMetrics) -> HashMap<u32, Action> {
-        let mut warrior_actions = HashMap::new();
-        let environment_state = self.environment.get_environment_state();
+    /// Puts the hall-of-fame warrior, same id and genome but a fresh life,
+    /// in place of its namesake in `next_generation` or else the last member.
+    fn reinject_champion(&mut self, next_generation: &mut Vec<NeuralWarrior>) {
+        let Some(champion) = self.hall_of_fame.clone() else {
+            return;
+        };
+        let position = champion.position;
+        let champion = Self::reborn(champion, position);
+        let slot = next_generation.iter().position(|warrior| warrior.id == champion.id)
+            .or(next_generation.len().checked_sub(1));
+        match slot {
+            Some(index) => next_generation[index] = champion,
+            None => next_generation.push(champion),
+        }
+    }
+    
+    /// `warrior`'s identity, genome and lineage at `position` with
+    /// per-life state (energy, age, fitness, histories) reset.
+    fn reborn(warrior: NeuralWarrior, position: (f32, f32)) -> NeuralWarrior {
+        let mut fresh = NeuralWarrior::with_position(warrior.genome, warrior.id, position);
+        fresh.name = warrior.name;
+        fresh.network = warrior.network;
+        fresh.lineage_depth = warrior.lineage_depth;
+        fresh.founder_id = warrior.founder_id;
+        fresh.parent_ids = warrior.parent_ids;
+        fresh
+    }
+    
+    /// Base mutation rate `mutation_schedule` gives for the current
+    /// generation.
+    pub fn effective_mutation_rate(&self) -> f32 {
+        self.simulation_config.mutation_schedule.rate(
+            self.simulation_config.mutation_rate,
+            self.generation,
+            self.simulation_config.max_generations,
+            self.stagnant_generations,
+        )
+    }
+    
+    fn build_environment(config: &SimulationConfig, seed: u64) -> Environment {
+        let mut environment = Environment::with_seed(config.arena_width, config.arena_height, config.max_population, seed);
+        environment.sensor_config = config.sensor_config.clone();
+        environment.pressure_config = config.pressure_config.clone();
+        environment.resource_config = config.resource_config.clone();
+        environment.reproduction_config = config.reproduction_config.clone();
+        environment.collision_config = config.collision_config.clone();
+        environment.territory_config = config.territory_config.clone();
+        environment.mutation_weights = config.mutation_weights;
+        environment.deletion_rate = config.genome_deletion_rate;
+        environment.spawn_strategy = config.spawn_strategy.clone();
+        environment.event_config = config.event_config.clone();
+        environment.relative_steering = config.relative_steering;
+        environment.ticks_per_age_unit = config.ticks_per_age_unit;
+        environment.boundary_mode = config.boundary_mode;
+        if !config.safe_zones {
+            environment.safe_zones.clear();
+        }
+        // The starting stock was spawned under the default resource config
+        environment.respawn_resources();
+        environment.action_costs.combat_energy_transfer = config.combat_energy_transfer;
+        if config.fast_mode {
+            environment.set_history_capacity(0);
+            environment.change_log.set_capacity(0);
+        }
+        environment
+    }
+    
+    fn random_genome(&mut self) -> Genome {
+        let mut genome = Genome::random_with_rng(&mut self.rng);
+        genome.set_codec(self.simulation_config.genome_codec);
+        genome
+    }
+    
+    /// Spawn position chosen by the environment's spawn strategy, drawn
+    /// from the simulation's seeded RNG.
+    fn random_arena_position(&mut self) -> (f32, f32) {
+        self.environment.spawn_position(&mut self.rng)
+    }
+    
+    fn build_vm(config: &SimulationConfig, seed: u64) -> VirtualMachine {
+        let mut vm = VirtualMachine::with_seed(config.vm_memory_size, seed);
+        vm.set_enabled_opcodes(config.enabled_opcodes.clone());
+        vm
+    }
+    
+    fn build_speciation_manager(config: &SimulationConfig, seed: u64) -> SpeciationManager {
+        let mut speciation_manager = SpeciationManager::new(config.target_species_count);
+        speciation_manager.reseed(seed);
+        speciation_manager.auto_adjust_threshold = config.auto_adjust_threshold;
+        speciation_manager.deletion_rate = config.genome_deletion_rate;
+        speciation_manager.parsimony_coefficient = config.parsimony_coefficient;
+        speciation_manager.fitness_floor = config.fitness_floor;
+        speciation_manager.mutation_weights = config.mutation_weights;
+        speciation_manager.base_mutation_rate = config.mutation_rate;
+        speciation_manager.max_offspring_fraction = config.max_offspring_fraction;
+        speciation_manager.tournament_size = config.tournament_size;
+        speciation_manager.tournament_replacement = config.tournament_replacement;
+        speciation_manager
+    }
+    
+    /// Decides every free warrior's action from `environment_state`, the
+    /// population as it stood before this tick's actions, into
+    /// `warrior_actions` (cleared first).
+    fn execute_neural_decisions(&mut self, environment_state: &EnvironmentState, performance_metrics: &mut PerformanceMetrics, warrior_actions: &mut ActionMap) {
+        warrior_actions.clear();
         
-        for warrior in warriors {
+        for warrior in &environment_state.warriors {
+            // Warriors locked into a multi-tick action don't decide
+            if self.environment.pending_actions.contains_key(&warrior.id) {
+                continue;
+            }
+            
             // Sense environment
-            let sensors = warrior.sense_environment(&environment_state);
+            let mut sensors = warrior.sense_environment(environment_state);
+            sensors.apply_noise(&environment_state.sensor_config, &mut self.rng);
             
             // Make decision
-            let mut warrior_copy = warrior.clone();
-            let action = warrior_copy.decide_action(&sensors);
+            let temperature = warrior.action_temperature(
+                self.simulation_config.action_temperature,
+                self.simulation_config.evolvable_temperature,
+            );
+            let action = warrior.choose_action_into(&sensors, temperature, &mut self.rng, &mut self.network_outputs);
+            if let Some(actor) = self.environment.warriors.get_mut(&warrior.id) {
+                actor.record_action(action);
+            }
             
             // Execute VM instructions for neural processing
-            if let Ok(instructions) = warrior_copy.execute_vm_instructions(&mut self.vm) {
-                for instruction in instructions {
-                    if let Err(_) = self.vm.execute_instruction(&instruction) {
-                        // VM instruction failed - continue with basic neural decision
-                        break;
-                    }
-                    performance_metrics.vm_cycles_executed += 1;
-                }
+            if let Ok(instructions) = warrior.execute_vm_instructions_with_rng(&mut self.vm, environment_state, &mut self.rng) {
+                performance_metrics.vm_cycles_executed += self.execute_warrior_program(warrior.id, &instructions);
             }
             
             // Allocate memory territory if needed
-            if warrior.territory_id.is_none() && rand::random::<f32>() < 0.1 {
-                if let Ok(_territory_id) = self.memory_allocator.allocate_territory(warrior.id) {
-                    performance_metrics.memory_allocations += 1;
+            if warrior.territory_id.is_none() && self.rng.gen::<f32>() < 0.1 {
+                match self.memory_allocator.allocate_territory(warrior.id) {
+                    Ok(_territory_id) => performance_metrics.memory_allocations += 1,
+                    Err(AllocationError::InsufficientMemory { .. }) => performance_metrics.allocation_failures += 1,
+                    Err(_) => {}
                 }
             }
             
+            self.action_histogram.record(&action);
             warrior_actions.insert(warrior.id, action);
         }
+    }
+    
+    /// Instruction cost each warrior has spent so far this generation (or
+    /// in the last one, between generations), by warrior id.
+    pub fn compute_usage(&self) -> &HashMap<u32, u32> {
+        &self.compute_usage
+    }
+    
+    /// Average fitness per unit of compute the average warrior spent this
+    /// generation; 0 when nothing was spent.
+    fn computational_efficiency(&self, average_fitness: f32) -> f32 {
+        if self.compute_usage.is_empty() {
+            return 0.0;
+        }
+        let total: u64 = self.compute_usage.values().map(|&spent| spent as u64).sum();
+        let mean_spent = total as f32 / self.compute_usage.len() as f32;
+        if mean_spent > 0.0 { average_fitness / mean_spent } else { 0.0 }
+    }
+    
+    /// Runs a warrior's VM instructions and attributes the compute spent to
+    /// it. With a per-tick budget configured, overdraft costs the warrior
+    /// energy and unspent budget accrues toward a small fitness bonus.
+    /// Returns the number of instructions executed.
+    pub fn execute_warrior_program(&mut self, warrior_id: u32, instructions: &[Instruction]) -> u64 {
+        let budget = self.simulation_config.vm_budget_per_tick;
+        let program_id = warrior_id as usize;
+        let mut cycles = 0;
+        let mut spent = 0;
+        
+        if budget > 0 {
+            self.vm.set_program_budget(program_id, budget);
+        }
+        
+        for instruction in instructions {
+            let result = if budget > 0 {
+                self.vm.execute_instruction_as(program_id, instruction)
+            } else {
+                self.vm.execute_instruction(instruction)
+            };
+            
+            if result.is_err() {
+                // VM instruction failed - continue with basic neural decision
+                break;
+            }
+            cycles += 1;
+            spent += instruction.cost();
+        }
         
-        warrior_actions
+        let usage = if budget > 0 { self.vm.take_program_budget(program_id) } else { None };
+        let penalty = self.simulation_config.vm_overdraft_energy_penalty;
+        
+        let usage_this_generation = self.compute_usage.entry(warrior_id).or_insert(0);
+        *usage_this_generation = usage_this_generation.saturating_add(spent);
+        
+        let record_behavior = !self.simulation_config.fast_mode;
+        if let Some(warrior) = self.environment.warriors.get_mut(&warrior_id) {
+            if record_behavior {
+                warrior.behavior.compute_spent += spent as u64;
+            }
+            if let Some(usage) = usage {
+                // Savings feed fitness, so they are kept even in fast mode
+                warrior.behavior.compute_saved += usage.remaining() as u64;
+                if record_behavior {
+                    warrior.behavior.compute_overdraft += usage.overdraft as u64;
+                }
+                warrior.consume_energy(usage.overdraft as f32 * penalty);
+            }
+        }
+        
+        cycles
     }
     
     fn update_fitness_scores(&mut self, _action_results: &ActionResults) {
+        let savings_bonus = self.simulation_config.vm_savings_fitness_bonus;
+        let territory_bonus = self.simulation_config.territory_fitness_bonus;
+        let efficiency_bonus = self.simulation_config.compute_efficiency_fitness_bonus;
+        let track_lineage = !self.simulation_config.fast_mode;
         for warrior in self.environment.warriors.values_mut() {
             // Calculate fitness based on survival, energy, age, and lineage
             let survival_time = warrior.age;
@@ -334,37 +1295,69 @@ impl NeuralArenaSimulation {
             let combat_success = 0.0; // TODO: Track combat success
             
             warrior.update_fitness(survival_time, resources_acquired, combat_success);
+            warrior.fitness_score += warrior.behavior.compute_saved as f32 * savings_bonus;
+            warrior.fitness_score += warrior.behavior.territory_ticks as f32 * territory_bonus;
+            if efficiency_bonus != 0.0 {
+                let spent = self.compute_usage.get(&warrior.id).copied().unwrap_or(0);
+                warrior.fitness_score += efficiency_bonus * warrior.energy / (1.0 + spent as f32);
+            }
+            if !warrior.fitness_score.is_finite() {
+                warrior.fitness_score = 0.0;
+            }
+            if track_lineage {
+                self.lineage.record(warrior);
+            }
+            self.population_stats.update(warrior);
+        }
+        
+        // Every living warrior was just counted, so any surplus belongs to
+        // warriors removed outside a tick
+        if self.population_stats.count() != self.environment.warriors.len() {
+            self.population_stats = PopulationAccumulator::from_warriors(self.environment.warriors.values());
+        }
+    }
+    
+    fn forget_dead_warriors(&mut self, update: &EnvironmentUpdate) {
+        for &warrior_id in &update.dead_warriors {
+            self.population_stats.remove(warrior_id);
         }
     }
     
-    fn create_emergency_population(&self, survivors: &[NeuralWarrior]) -> Vec<NeuralWarrior> {
+    fn create_emergency_population(&mut self, survivors: &[NeuralWarrior]) -> Vec<NeuralWarrior> {
         let mut emergency_population = Vec::new();
-        let target_size = self.simulation_config.max_population / 4; // Quarter population for recovery
+        let recovery = self.simulation_config.recovery_config.clone();
+        let target_size = (self.simulation_config.max_population as f32 * recovery.fraction) as usize;
         
         if survivors.is_empty() {
             // Complete extinction - create new random population
             for i in 0..target_size {
-                let genome = Genome::new_random();
-                let warrior = NeuralWarrior::new(genome, i as u32);
+                // Placed when the generation is installed
+                let genome = self.random_genome();
+                let mut warrior = NeuralWarrior::with_position(genome, i as u32, (0.0, 0.0));
+                warrior.energy = recovery.spawn_energy;
                 emergency_population.push(warrior);
             }
         } else {
             // Clone and mutate best survivors
             let best_survivors: Vec<&NeuralWarrior> = {
                 let mut sorted = survivors.iter().collect::<Vec<_>>();
-                sorted.sort_by(|a, b| b.fitness_score.partial_cmp(&a.fitness_score).unwrap());
+                sorted.sort_by(|a, b| b.fitness_score.total_cmp(&a.fitness_score));
                 sorted.into_iter().take(5).collect()
             };
             
             for i in 0..target_size {
                 let parent = best_survivors[i % best_survivors.len()];
-                let mut child = parent.clone();
-                child.id = rand::random();
-                child.age = 0;
-                child.fitness_score = 0.0;
-                child.genome.mutate(0.2); // Higher mutation rate for recovery
-                child.network = child.genome.to_network();
-                child.lineage_depth += 1;
+                let child_id = unused_id(&mut self.rng, |id| {
+                    self.environment.warriors.contains_key(&id) || emergency_population.iter().any(|warrior| warrior.id == id)
+                });
+                let mut child = parent.spawn_child_with_operators(
+                    child_id,
+                    recovery.mutation_rate,
+                    0.0,
+                    &self.simulation_config.mutation_weights,
+                    &mut self.rng,
+                );
+                child.energy = recovery.spawn_energy;
                 emergency_population.push(child);
             }
         }
@@ -372,70 +1365,99 @@ impl NeuralArenaSimulation {
         emergency_population
     }
     
-    fn update_statistics(&mut self, survivors: &[NeuralWarrior]) {
-        let population_size = survivors.len();
-        
-        if population_size == 0 {
-            self.statistics = SimulationStatistics {
-                generation: self.generation,
-                tick: self.tick,
-                population_size: 0,
-                species_count: 0,
-                average_fitness: 0.0,
-                max_fitness: 0.0,
-                diversity_score: 0.0,
-                survival_rate: 0.0,
-                average_age: 0.0,
-                max_lineage_depth: 0,
-                computational_efficiency: 0.0,
-                rounds_per_second: 0.0,
-                resource_utilization: 0.0,
-                environmental_pressure: self.environment.environmental_pressure,
-            };
-            return;
-        }
-        
-        let total_fitness: f32 = survivors.iter().map(|w| w.fitness_score).sum();
-        let average_fitness = total_fitness / population_size as f32;
-        let max_fitness = survivors.iter().map(|w| w.fitness_score).fold(0.0, f32::max);
+    /// Copies the environment-level fields (tick, pressure, resource
+    /// utilization) from `Environment::get_statistics`, so the two reports
+    /// never disagree.
+    fn sync_environment_statistics(&mut self) {
+        let environment_stats = self.environment.get_statistics();
+        let max_resources = self.environment.resource_config.max_resources;
         
-        let total_age: u32 = survivors.iter().map(|w| w.age).sum();
-        let average_age = total_age as f32 / population_size as f32;
+        self.statistics.tick = environment_stats.tick;
+        self.statistics.environmental_pressure = environment_stats.environmental_pressure;
+        self.statistics.resource_utilization = if max_resources == 0 {
+            0.0
+        } else {
+            environment_stats.total_resources as f32 / max_resources as f32
+        };
         
-        let max_lineage_depth = survivors.iter().map(|w| w.lineage_depth).max().unwrap_or(0);
+        let flows = self.environment.energy_flows.since(self.generation_energy_flows);
+        let warrior_energy = self.environment.warrior_energy();
+        let resource_energy = self.environment.resource_energy();
+        let standing = warrior_energy + resource_energy;
+        self.statistics.warrior_energy = warrior_energy;
+        self.statistics.resource_energy = resource_energy;
+        self.statistics.energy_injected = flows.injected;
+        self.statistics.energy_destroyed = flows.destroyed;
+        self.statistics.biomass_turnover = if standing > 0.0 { flows.injected / standing } else { 0.0 };
+    }
+    
+    /// Builds generation statistics from `population_stats`, which by the
+    /// end of a generation's tick loop covers exactly the survivors.
+    fn update_statistics(&mut self, survivors: &[NeuralWarrior]) {
+        self.update_population_statistics(survivors);
+        self.update_lineage_statistics();
+        self.sync_environment_statistics();
+    }
+    
+    /// Dates lineages founded this generation, forgets extinct ones and
+    /// copies the lineage summary into the statistics.
+    fn update_lineage_statistics(&mut self) {
+        let living: BTreeSet<u32> = self.environment.warriors.values().map(|warrior| warrior.founder_id).collect();
+        self.lineage_origins.retain(|founder, _| living.contains(founder));
+        for founder in living {
+            self.lineage_origins.entry(founder).or_insert(self.generation);
+        }
         
-        // Calculate diversity as variance in fitness scores
-        let fitness_variance = if population_size > 1 {
-            let variance_sum: f32 = survivors.iter()
-                .map(|w| (w.fitness_score - average_fitness).powi(2))
-                .sum();
-            variance_sum / (population_size - 1) as f32
-        } else {
+        let summary = self.lineage_summary();
+        self.statistics.largest_lineage_share = summary.largest_lineage_share;
+        self.statistics.surviving_lineages = summary.surviving_lineages;
+        self.statistics.oldest_lineage_age = summary.oldest_lineage_age;
+    }
+    
+    /// Writes the population-level fields only. Lineage fields are owned by
+    /// `update_lineage_statistics` and environment-level ones (tick,
+    /// pressure, resources, energy) by `sync_environment_statistics`.
+    fn update_population_statistics(&mut self, survivors: &[NeuralWarrior]) {
+        let stats = &self.population_stats;
+        let population_size = stats.count();
+        let average_fitness = stats.average_fitness();
+        let computational_efficiency = if population_size == 0 {
             0.0
+        } else {
+            self.computational_efficiency(average_fitness)
         };
         
-        let diversity_score = fitness_variance.sqrt();
+        let statistics = &mut self.statistics;
+        statistics.generation = self.generation;
+        statistics.population_size = population_size;
+        statistics.dominant_action_share = self.action_histogram.dominant().map_or(0.0, |(_, share)| share);
+        statistics.behavioral_entropy = self.action_histogram.entropy();
+        statistics.computational_efficiency = computational_efficiency;
         
-        let species_count = self.speciation_manager.species.len();
-        let survival_rate = population_size as f32 / self.simulation_config.max_population as f32;
-        let resource_utilization = self.environment.resources.len() as f32 / 200.0; // Assuming max 200 resources
+        if population_size == 0 {
+            statistics.species_count = 0;
+            statistics.average_fitness = 0.0;
+            statistics.max_fitness = 0.0;
+            statistics.diversity_score = 0.0;
+            statistics.survival_rate = 0.0;
+            statistics.average_age = 0.0;
+            statistics.max_lineage_depth = 0;
+            statistics.mean_genome_size = 0.0;
+            statistics.dominant_action_share = 0.0;
+            statistics.behavior_variance = 0.0;
+            return;
+        }
         
-        self.statistics = SimulationStatistics {
-            generation: self.generation,
-            tick: self.tick,
-            population_size,
-            species_count,
-            average_fitness,
-            max_fitness,
-            diversity_score,
-            survival_rate,
-            average_age,
-            max_lineage_depth,
-            computational_efficiency: self.vm.available_resources() as f32 / 10000.0,
-            rounds_per_second: 0.0, // Updated in performance metrics
-            resource_utilization,
-            environmental_pressure: self.environment.environmental_pressure,
-        };
+        statistics.species_count = self.speciation_manager.species.len();
+        statistics.average_fitness = average_fitness;
+        statistics.max_fitness = stats.max_fitness();
+        // Diversity is the spread of fitness scores
+        statistics.diversity_score = stats.fitness_std_dev();
+        statistics.survival_rate = population_size as f32 / self.simulation_config.max_population as f32;
+        statistics.average_age = stats.average_age();
+        statistics.max_lineage_depth = stats.max_lineage_depth();
+        statistics.mean_genome_size = stats.mean_genome_size();
+        statistics.behavior_variance = behavior_variance(survivors);
     }
 }
 
@@ -456,6 +1478,18 @@ impl Default for SimulationStatistics {
             rounds_per_second: 0.0,
             resource_utilization: 0.0,
             environmental_pressure: 0.0,
+            mean_genome_size: 0.0,
+            dominant_action_share: 0.0,
+            behavioral_entropy: 0.0,
+            behavior_variance: 0.0,
+            largest_lineage_share: 0.0,
+            surviving_lineages: 0,
+            oldest_lineage_age: 0,
+            warrior_energy: 0.0,
+            resource_energy: 0.0,
+            energy_injected: 0.0,
+            energy_destroyed: 0.0,
+            biomass_turnover: 0.0,
         }
     }
-}
\ No newline at end of file
+}
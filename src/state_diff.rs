@@ -0,0 +1,87 @@
+//! Structured comparison of two `SimulationState` snapshots, for finding
+//! where two runs that should match start to diverge.
+
+use crate::wasm_api::SimulationState;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A warrior present in both states whose position or energy differs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WarriorDiff {
+    pub id: u32,
+    /// `b`'s position minus `a`'s.
+    pub position_delta: (f32, f32),
+    /// `b`'s energy minus `a`'s.
+    pub energy_delta: f32,
+}
+
+/// A top-level statistic that differs between the two states.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatDiff {
+    pub stat: String,
+    pub a: f64,
+    pub b: f64,
+}
+
+/// Everything that differs going from state `a` to state `b`. Warrior ids
+/// are in ascending order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StateDiff {
+    /// In `b` but not `a`.
+    pub added_warriors: Vec<u32>,
+    /// In `a` but not `b`.
+    pub removed_warriors: Vec<u32>,
+    pub changed_warriors: Vec<WarriorDiff>,
+    pub stats: Vec<StatDiff>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_warriors.is_empty()
+            && self.removed_warriors.is_empty()
+            && self.changed_warriors.is_empty()
+            && self.stats.is_empty()
+    }
+}
+
+pub fn diff_states(a: &SimulationState, b: &SimulationState) -> StateDiff {
+    let by_id = |state: &SimulationState| -> BTreeMap<u32, (f32, f32, f32)> {
+        state.warriors.iter().map(|warrior| (warrior.id, (warrior.x, warrior.y, warrior.energy))).collect()
+    };
+    let (warriors_a, warriors_b) = (by_id(a), by_id(b));
+
+    let mut diff = StateDiff {
+        added_warriors: warriors_b.keys().filter(|id| !warriors_a.contains_key(id)).copied().collect(),
+        removed_warriors: warriors_a.keys().filter(|id| !warriors_b.contains_key(id)).copied().collect(),
+        ..StateDiff::default()
+    };
+
+    for (&id, &(x_a, y_a, energy_a)) in &warriors_a {
+        if let Some(&(x_b, y_b, energy_b)) = warriors_b.get(&id) {
+            if (x_a, y_a, energy_a) != (x_b, y_b, energy_b) {
+                diff.changed_warriors.push(WarriorDiff {
+                    id,
+                    position_delta: (x_b - x_a, y_b - y_a),
+                    energy_delta: energy_b - energy_a,
+                });
+            }
+        }
+    }
+
+    let stats = [
+        ("generation", a.generation as f64, b.generation as f64),
+        ("tick", a.tick as f64, b.tick as f64),
+        ("population_size", a.population_size as f64, b.population_size as f64),
+        ("species_count", a.species_count as f64, b.species_count as f64),
+        ("average_fitness", a.average_fitness as f64, b.average_fitness as f64),
+        ("max_fitness", a.max_fitness as f64, b.max_fitness as f64),
+        ("diversity_score", a.diversity_score as f64, b.diversity_score as f64),
+        ("environmental_pressure", a.environmental_pressure as f64, b.environmental_pressure as f64),
+    ];
+    diff.stats = stats.into_iter()
+        .filter(|&(_, value_a, value_b)| value_a != value_b)
+        .map(|(stat, a, b)| StatDiff { stat: stat.to_string(), a, b })
+        .collect();
+
+    diff
+}
@@ -0,0 +1,205 @@
+use crate::neural::{ActionKind, NeuralWarrior};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Running sums over a population, kept up to date from per-warrior
+/// deltas so generation statistics don't need another pass over the
+/// survivors. Each warrior's last counted values are remembered by id;
+/// `update` swaps them for the current ones and `remove` takes them out.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PopulationAccumulator {
+    members: HashMap<u32, Contribution>,
+    fitness_sum: f64,
+    fitness_square_sum: f64,
+    energy_sum: f64,
+    age_sum: u64,
+    genome_size_sum: usize,
+    /// Multisets of the counted values whose maximum is reported, so
+    /// removing the current maximum falls back to the next largest.
+    fitness_counts: BTreeMap<i32, usize>,
+    lineage_depth_counts: BTreeMap<u32, usize>,
+}
+
+/// What one warrior currently adds to the accumulator's sums.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Contribution {
+    fitness: f32,
+    energy: f32,
+    age: u32,
+    lineage_depth: u32,
+    genome_size: usize,
+}
+
+impl Contribution {
+    fn of(warrior: &NeuralWarrior) -> Self {
+        Self {
+            fitness: warrior.fitness_score,
+            energy: warrior.energy,
+            age: warrior.age,
+            lineage_depth: warrior.lineage_depth,
+            genome_size: warrior.genome.size(),
+        }
+    }
+}
+
+impl PopulationAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Full recompute over `warriors`, equivalent to adding each in turn.
+    pub fn from_warriors<'a>(warriors: impl IntoIterator<Item = &'a NeuralWarrior>) -> Self {
+        let mut accumulator = Self::new();
+        for warrior in warriors {
+            accumulator.add(warrior);
+        }
+        accumulator
+    }
+
+    /// Counts a warrior, replacing whatever was counted for its id before.
+    pub fn add(&mut self, warrior: &NeuralWarrior) {
+        self.update(warrior);
+    }
+
+    /// Brings the warrior's counted values up to date: a no-op when nothing
+    /// changed, an insert for an id seen for the first time.
+    pub fn update(&mut self, warrior: &NeuralWarrior) {
+        let contribution = Contribution::of(warrior);
+        match self.members.insert(warrior.id, contribution) {
+            Some(previous) if previous == contribution => {},
+            Some(previous) => {
+                self.subtract(&previous);
+                self.include(&contribution);
+            },
+            None => self.include(&contribution),
+        }
+    }
+
+    /// Stops counting the warrior with `warrior_id`, e.g. once it died.
+    pub fn remove(&mut self, warrior_id: u32) {
+        if let Some(previous) = self.members.remove(&warrior_id) {
+            self.subtract(&previous);
+        }
+    }
+
+    fn include(&mut self, contribution: &Contribution) {
+        self.fitness_sum += contribution.fitness as f64;
+        self.fitness_square_sum += (contribution.fitness as f64).powi(2);
+        self.energy_sum += contribution.energy as f64;
+        self.age_sum += contribution.age as u64;
+        self.genome_size_sum += contribution.genome_size;
+        *self.fitness_counts.entry(fitness_key(contribution.fitness)).or_default() += 1;
+        *self.lineage_depth_counts.entry(contribution.lineage_depth).or_default() += 1;
+    }
+
+    fn subtract(&mut self, contribution: &Contribution) {
+        self.fitness_sum -= contribution.fitness as f64;
+        self.fitness_square_sum -= (contribution.fitness as f64).powi(2);
+        self.energy_sum -= contribution.energy as f64;
+        self.age_sum -= contribution.age as u64;
+        self.genome_size_sum -= contribution.genome_size;
+        decrement(&mut self.fitness_counts, fitness_key(contribution.fitness));
+        decrement(&mut self.lineage_depth_counts, contribution.lineage_depth);
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn count(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn average_fitness(&self) -> f32 {
+        self.mean(self.fitness_sum)
+    }
+
+    /// Largest counted fitness, never below 0.
+    pub fn max_fitness(&self) -> f32 {
+        self.fitness_counts.keys().next_back()
+            .map_or(0.0, |&key| fitness_from_key(key).max(0.0))
+    }
+
+    /// Sample standard deviation of fitness.
+    pub fn fitness_std_dev(&self) -> f32 {
+        if self.count() < 2 {
+            return 0.0;
+        }
+
+        let n = self.count() as f64;
+        let sum = self.fitness_sum;
+        let variance = (self.fitness_square_sum - sum * sum / n) / (n - 1.0);
+        variance.max(0.0).sqrt() as f32
+    }
+
+    pub fn average_energy(&self) -> f32 {
+        self.mean(self.energy_sum)
+    }
+
+    pub fn average_age(&self) -> f32 {
+        self.mean(self.age_sum as f64)
+    }
+
+    pub fn max_lineage_depth(&self) -> u32 {
+        self.lineage_depth_counts.keys().next_back().copied().unwrap_or(0)
+    }
+
+    pub fn mean_genome_size(&self) -> f32 {
+        self.mean(self.genome_size_sum as f64)
+    }
+
+    fn mean(&self, sum: f64) -> f32 {
+        if self.members.is_empty() {
+            0.0
+        } else {
+            (sum / self.members.len() as f64) as f32
+        }
+    }
+}
+
+/// Variance of the action-frequency descriptors across `warriors`,
+/// averaged over actions. 0 means every warrior behaves identically.
+/// Walks every warrior's action history, so it runs once per generation
+/// rather than per tick.
+pub fn behavior_variance<'a>(warriors: impl IntoIterator<Item = &'a NeuralWarrior>) -> f32 {
+    let mut count = 0;
+    let mut sum = [0.0f32; ActionKind::ALL.len()];
+    let mut square_sum = [0.0f32; ActionKind::ALL.len()];
+    for warrior in warriors {
+        count += 1;
+        for (kind, frequency) in warrior.action_frequencies().into_iter().enumerate() {
+            sum[kind] += frequency;
+            square_sum[kind] += frequency * frequency;
+        }
+    }
+    if count == 0 {
+        return 0.0;
+    }
+
+    let total: f32 = sum.iter().zip(&square_sum)
+        .map(|(&sum, &square_sum)| {
+            let mean = sum / count as f32;
+            (square_sum / count as f32 - mean * mean).max(0.0)
+        })
+        .sum();
+    total / ActionKind::ALL.len() as f32
+}
+
+/// Order-preserving integer key for a fitness value, as in `f32::total_cmp`.
+fn fitness_key(fitness: f32) -> i32 {
+    let bits = fitness.to_bits() as i32;
+    bits ^ (((bits >> 31) as u32) >> 1) as i32
+}
+
+fn fitness_from_key(key: i32) -> f32 {
+    f32::from_bits((key ^ (((key >> 31) as u32) >> 1) as i32) as u32)
+}
+
+fn decrement<K: Ord>(counts: &mut BTreeMap<K, usize>, key: K) {
+    if let Some(count) = counts.get_mut(&key) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(&key);
+        }
+    }
+}
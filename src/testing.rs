@@ -0,0 +1,25 @@
+//! Deterministic fixtures for tests. Everything random is drawn from a
+//! caller-supplied seed, so a test can assert exact outcomes instead of
+//! branching on whether a random event happened to occur. Pair these with
+//! `Environment::with_seed` and a `SimulationConfig` with a `seed`.
+//!
+//! Only built with the `testing` feature, which the crate's own tests
+//! enable through a dev-dependency on itself.
+
+use crate::neural::{Genome, NeuralWarrior};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+pub fn fixed_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// A warrior at `position` whose genome is drawn from `rng`.
+pub fn fixed_warrior(id: u32, position: (f32, f32), rng: &mut StdRng) -> NeuralWarrior {
+    NeuralWarrior::with_position(Genome::random_with_rng(rng), id, position)
+}
+
+/// A uniform position in an `arena_size` arena, drawn from `rng`.
+pub fn random_position(arena_size: (f32, f32), rng: &mut StdRng) -> (f32, f32) {
+    (rng.gen::<f32>() * arena_size.0, rng.gen::<f32>() * arena_size.1)
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OpCode {
     Activate,
     Mutate,
@@ -10,6 +10,17 @@ pub enum OpCode {
     Noop,
 }
 
+impl OpCode {
+    pub const ALL: [OpCode; 6] = [
+        OpCode::Activate,
+        OpCode::Mutate,
+        OpCode::Replicate,
+        OpCode::Move,
+        OpCode::Sense,
+        OpCode::Noop,
+    ];
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Instruction {
     pub opcode: OpCode,
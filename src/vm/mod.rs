@@ -2,4 +2,4 @@ pub mod instruction;
 pub mod virtual_machine;
 
 pub use instruction::{Instruction, OpCode};
-pub use virtual_machine::VirtualMachine;
+pub use virtual_machine::{ProgramBudget, VirtualMachine, VmError};
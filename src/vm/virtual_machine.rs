@@ -1,26 +1,58 @@
 use super::instruction::{Instruction, OpCode};
-use std::collections::HashMap;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Clone)]
+/// Zero runs shorter than this stay inline in a compact snapshot; splitting
+/// on them would cost more than it saves.
+const MIN_OMITTED_ZERO_RUN: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryTerritory {
+    #[allow(dead_code)]
     id: usize,
     owner_program: usize,
     start_address: usize,
     size: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VirtualMachine {
+    #[serde(deserialize_with = "deserialize_memory")]
     memory: Vec<f32>,
     memory_size: usize,
     cycle_count: u64,
     available_resources: u32,
-    programs: HashMap<usize, Vec<Instruction>>,
-    program_counters: HashMap<usize, usize>,
+    /// Ordered by id, which is the order round-robin cycles run them in.
+    programs: BTreeMap<usize, Vec<Instruction>>,
+    program_counters: BTreeMap<usize, usize>,
     current_program: usize,
     territories: HashMap<usize, MemoryTerritory>,
     next_territory_id: usize,
     allocated_memory: usize,
+    program_budgets: HashMap<usize, ProgramBudget>,
+    enabled_opcodes: Vec<OpCode>,
+    /// Drives Mutate and random sensors. Snapshots carry its state, so a
+    /// restored VM draws what the original would have; ones taken before
+    /// it was recorded restore with a fresh one.
+    #[serde(default = "ChaCha12Rng::from_entropy")]
+    rng: ChaCha12Rng,
+}
+
+/// Per-program compute pool. Programs map one-to-one onto warriors, so this
+/// is how the VM attributes compute to the warrior that spent it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProgramBudget {
+    pub budget: u32,
+    pub spent: u32,
+    pub overdraft: u32,
+}
+
+impl ProgramBudget {
+    pub fn remaining(&self) -> u32 {
+        self.budget.saturating_sub(self.spent)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -37,26 +69,112 @@ pub enum VmError {
     InsufficientMemory { requested: usize, available: usize },
     #[error("Territory access out of bounds: offset {offset}, territory size {size}")]
     TerritoryBoundsViolation { offset: usize, size: usize },
+    #[error("Opcode {opcode:?} is disabled")]
+    OpcodeDisabled { opcode: OpCode },
+    #[error("Snapshot memory holds {actual} cells, expected {expected}")]
+    MemorySizeMismatch { expected: usize, actual: usize },
+    #[error("Program {id} counter {counter} is past its {len} instructions")]
+    ProgramCounterOutOfRange { id: usize, counter: usize, len: usize },
+    #[error("Territory {id} spans {start}..{end}, beyond memory size {size}")]
+    TerritoryOutOfRange { id: usize, start: usize, end: usize, size: usize },
+    #[error("Invalid VM snapshot: {0}")]
+    Snapshot(#[from] serde_json::Error),
+}
+
+/// Memory with long zero runs dropped: only the `(start, values)` segments
+/// that hold data are kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CompactMemory {
+    len: usize,
+    segments: Vec<(usize, Vec<f32>)>,
+}
+
+impl CompactMemory {
+    fn encode(memory: &[f32]) -> Self {
+        let mut segments: Vec<(usize, Vec<f32>)> = Vec::new();
+        let mut zeros = 0;
+        for (address, &value) in memory.iter().enumerate() {
+            if value.to_bits() == 0 {
+                zeros += 1;
+                continue;
+            }
+            match segments.last_mut() {
+                Some((start, values)) if zeros < MIN_OMITTED_ZERO_RUN && *start + values.len() + zeros == address => {
+                    values.resize(values.len() + zeros, 0.0);
+                    values.push(value);
+                }
+                _ => segments.push((address, vec![value])),
+            }
+            zeros = 0;
+        }
+        Self { len: memory.len(), segments }
+    }
+
+    fn decode(self) -> Result<Vec<f32>, String> {
+        let mut memory = vec![0.0; self.len];
+        for (start, values) in self.segments {
+            let target = memory.get_mut(start..start + values.len())
+                .ok_or_else(|| format!("segment at {} overruns {} cells", start, self.len))?;
+            target.copy_from_slice(&values);
+        }
+        Ok(memory)
+    }
+}
+
+/// Accepts both the dense memory array and the compact form.
+fn deserialize_memory<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f32>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MemoryImage {
+        Dense(Vec<f32>),
+        Compact(CompactMemory),
+    }
+
+    match MemoryImage::deserialize(deserializer)? {
+        MemoryImage::Dense(memory) => Ok(memory),
+        MemoryImage::Compact(compact) => compact.decode().map_err(serde::de::Error::custom),
+    }
 }
 
 type VmResult<T> = Result<T, VmError>;
 
 impl VirtualMachine {
     pub fn new(memory_size: usize) -> Self {
+        Self::with_seed(memory_size, rand::random())
+    }
+
+    /// Like `new`, with Mutate and random sensor reads drawn from `seed`.
+    /// The simulation passes its `RngStream::Vm` seed, already derived.
+    pub fn with_seed(memory_size: usize, seed: u64) -> Self {
         Self {
             memory: vec![0.0; memory_size],
             memory_size,
             cycle_count: 0,
             available_resources: 10000,
-            programs: HashMap::new(),
-            program_counters: HashMap::new(),
+            programs: BTreeMap::new(),
+            program_counters: BTreeMap::new(),
             current_program: 0,
             territories: HashMap::new(),
             next_territory_id: 0,
             allocated_memory: 0,
+            program_budgets: HashMap::new(),
+            enabled_opcodes: OpCode::ALL.to_vec(),
+            // The same generator `RngStream::rng` gives, spelled out
+            // because `StdRng` can't be serialized
+            rng: ChaCha12Rng::seed_from_u64(seed),
         }
     }
 
+    /// Restricts the instruction set. Loading or executing any other
+    /// opcode fails with `VmError::OpcodeDisabled`.
+    pub fn set_enabled_opcodes(&mut self, opcodes: Vec<OpCode>) {
+        self.enabled_opcodes = opcodes;
+    }
+
+    pub fn is_opcode_enabled(&self, opcode: OpCode) -> bool {
+        self.enabled_opcodes.contains(&opcode)
+    }
+
     pub fn memory_size(&self) -> usize {
         self.memory_size
     }
@@ -69,7 +187,53 @@ impl VirtualMachine {
         self.available_resources
     }
 
+    pub fn memory(&self) -> &[f32] {
+        &self.memory
+    }
+
+    pub fn program_counter(&self, program_id: usize) -> Option<usize> {
+        self.program_counters.get(&program_id).copied()
+    }
+
+    /// Serializes the full VM state. In `compact` mode long zeroed memory
+    /// runs are omitted, which keeps checkpoints of large, sparse memories
+    /// small.
+    pub fn snapshot(&self, compact: bool) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        if compact {
+            value["memory"] = serde_json::to_value(CompactMemory::encode(&self.memory))?;
+        }
+        serde_json::to_string(&value)
+    }
+
+    /// Restores a VM from either kind of `snapshot`, rejecting program
+    /// counters past the end of their program and territories that don't
+    /// fit in memory.
+    pub fn restore(json: &str) -> VmResult<Self> {
+        let vm: Self = serde_json::from_str(json)?;
+        if vm.memory.len() != vm.memory_size {
+            return Err(VmError::MemorySizeMismatch { expected: vm.memory_size, actual: vm.memory.len() });
+        }
+        for (&id, &counter) in &vm.program_counters {
+            let len = vm.programs.get(&id).ok_or(VmError::ProgramNotFound { id })?.len();
+            // A counter equal to the length marks a finished program
+            if counter > len {
+                return Err(VmError::ProgramCounterOutOfRange { id, counter, len });
+            }
+        }
+        for (&id, territory) in &vm.territories {
+            let end = territory.start_address.saturating_add(territory.size);
+            if end > vm.memory_size {
+                return Err(VmError::TerritoryOutOfRange { id, start: territory.start_address, end, size: vm.memory_size });
+            }
+        }
+        Ok(vm)
+    }
+
     pub fn load_program(&mut self, id: usize, program: Vec<Instruction>) -> VmResult<()> {
+        if let Some(instruction) = program.iter().find(|instruction| !self.is_opcode_enabled(instruction.opcode)) {
+            return Err(VmError::OpcodeDisabled { opcode: instruction.opcode });
+        }
         self.programs.insert(id, program);
         self.program_counters.insert(id, 0);
         Ok(())
@@ -84,6 +248,51 @@ impl VirtualMachine {
             });
         }
 
+        self.dispatch(instruction)?;
+
+        self.available_resources -= cost;
+        self.cycle_count += 1;
+        Ok(())
+    }
+
+    /// Grants `program_id` a fresh compute budget, clearing its previous
+    /// spend and overdraft.
+    pub fn set_program_budget(&mut self, program_id: usize, budget: u32) {
+        self.program_budgets.insert(program_id, ProgramBudget {
+            budget,
+            ..ProgramBudget::default()
+        });
+    }
+
+    pub fn program_budget(&self, program_id: usize) -> Option<ProgramBudget> {
+        self.program_budgets.get(&program_id).copied()
+    }
+
+    /// Removes and returns the program's budget once its accounting is done.
+    pub fn take_program_budget(&mut self, program_id: usize) -> Option<ProgramBudget> {
+        self.program_budgets.remove(&program_id)
+    }
+
+    /// Executes an instruction on behalf of `program_id`, charging its cost
+    /// against that program's budget instead of the global pool. Spending
+    /// past the budget is allowed but recorded as overdraft.
+    pub fn execute_instruction_as(&mut self, program_id: usize, instruction: &Instruction) -> VmResult<()> {
+        self.dispatch(instruction)?;
+
+        let cost = instruction.cost();
+        let pool = self.program_budgets.entry(program_id).or_default();
+        let covered = cost.min(pool.remaining());
+        pool.spent += cost;
+        pool.overdraft += cost - covered;
+
+        self.cycle_count += 1;
+        Ok(())
+    }
+
+    fn dispatch(&mut self, instruction: &Instruction) -> VmResult<()> {
+        if !self.is_opcode_enabled(instruction.opcode) {
+            return Err(VmError::OpcodeDisabled { opcode: instruction.opcode });
+        }
         if instruction.arg1 >= self.memory_size || instruction.arg2 >= self.memory_size {
             return Err(VmError::OutOfBounds {
                 index: instruction.arg1.max(instruction.arg2),
@@ -98,11 +307,7 @@ impl VirtualMachine {
             OpCode::Move => self.execute_move(instruction),
             OpCode::Sense => self.execute_sense(instruction),
             OpCode::Noop => Ok(()),
-        }?;
-
-        self.available_resources -= cost;
-        self.cycle_count += 1;
-        Ok(())
+        }
     }
 
     pub fn execute_round_robin_cycle(&mut self) -> VmResult<()> {
@@ -141,7 +346,7 @@ impl VirtualMachine {
     fn execute_mutate(&mut self, instruction: &Instruction) -> VmResult<()> {
         let mutation_rate = instruction.arg3;
         let current_value = self.memory[instruction.arg1];
-        let mutation = (rand::random::<f32>() - 0.5) * mutation_rate;
+        let mutation = (self.rng.gen::<f32>() - 0.5) * mutation_rate;
         self.memory[instruction.arg2] = (current_value + mutation).clamp(-1.0, 1.0);
         Ok(())
     }
@@ -166,11 +371,11 @@ impl VirtualMachine {
         (2.0 / (1.0 + (-2.0 * x).exp())) - 1.0
     }
 
-    fn get_sensor_data(&self, sensor_id: usize) -> VmResult<f32> {
+    fn get_sensor_data(&mut self, sensor_id: usize) -> VmResult<f32> {
         match sensor_id {
             0 => Ok(self.available_resources as f32 / 10000.0),
             1 => Ok(self.memory_size as f32 / 1024.0),
-            _ => Ok(rand::random::<f32>()),
+            _ => Ok(self.rng.gen::<f32>()),
         }
     }
 
@@ -1,10 +1,11 @@
 use wasm_bindgen::prelude::*;
 use web_sys::console;
+use crate::environment::Environment;
+use crate::neural::{Action, NeuralWarrior, Resource};
+use crate::simulation::GenerationResult;
 use crate::{NeuralArenaSimulation, SimulationConfig};
-use crate::neural::{NeuralWarrior, Action};
-use crate::environment::EnvironmentStats;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashSet};
 
 // Use `wee_alloc` as the global allocator for smaller WASM binary size
 #[cfg(feature = "wee_alloc")]
@@ -33,13 +34,21 @@ pub fn greet() {
 pub struct WasmSimulation {
     simulation: NeuralArenaSimulation,
     is_running: bool,
+    /// Whether per-frame states carry barriers and safe zones; see
+    /// `set_include_terrain`.
+    include_terrain: bool,
+    #[allow(dead_code)]
     animation_frame_id: Option<i32>,
+    /// Summary of the most recent generation; see
+    /// `get_last_generation_result`.
+    last_generation_result: Option<GenerationResultData>,
 }
 
 // Serializable data structures for JavaScript
 #[derive(Serialize, Deserialize)]
 pub struct WarriorData {
     pub id: u32,
+    pub name: String,
     pub x: f32,
     pub y: f32,
     pub energy: f32,
@@ -48,14 +57,95 @@ pub struct WarriorData {
     pub lineage_depth: u32,
     pub species_id: Option<u32>,
     pub action: String,
+    /// `WarriorData::STATUS_FLAGS` bits set for this warrior.
+    #[serde(default)]
+    pub status_flags: u32,
+}
+
+impl WarriorData {
+    /// Energy below 20% of the maximum.
+    pub const STARVING: u32 = 1 << 0;
+    /// Defended on its most recent action (needs a non-zero action history).
+    pub const SHIELDED: u32 = 1 << 1;
+    /// Too young or too low on energy to replicate right now.
+    pub const REPLICATE_COOLDOWN: u32 = 1 << 2;
+    /// Holds a claim on a resource.
+    pub const CARRYING: u32 = 1 << 3;
+    /// Inside a safe zone.
+    pub const IN_SAFE_ZONE: u32 = 1 << 4;
+    /// Inside an arena territory it owns.
+    pub const IN_OWNED_TERRITORY: u32 = 1 << 5;
+    
+    /// Every status flag by name, as returned by `get_status_flag_legend`.
+    pub const STATUS_FLAGS: [(&'static str, u32); 6] = [
+        ("starving", Self::STARVING),
+        ("shielded", Self::SHIELDED),
+        ("replicate_cooldown", Self::REPLICATE_COOLDOWN),
+        ("carrying", Self::CARRYING),
+        ("in_safe_zone", Self::IN_SAFE_ZONE),
+        ("in_owned_territory", Self::IN_OWNED_TERRITORY),
+    ];
+    
+    const STARVING_ENERGY: f32 = 20.0;
+}
+
+/// Composes `WarriorData::status_flags`. Build one per frame: the resource
+/// claims are indexed up front so each warrior costs a few comparisons.
+pub struct StatusFlagComposer<'a> {
+    environment: &'a Environment,
+    claimants: HashSet<u32>,
+    replication_min_energy: f32,
+}
+
+impl<'a> StatusFlagComposer<'a> {
+    pub fn new(environment: &'a Environment) -> Self {
+        Self {
+            environment,
+            claimants: environment.resources.iter()
+                .filter_map(|resource| resource.claim.map(|claim| claim.warrior_id))
+                .collect(),
+            replication_min_energy: environment.replication_min_energy(),
+        }
+    }
+    
+    pub fn flags(&self, warrior: &NeuralWarrior) -> u32 {
+        let (x, y) = warrior.position;
+        let mut flags = 0;
+        if warrior.energy < WarriorData::STARVING_ENERGY {
+            flags |= WarriorData::STARVING;
+        }
+        if matches!(warrior.action_history.back(), Some(Action::Defend { .. })) {
+            flags |= WarriorData::SHIELDED;
+        }
+        if !warrior.can_replicate_with_min_energy(self.replication_min_energy) {
+            flags |= WarriorData::REPLICATE_COOLDOWN;
+        }
+        if self.claimants.contains(&warrior.id) {
+            flags |= WarriorData::CARRYING;
+        }
+        if self.environment.strongest_safe_zone_at(warrior.position).is_some() {
+            flags |= WarriorData::IN_SAFE_ZONE;
+        }
+        if self.environment.territories.iter()
+            .any(|territory| {
+                territory.owner_id == Some(warrior.id)
+                    && (x - territory.center.0).powi(2) + (y - territory.center.1).powi(2) < territory.radius.powi(2)
+            }) {
+            flags |= WarriorData::IN_OWNED_TERRITORY;
+        }
+        flags
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct ResourceData {
+    pub id: u64,
     pub x: f32,
     pub y: f32,
     pub energy_value: f32,
     pub resource_type: String,
+    /// Warrior currently holding a claim on this resource.
+    pub claimed_by: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -67,11 +157,65 @@ pub struct TerritoryData {
     pub resource_multiplier: f32,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BarrierData {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub strength: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SafeZoneData {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+    pub protection: f32,
+    pub bonus: f32,
+}
+
+/// The arena's static terrain, returned by `get_terrain`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerrainData {
+    pub barriers: Vec<BarrierData>,
+    pub safe_zones: Vec<SafeZoneData>,
+}
+
+impl TerrainData {
+    pub fn from_environment(environment: &Environment) -> Self {
+        Self {
+            barriers: environment.barriers.iter()
+                .map(|barrier| BarrierData {
+                    x: barrier.position.0,
+                    y: barrier.position.1,
+                    width: barrier.width,
+                    height: barrier.height,
+                    strength: barrier.strength,
+                })
+                .collect(),
+            safe_zones: environment.safe_zones.iter()
+                .map(|zone| SafeZoneData {
+                    x: zone.center.0,
+                    y: zone.center.1,
+                    radius: zone.radius,
+                    protection: zone.protection_level,
+                    bonus: zone.resource_bonus,
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct SimulationState {
     pub warriors: Vec<WarriorData>,
     pub resources: Vec<ResourceData>,
     pub territories: Vec<TerritoryData>,
+    /// Empty when terrain is left out of per-frame states.
+    pub barriers: Vec<BarrierData>,
+    /// Empty when terrain is left out of per-frame states.
+    pub safe_zones: Vec<SafeZoneData>,
     pub generation: u32,
     pub tick: u64,
     pub population_size: usize,
@@ -82,6 +226,72 @@ pub struct SimulationState {
     pub environmental_pressure: f32,
 }
 
+/// The headline numbers of a `GenerationResult`, for per-generation
+/// summaries in the frontend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenerationResultData {
+    pub generation: u32,
+    pub survivor_count: usize,
+    pub extinct_lineages: Vec<u32>,
+    pub new_species: usize,
+    pub species_count: usize,
+    pub extinct_species: Vec<u32>,
+    /// Highest survivor fitness (0 with none).
+    pub best_fitness: f32,
+    pub simulation_time_ms: u64,
+    pub rounds_per_second: f32,
+    pub vm_cycles_executed: u64,
+    /// Per-phase timings in microseconds, see `PerformanceMetrics`.
+    pub decide_time_us: u64,
+    pub act_time_us: u64,
+    pub evolve_time_us: u64,
+    pub warning_count: usize,
+}
+
+impl From<&GenerationResult> for GenerationResultData {
+    fn from(result: &GenerationResult) -> Self {
+        Self {
+            generation: result.generation,
+            survivor_count: result.survivors.len(),
+            extinct_lineages: result.extinct_lineages.clone(),
+            new_species: result.new_species,
+            species_count: result.species_count,
+            extinct_species: result.extinct_species.clone(),
+            best_fitness: result.survivors.iter().map(|warrior| warrior.fitness_score).fold(0.0, f32::max),
+            simulation_time_ms: result.performance_metrics.simulation_time_ms as u64,
+            rounds_per_second: result.performance_metrics.rounds_per_second,
+            vm_cycles_executed: result.performance_metrics.vm_cycles_executed,
+            decide_time_us: result.performance_metrics.decide_time_us as u64,
+            act_time_us: result.performance_metrics.act_time_us as u64,
+            evolve_time_us: result.performance_metrics.evolve_time_us as u64,
+            warning_count: result.warnings.len(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StateDeltaData {
+    pub since_tick: u64,
+    pub tick: u64,
+    pub full: bool,
+    pub warriors: Vec<WarriorData>,
+    pub removed_warriors: Vec<u32>,
+    pub added_resources: Vec<ResourceData>,
+    pub removed_resources: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EnvironmentHistoryData {
+    pub ticks: Vec<u64>,
+    pub warriors: Vec<usize>,
+    pub resources: Vec<usize>,
+    pub total_energy: Vec<f32>,
+    pub pressure: Vec<f32>,
+    pub resource_energy: Vec<f32>,
+    pub energy_injected: Vec<f32>,
+    pub energy_destroyed: Vec<f32>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct MemoryHeatmapData {
     pub width: usize,
@@ -89,6 +299,15 @@ pub struct MemoryHeatmapData {
     pub data: Vec<f32>, // Flattened 2D array of memory usage intensities (0.0 to 1.0)
 }
 
+/// One entry of `MemoryAllocator::territory_map`.
+#[derive(Serialize, Deserialize)]
+pub struct TerritoryMapEntry {
+    pub id: usize,
+    pub owner_id: Option<u32>,
+    pub resource_density: f32,
+    pub size: usize,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct NetworkTopologyData {
     pub nodes: Vec<NetworkNode>,
@@ -118,8 +337,9 @@ impl WasmSimulation {
         // Set panic hook for better error messages
         std::panic::set_hook(Box::new(console_error_panic_hook::hook));
         
-        let config: SimulationConfig = serde_json::from_str(config_json)
-            .map_err(|e| JsValue::from_str(&format!("Config parse error: {}", e)))?;
+        // Accepts a plain config or {"preset": "quick_demo", "overrides": {...}}
+        let config = SimulationConfig::from_json(config_json)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
         
         let simulation = NeuralArenaSimulation::new(config);
         
@@ -128,7 +348,9 @@ impl WasmSimulation {
         Ok(WasmSimulation {
             simulation,
             is_running: false,
+            include_terrain: true,
             animation_frame_id: None,
+            last_generation_result: None,
         })
     }
     
@@ -154,6 +376,7 @@ impl WasmSimulation {
     pub fn reset(&mut self) {
         self.simulation.reset();
         self.is_running = false;
+        self.last_generation_result = None;
         log!("Simulation reset");
     }
     
@@ -167,6 +390,7 @@ impl WasmSimulation {
     #[wasm_bindgen]
     pub fn run_generation(&mut self) -> JsValue {
         let result = self.simulation.run_generation();
+        self.last_generation_result = Some(GenerationResultData::from(&result));
         let state = self.get_simulation_state();
         
         log!("Generation {} completed with {} survivors", 
@@ -175,12 +399,83 @@ impl WasmSimulation {
         serde_wasm_bindgen::to_value(&state).unwrap()
     }
     
+    /// `GenerationResultData` for the most recent `run_generation`, or
+    /// null before the first one.
+    #[wasm_bindgen]
+    pub fn get_last_generation_result(&self) -> JsValue {
+        self.last_generation_result.as_ref()
+            .map_or(JsValue::NULL, |result| serde_wasm_bindgen::to_value(result).unwrap())
+    }
+    
     #[wasm_bindgen]
     pub fn get_simulation_state_json(&self) -> String {
         let state = self.get_simulation_state();
         serde_json::to_string(&state).unwrap_or_else(|_| "{}".to_string())
     }
     
+    /// Barriers and safe zones. Terrain rarely changes, so frontends can
+    /// fetch it once and call `set_include_terrain(false)` to slim down
+    /// every per-frame state.
+    #[wasm_bindgen]
+    pub fn get_terrain(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&TerrainData::from_environment(&self.simulation.environment)).unwrap()
+    }
+    
+    #[wasm_bindgen]
+    pub fn set_include_terrain(&mut self, include: bool) {
+        self.include_terrain = include;
+    }
+    
+    #[wasm_bindgen]
+    pub fn get_environment_history(&self, since_tick: u64) -> JsValue {
+        let samples = self.simulation.environment.history_since(since_tick);
+        let history = EnvironmentHistoryData {
+            ticks: samples.iter().map(|s| s.tick).collect(),
+            warriors: samples.iter().map(|s| s.warriors).collect(),
+            resources: samples.iter().map(|s| s.resources).collect(),
+            total_energy: samples.iter().map(|s| s.total_energy).collect(),
+            pressure: samples.iter().map(|s| s.pressure).collect(),
+            resource_energy: samples.iter().map(|s| s.resource_energy).collect(),
+            energy_injected: samples.iter().map(|s| s.energy_injected).collect(),
+            energy_destroyed: samples.iter().map(|s| s.energy_destroyed).collect(),
+        };
+        serde_wasm_bindgen::to_value(&history).unwrap()
+    }
+    
+    /// Warriors and resources that changed since `since_tick`. Apply the
+    /// returned delta to the local copy, replacing it when `full` is set.
+    #[wasm_bindgen]
+    pub fn get_state_delta(&self, since_tick: u64) -> JsValue {
+        let delta = self.simulation.environment.state_delta(since_tick);
+        let status = StatusFlagComposer::new(&self.simulation.environment);
+        let data = StateDeltaData {
+            since_tick: delta.since_tick,
+            tick: delta.tick,
+            full: delta.full,
+            warriors: delta.warriors.iter().map(|warrior| self.warrior_data(warrior, &status)).collect(),
+            removed_warriors: delta.removed_warriors,
+            added_resources: delta.added_resources.iter().map(Self::resource_data).collect(),
+            removed_resources: delta.removed_resources,
+        };
+        serde_wasm_bindgen::to_value(&data).unwrap()
+    }
+    
+    /// Status flag names mapped to their `WarriorData::status_flags` bits.
+    #[wasm_bindgen]
+    pub fn get_status_flag_legend(&self) -> JsValue {
+        let legend: BTreeMap<&str, u32> = WarriorData::STATUS_FLAGS.into_iter().collect();
+        legend.serialize(&serde_wasm_bindgen::Serializer::json_compatible()).unwrap()
+    }
+    
+    /// Every memory territory with its owner, density and size.
+    #[wasm_bindgen]
+    pub fn get_territory_map(&self) -> JsValue {
+        let entries: Vec<TerritoryMapEntry> = self.simulation.memory_allocator.territory_map().into_iter()
+            .map(|(id, owner_id, resource_density, size)| TerritoryMapEntry { id, owner_id, resource_density, size })
+            .collect();
+        serde_wasm_bindgen::to_value(&entries).unwrap()
+    }
+    
     #[wasm_bindgen]
     pub fn get_memory_heatmap(&self) -> JsValue {
         let heatmap = self.generate_memory_heatmap();
@@ -193,6 +488,41 @@ impl WasmSimulation {
         serde_wasm_bindgen::to_value(&topology).unwrap()
     }
     
+    /// The warrior's network in the portable `NetworkExport` JSON format.
+    #[wasm_bindgen]
+    pub fn export_network(&self, warrior_id: u32) -> Result<String, JsValue> {
+        let warrior = self.simulation.environment.warriors.get(&warrior_id)
+            .ok_or_else(|| JsValue::from_str(&format!("No warrior with id {}", warrior_id)))?;
+        warrior.network.export_json().map_err(|error| JsValue::from_str(&error.to_string()))
+    }
+    
+    /// The warrior's layers as `LayerView`s: nested `weights[from][to]`
+    /// arrays plus biases, ready for `numpy.array`.
+    #[wasm_bindgen]
+    pub fn export_network_layers(&self, warrior_id: u32) -> Result<JsValue, JsValue> {
+        let warrior = self.simulation.environment.warriors.get(&warrior_id)
+            .ok_or_else(|| JsValue::from_str(&format!("No warrior with id {}", warrior_id)))?;
+        serde_wasm_bindgen::to_value(&warrior.network.layers()).map_err(JsValue::from)
+    }
+    
+    #[wasm_bindgen]
+    pub fn get_species_report(&self) -> JsValue {
+        let report = self.simulation.get_species_stats();
+        serde_wasm_bindgen::to_value(&report).unwrap()
+    }
+    
+    #[wasm_bindgen]
+    pub fn get_lineage_summary(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.simulation.lineage_summary()).unwrap()
+    }
+    
+    /// Instruction cost each warrior spent this generation, keyed by id.
+    #[wasm_bindgen]
+    pub fn get_compute_usage(&self) -> JsValue {
+        let usage: BTreeMap<u32, u32> = self.simulation.compute_usage().iter().map(|(&id, &spent)| (id, spent)).collect();
+        usage.serialize(&serde_wasm_bindgen::Serializer::json_compatible()).unwrap()
+    }
+    
     #[wasm_bindgen]
     pub fn get_performance_metrics(&self) -> JsValue {
         let stats = self.simulation.get_statistics();
@@ -230,6 +560,11 @@ impl WasmSimulation {
     pub fn get_tick(&self) -> u64 {
         self.simulation.tick
     }
+    
+    #[wasm_bindgen]
+    pub fn rename_warrior(&mut self, warrior_id: u32, name: &str) -> bool {
+        self.simulation.rename_warrior(warrior_id, name)
+    }
 }
 
 impl WasmSimulation {
@@ -239,29 +574,15 @@ impl WasmSimulation {
         let species_stats = self.simulation.get_species_stats();
         
         // Convert warriors to serializable format
-        let warriors: Vec<WarriorData> = env_state.warriors.iter().map(|warrior| {
-            WarriorData {
-                id: warrior.id,
-                x: warrior.position.0,
-                y: warrior.position.1,
-                energy: warrior.energy,
-                age: warrior.age,
-                fitness: warrior.fitness_score,
-                lineage_depth: warrior.lineage_depth,
-                species_id: None, // TODO: Map warrior to species
-                action: "idle".to_string(), // TODO: Get last action
-            }
-        }).collect();
+        let status = StatusFlagComposer::new(&self.simulation.environment);
+        let warriors: Vec<WarriorData> = env_state.warriors.iter()
+            .map(|warrior| self.warrior_data(warrior, &status))
+            .collect();
         
         // Convert resources to serializable format
-        let resources: Vec<ResourceData> = env_state.resources.iter().map(|resource| {
-            ResourceData {
-                x: resource.position.0,
-                y: resource.position.1,
-                energy_value: resource.energy_value,
-                resource_type: format!("{:?}", resource.resource_type),
-            }
-        }).collect();
+        let resources: Vec<ResourceData> = env_state.resources.iter()
+            .map(Self::resource_data)
+            .collect();
         
         // Convert territories to serializable format
         let territories: Vec<TerritoryData> = env_state.territories.iter().map(|territory| {
@@ -274,10 +595,18 @@ impl WasmSimulation {
             }
         }).collect();
         
+        let terrain = if self.include_terrain {
+            TerrainData::from_environment(&self.simulation.environment)
+        } else {
+            TerrainData { barriers: Vec::new(), safe_zones: Vec::new() }
+        };
+        
         SimulationState {
             warriors,
             resources,
             territories,
+            barriers: terrain.barriers,
+            safe_zones: terrain.safe_zones,
             generation: stats.generation,
             tick: stats.tick,
             population_size: stats.population_size,
@@ -289,28 +618,53 @@ impl WasmSimulation {
         }
     }
     
+    fn warrior_data(&self, warrior: &NeuralWarrior, status: &StatusFlagComposer) -> WarriorData {
+        WarriorData {
+            id: warrior.id,
+            name: warrior.name.clone(),
+            x: warrior.position.0,
+            y: warrior.position.1,
+            energy: warrior.energy,
+            age: warrior.age,
+            fitness: warrior.fitness_score,
+            lineage_depth: warrior.lineage_depth,
+            species_id: self.simulation.speciation_manager.species_of(warrior.id),
+            action: "idle".to_string(), // TODO: Get last action
+            status_flags: status.flags(warrior),
+        }
+    }
+    
+    fn resource_data(resource: &Resource) -> ResourceData {
+        ResourceData {
+            id: resource.id,
+            x: resource.position.0,
+            y: resource.position.1,
+            energy_value: resource.energy_value,
+            resource_type: format!("{:?}", resource.resource_type),
+            claimed_by: resource.claim.map(|claim| claim.warrior_id),
+        }
+    }
+    
+    /// Owned territories glow brightest, free ones dimly by resource
+    /// density; unpartitioned memory stays dark.
     fn generate_memory_heatmap(&self) -> MemoryHeatmapData {
         let width = 64;
         let height = 64;
-        let mut data = vec![0.0; width * height];
-        
-        // Generate heatmap based on VM memory usage and territory allocation
-        let memory_size = self.simulation.vm.memory_size();
-        let territories = self.simulation.memory_allocator.total_territories();
+        let allocator = &self.simulation.memory_allocator;
+        let territory_map = allocator.territory_map();
+        let memory_size = allocator.total_size();
+        let territory_size = allocator.territory_size();
         
-        for i in 0..data.len() {
-            // Map 2D heatmap coordinates to VM memory addresses
-            let memory_address = (i * memory_size) / data.len();
-            
-            // Check if this memory region is allocated
-            let intensity = if self.simulation.memory_allocator.can_access(memory_address, 999) {
-                0.3 + (rand::random::<f32>() * 0.7) // Random activity for visualization
-            } else {
-                0.8 + (rand::random::<f32>() * 0.2) // High intensity for allocated regions
-            };
-            
-            data[i] = intensity;
-        }
+        let data = (0..width * height).map(|cell| {
+            // Map 2D heatmap coordinates to memory addresses; territories
+            // tile memory in address order, so the owner is found directly
+            let memory_address = (cell * memory_size) / (width * height);
+            territory_map.get(memory_address / territory_size)
+                .map_or(0.0, |&(_, owner, density, _)| match owner {
+                    Some(_) => 0.8 + density * 0.2,
+                    None => density * 0.3,
+                })
+        }).collect();
         
         MemoryHeatmapData {
             width,
@@ -323,58 +677,54 @@ impl WasmSimulation {
         let mut nodes = Vec::new();
         let mut connections = Vec::new();
         
-        // Generate a simple neural network topology visualization
-        // Input layer (8 nodes)
-        for i in 0..8 {
-            nodes.push(NetworkNode {
-                id: i,
-                x: 50.0,
-                y: 50.0 + (i as f32 * 40.0),
-                activation: rand::random::<f32>(),
-                node_type: "input".to_string(),
-            });
-        }
-        
-        // Hidden layer (16 nodes)
-        for i in 8..24 {
-            nodes.push(NetworkNode {
-                id: i,
-                x: 200.0,
-                y: 25.0 + ((i - 8) as f32 * 20.0),
-                activation: rand::random::<f32>(),
-                node_type: "hidden".to_string(),
-            });
-        }
+        let warrior = match self.simulation.environment.warriors.get(&warrior_id) {
+            Some(warrior) => warrior,
+            None => return NetworkTopologyData { nodes, connections },
+        };
         
-        // Output layer (4 nodes)
-        for i in 24..28 {
-            nodes.push(NetworkNode {
-                id: i,
-                x: 350.0,
-                y: 100.0 + ((i - 24) as f32 * 50.0),
-                activation: rand::random::<f32>(),
-                node_type: "output".to_string(),
-            });
-        }
+        let network = &warrior.network;
+        let layer_sizes = network.layer_sizes();
+        let sensors = warrior.sense_environment(&self.simulation.environment.get_environment_state());
+        let activations = network.layer_activations(&sensors.to_inputs());
+        let last_layer = layer_sizes.len().saturating_sub(1);
         
-        // Generate connections (simplified - full connectivity between layers)
-        for input_id in 0..8 {
-            for hidden_id in 8..24 {
-                connections.push(NetworkConnection {
-                    from: input_id,
-                    to: hidden_id,
-                    weight: (rand::random::<f32>() - 0.5) * 2.0,
+        // Lay each layer out as a column; node ids are assigned layer by layer
+        let mut layer_offsets = Vec::with_capacity(layer_sizes.len());
+        let mut next_id = 0u32;
+        for (layer, &size) in layer_sizes.iter().enumerate() {
+            layer_offsets.push(next_id);
+            let node_type = if layer == 0 {
+                "input"
+            } else if layer == last_layer {
+                "output"
+            } else {
+                "hidden"
+            };
+            let spacing = 400.0 / size.max(1) as f32;
+            
+            for i in 0..size {
+                nodes.push(NetworkNode {
+                    id: next_id,
+                    x: 50.0 + layer as f32 * 150.0,
+                    y: 25.0 + (i as f32 + 0.5) * spacing,
+                    activation: activations.get(layer).and_then(|layer| layer.get(i)).copied().unwrap_or(0.0),
+                    node_type: node_type.to_string(),
                 });
+                next_id += 1;
             }
         }
         
-        for hidden_id in 8..24 {
-            for output_id in 24..28 {
-                connections.push(NetworkConnection {
-                    from: hidden_id,
-                    to: output_id,
-                    weight: (rand::random::<f32>() - 0.5) * 2.0,
-                });
+        for layer in 0..last_layer {
+            for from in 0..layer_sizes[layer] {
+                for to in 0..layer_sizes[layer + 1] {
+                    if let Some(weight) = network.weight(layer, from, to) {
+                        connections.push(NetworkConnection {
+                            from: layer_offsets[layer] + from as u32,
+                            to: layer_offsets[layer + 1] + to as u32,
+                            weight,
+                        });
+                    }
+                }
             }
         }
         
@@ -389,12 +739,12 @@ impl WasmSimulation {
         let mut csv = String::new();
         
         // CSV header
-        csv.push_str("id,x,y,energy,age,fitness,lineage_depth\n");
+        csv.push_str("id,name,x,y,energy,age,fitness,lineage_depth\n");
         
-        // Warrior data
+        // Warrior data; names are user-editable, so quote them
         for warrior in &state.warriors {
-            csv.push_str(&format!("{},{},{},{},{},{},{}\n",
-                warrior.id, warrior.x, warrior.y, warrior.energy,
+            csv.push_str(&format!("{},\"{}\",{},{},{},{},{},{}\n",
+                warrior.id, warrior.name.replace('"', "\"\""), warrior.x, warrior.y, warrior.energy,
                 warrior.age, warrior.fitness, warrior.lineage_depth
             ));
         }
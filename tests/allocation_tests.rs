@@ -0,0 +1,142 @@
+// A second global allocator can't coexist with wee_alloc's
+#![cfg(not(feature = "wee_alloc"))]
+
+use neural_network_arena::environment::{ActionMap, Environment};
+use neural_network_arena::neural::{Action, Genome, GenomeCodecKind, NeuralWarrior};
+use neural_network_arena::{NeuralArenaSimulation, SimulationConfig};
+use neural_network_arena::testing::{fixed_rng, fixed_warrior};
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts allocations made by the current thread, so parallel tests don't
+/// see each other's.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/// A genome whose network actually responds to its inputs; the default
+/// codec builds one that outputs zeros.
+fn direct_genome(rng: &mut StdRng) -> Genome {
+    let mut genome = Genome::random_with_rng(rng);
+    genome.set_codec(GenomeCodecKind::Direct);
+    genome
+}
+
+#[test]
+fn test_forward_into_matches_forward_without_allocating() {
+    let mut rng = fixed_rng(3);
+    let mut outputs = Vec::new();
+    for _ in 0..20 {
+        let network = direct_genome(&mut rng).to_network();
+        // Short, exact and overlong inputs
+        let length = rng.gen_range(0..30);
+        let inputs: Vec<f32> = (0..length).map(|_| rng.gen_range(-2.0..2.0)).collect();
+
+        network.forward_into(&inputs, &mut outputs);
+        assert_eq!(outputs, network.forward(&inputs));
+        assert!(outputs.iter().any(|&output| output != 0.0));
+        assert_eq!(allocations_during(|| network.forward_into(&inputs, &mut outputs)), 0);
+    }
+}
+
+#[test]
+fn test_buffered_decisions_stop_allocating() {
+    let mut rng = fixed_rng(4);
+    let environment = Environment::with_seed(500.0, 500.0, 50, 4);
+    let warrior = NeuralWarrior::with_position(direct_genome(&mut rng), 1, (250.0, 250.0));
+    let sensors = warrior.sense_environment(&environment.get_environment_state());
+
+    let mut outputs = Vec::new();
+    for temperature in [0.0, 1.5] {
+        let expected = warrior.choose_action(&sensors, temperature, &mut fixed_rng(9));
+        let action = warrior.choose_action_into(&sensors, temperature, &mut fixed_rng(9), &mut outputs);
+        assert_eq!(format!("{:?}", action), format!("{:?}", expected));
+
+        let mut decision_rng = fixed_rng(9);
+        assert_eq!(allocations_during(|| {
+            warrior.choose_action_into(&sensors, temperature, &mut decision_rng, &mut outputs);
+        }), 0);
+    }
+    assert!(allocations_during(|| {
+        warrior.choose_action(&sensors, 0.0, &mut fixed_rng(9));
+    }) > 0);
+}
+
+#[test]
+fn test_action_map_keeps_its_capacity() {
+    let mut environment = Environment::with_seed(500.0, 500.0, 50, 5);
+    let mut rng = fixed_rng(5);
+    for id in 1..=8 {
+        environment.add_warrior(fixed_warrior(id, (50.0 * id as f32, 250.0), &mut rng));
+    }
+
+    let mut actions = ActionMap::new();
+    for _ in 0..3 {
+        actions.extend((1..=8).map(|id| (id, Action::Rest)));
+        let capacity = actions.capacity();
+        let results = environment.execute_action_map(&mut actions);
+        assert_eq!(results.results.len(), 8);
+        assert!(actions.is_empty());
+        assert_eq!(actions.capacity(), capacity);
+    }
+
+    // Refilling the drained map needs no new allocation
+    assert_eq!(allocations_during(|| actions.extend((1..=8).map(|id| (id, Action::Rest)))), 0);
+}
+
+#[test]
+fn test_simulation_tick_clones_the_population_at_most_once() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(6),
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(50);
+    // Let scratch buffers and maps grow to their working size
+    for _ in 0..5 {
+        simulation.single_tick();
+    }
+
+    let population_clone = allocations_during(|| {
+        let _warriors: Vec<NeuralWarrior> = simulation.environment.warriors.values().cloned().collect();
+    });
+    let tick = allocations_during(|| {
+        simulation.single_tick();
+    });
+    assert!(!simulation.environment.warriors.is_empty());
+    // The environment state's snapshot is the one copy; decisions used to
+    // clone every warrior a second time on top of it
+    assert!(tick < 2 * population_clone, "A tick made {} allocations, a population clone {}", tick, population_clone);
+}
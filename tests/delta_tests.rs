@@ -0,0 +1,111 @@
+use neural_network_arena::delta::EnvironmentMirror;
+use neural_network_arena::environment::Environment;
+use neural_network_arena::neural::{Action, Genome, NeuralWarrior};
+use std::collections::HashMap;
+
+fn populated_environment() -> Environment {
+    let mut environment = Environment::with_seed(500.0, 500.0, 50, 11);
+    for id in 1..=10 {
+        let warrior = NeuralWarrior::with_position(Genome::new_random(), id, (id as f32 * 40.0, 250.0));
+        environment.add_warrior(warrior);
+    }
+    environment.sync_changes();
+    environment
+}
+
+fn step(environment: &mut Environment, tick: u32) {
+    environment.tick();
+    
+    let mut actions = HashMap::new();
+    for &id in environment.warriors.keys() {
+        let action = if id % 3 == 0 {
+            Action::Rest
+        } else {
+            Action::Move { direction: tick as f32 * 0.3 + id as f32, intensity: 0.8 }
+        };
+        actions.insert(id, action);
+    }
+    environment.execute_warrior_actions(actions);
+    
+    // Churn the population so removals and additions show up in deltas
+    if tick % 7 == 0 {
+        let oldest = *environment.warriors.keys().next().unwrap();
+        environment.remove_warrior(oldest);
+        let newcomer = NeuralWarrior::with_position(Genome::new_random(), 100 + tick, (250.0, 100.0));
+        environment.add_warrior(newcomer);
+    }
+    environment.sync_changes();
+}
+
+fn assert_mirror_matches(mirror: &EnvironmentMirror, environment: &Environment, tolerance: f32) {
+    let mirror_ids: Vec<u32> = mirror.warriors.keys().copied().collect();
+    let environment_ids: Vec<u32> = environment.warriors.keys().copied().collect();
+    assert_eq!(mirror_ids, environment_ids);
+    
+    for (id, warrior) in &environment.warriors {
+        let mirrored = &mirror.warriors[id];
+        let dx = mirrored.position.0 - warrior.position.0;
+        let dy = mirrored.position.1 - warrior.position.1;
+        assert!((dx * dx + dy * dy).sqrt() <= tolerance, "Warrior {} position drifted", id);
+        assert!((mirrored.energy - warrior.energy).abs() <= tolerance, "Warrior {} energy drifted", id);
+    }
+    
+    let mut resource_ids: Vec<u64> = environment.resources.iter().map(|r| r.id).collect();
+    resource_ids.sort_unstable();
+    let mirror_resource_ids: Vec<u64> = mirror.resources.keys().copied().collect();
+    assert_eq!(mirror_resource_ids, resource_ids);
+}
+
+#[test]
+fn test_applying_deltas_reproduces_current_state() {
+    let mut environment = populated_environment();
+    environment.change_log.position_threshold = 0.0;
+    environment.change_log.energy_threshold = 0.0;
+    
+    let mut mirror = EnvironmentMirror::new();
+    mirror.apply(&environment.state_delta(0));
+    assert_mirror_matches(&mirror, &environment, 0.0);
+    
+    for tick in 1..=40 {
+        step(&mut environment, tick);
+        let delta = environment.state_delta(mirror.tick);
+        assert!(!delta.full);
+        mirror.apply(&delta);
+        assert_mirror_matches(&mirror, &environment, 0.0);
+    }
+}
+
+#[test]
+fn test_thresholds_suppress_small_changes() {
+    let mut environment = populated_environment();
+    let mut mirror = EnvironmentMirror::new();
+    mirror.apply(&environment.state_delta(0));
+    
+    // One tick of aging drains far less than the default 1.0 energy threshold
+    environment.tick();
+    let delta = environment.state_delta(environment.tick);
+    assert!(delta.warriors.is_empty(), "Expected no reportable changes, got {}", delta.warriors.len());
+    
+    for tick in 1..=20 {
+        step(&mut environment, tick);
+        mirror.apply(&environment.state_delta(mirror.tick));
+    }
+    assert_mirror_matches(&mirror, &environment, environment.change_log.position_threshold.max(1.0));
+}
+
+#[test]
+fn test_evicted_history_falls_back_to_full_snapshot() {
+    let mut environment = populated_environment();
+    environment.change_log.set_capacity(5);
+    
+    for tick in 1..=10 {
+        step(&mut environment, tick);
+    }
+    
+    let delta = environment.state_delta(1);
+    assert!(delta.full);
+    
+    let mut mirror = EnvironmentMirror::new();
+    mirror.apply(&delta);
+    assert_mirror_matches(&mirror, &environment, 0.0);
+}
@@ -0,0 +1,159 @@
+use neural_network_arena::diagnostics::{ActionHistogram, ConvergenceMonitor, DegenerateActionMonitor};
+use neural_network_arena::neural::{Action, ActionKind, EnvironmentSensors, Genome, NeuralNetwork, NeuralWarrior};
+use neural_network_arena::statistics::behavior_variance;
+use neural_network_arena::{NeuralArenaSimulation, SimulationConfig, SimulationWarning};
+use neural_network_arena::testing::{fixed_rng, random_position};
+
+fn histogram_of(actions: &[Action]) -> ActionHistogram {
+    let mut histogram = ActionHistogram::new();
+    for action in actions {
+        histogram.record(action);
+    }
+    histogram
+}
+
+#[test]
+fn test_all_rest_population_raises_warning() {
+    let mut monitor = DegenerateActionMonitor::new(0.95, 3);
+    let all_rest = histogram_of(&[Action::Rest; 50]);
+    
+    assert_eq!(all_rest.dominant(), Some((ActionKind::Rest, 1.0)));
+    assert!(monitor.observe(1, &all_rest).is_none());
+    assert!(monitor.observe(2, &all_rest).is_none());
+    
+    match monitor.observe(3, &all_rest) {
+        Some(SimulationWarning::DegenerateActions { generation, action, share, generations }) => {
+            assert_eq!(generation, 3);
+            assert_eq!(action, ActionKind::Rest);
+            assert_eq!(share, 1.0);
+            assert_eq!(generations, 3);
+        },
+        other => panic!("Expected a degenerate action warning, got {:?}", other),
+    }
+}
+
+/// A warrior whose network ignores its inputs and has a single output,
+/// too few to select an action from, so it always rests.
+fn resting_warrior(id: u32) -> NeuralWarrior {
+    let network = NeuralNetwork::import_json(&serde_json::json!({
+        "format_version": 1,
+        "activation": "tanh",
+        "layer_sizes": [EnvironmentSensors::INPUT_SIZE, 1],
+        "weights": vec![0.0; EnvironmentSensors::INPUT_SIZE],
+        "biases": [0.0],
+    }).to_string()).unwrap();
+    
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), id, (50.0 * id as f32, 500.0));
+    warrior.network = network;
+    warrior
+}
+
+#[test]
+fn test_all_rest_generations_warn_through_the_simulation() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(5),
+        max_population: 20,
+        generation_ticks: 50,
+        degenerate_action_generations: 3,
+        ..SimulationConfig::default()
+    });
+    
+    // Offspring decode new networks from their genomes, so every
+    // generation starts over from resting warriors
+    let mut results = Vec::new();
+    for _ in 0..3 {
+        simulation.environment.warriors.clear();
+        for id in 0..20 {
+            simulation.environment.add_warrior(resting_warrior(id));
+        }
+        results.push(simulation.run_generation());
+        assert_eq!(simulation.statistics.dominant_action_share, 1.0);
+    }
+    
+    // Twenty warriors can also saturate the territory
+    // allocator; only the degenerate action warnings matter here
+    let degenerate: Vec<Vec<SimulationWarning>> = results.iter()
+        .map(|result| result.warnings.iter()
+            .filter(|warning| matches!(warning, SimulationWarning::DegenerateActions { .. }))
+            .cloned()
+            .collect())
+        .collect();
+    
+    assert!(degenerate[0].is_empty());
+    assert!(degenerate[1].is_empty());
+    assert!(matches!(
+        degenerate[2].as_slice(),
+        [SimulationWarning::DegenerateActions { generation: 3, action: ActionKind::Rest, generations: 3, .. }]
+    ), "Expected a degenerate action warning, got {:?}", results[2].warnings);
+    assert!(simulation.warnings.contains(&degenerate[2][0]));
+}
+
+#[test]
+fn test_diverse_generation_resets_streak() {
+    let mut monitor = DegenerateActionMonitor::new(0.95, 2);
+    let all_rest = histogram_of(&[Action::Rest; 20]);
+    let mut mixed_actions = vec![Action::Rest; 10];
+    mixed_actions.extend([Action::Defend { shield_strength: 0.5 }; 10]);
+    let mixed = histogram_of(&mixed_actions);
+    
+    assert!(monitor.observe(1, &all_rest).is_none());
+    assert!(monitor.observe(2, &mixed).is_none());
+    assert_eq!(monitor.streak(), 0);
+    assert!(monitor.observe(3, &all_rest).is_none());
+    assert!(monitor.observe(4, &all_rest).is_some());
+}
+
+fn mixed_actions() -> Vec<Action> {
+    vec![
+        Action::Rest,
+        Action::Defend { shield_strength: 0.5 },
+        Action::Move { direction: 0.0, intensity: 1.0 },
+        Action::Attack { target_direction: 0.0, strength: 1.0 },
+        Action::ChargedAttack { target_direction: 0.0, strength: 1.0 },
+        Action::DeepRest,
+    ]
+}
+
+#[test]
+fn test_behavioral_entropy_separates_converged_and_mixed_populations() {
+    let mut rng = fixed_rng(1);
+    let all_rest = histogram_of(&[Action::Rest; 40]);
+    let mixed = histogram_of(&mixed_actions().repeat(10));
+    
+    assert!(all_rest.entropy().abs() < 1e-6);
+    assert!(mixed.entropy() > 0.7, "Mixed entropy was {}", mixed.entropy());
+    
+    let mut population = |scripted: &dyn Fn(u32) -> Vec<Action>| {
+        let warriors: Vec<NeuralWarrior> = (0..8).map(|id| {
+            let mut warrior = NeuralWarrior::with_position(Genome::new_random(), id, random_position((1000.0, 1000.0), &mut rng));
+            for action in scripted(id) {
+                warrior.record_action(action);
+            }
+            warrior
+        }).collect();
+        behavior_variance(&warriors)
+    };
+    
+    let resting = population(&|_| vec![Action::Rest; 10]);
+    let specialists = population(&|id| vec![mixed_actions()[id as usize % 6]; 10]);
+    assert_eq!(resting, 0.0);
+    assert!(specialists > 0.05);
+}
+
+#[test]
+fn test_convergence_monitor_needs_consecutive_low_entropy() {
+    let mut monitor = ConvergenceMonitor::new(0.2, 2);
+    
+    assert!(monitor.observe(1, 0.05).is_none());
+    assert!(monitor.observe(2, 0.9).is_none());
+    assert!(monitor.observe(3, 0.05).is_none());
+    assert!(matches!(
+        monitor.observe(4, 0.0),
+        Some(SimulationWarning::BehavioralConvergence { generation: 4, generations: 2, .. })
+    ));
+    assert!(monitor.is_converged());
+    
+    // A zero floor never fires
+    let mut disabled = ConvergenceMonitor::new(0.0, 1);
+    assert!(disabled.observe(1, 0.0).is_none());
+}
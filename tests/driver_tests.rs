@@ -0,0 +1,42 @@
+use neural_network_arena::driver::{SimEvent, SimulationDriver};
+use neural_network_arena::{NeuralArenaSimulation, SimulationConfig};
+
+fn driver() -> SimulationDriver {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(3),
+        generation_ticks: 30,
+        ..SimulationConfig::preset_quick_demo()
+    });
+    simulation.initialize_population(20);
+    SimulationDriver::spawn(simulation)
+}
+
+#[tokio::test]
+async fn test_driver_runs_generation_and_publishes_events() {
+    let driver = driver();
+    let mut events = driver.subscribe_events();
+    
+    assert_eq!(driver.step(3).await.unwrap(), 3);
+    assert_eq!(driver.run_generation().await.unwrap(), 1);
+    
+    for expected_tick in 1..=3 {
+        assert!(matches!(events.recv().await.unwrap(), SimEvent::Tick { tick, .. } if tick == expected_tick));
+    }
+    assert!(matches!(events.recv().await.unwrap(), SimEvent::GenerationCompleted { generation: 1, .. }));
+    
+    let snapshot = driver.snapshot().await.unwrap();
+    assert_eq!(snapshot.generation, 1);
+    assert_eq!(snapshot.tick, 33);
+    
+    driver.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_every_subscriber_sees_events() {
+    let driver = driver();
+    let mut first = driver.subscribe_events();
+    let mut second = driver.subscribe_events();
+    
+    driver.step(1).await.unwrap();
+    assert_eq!(first.recv().await.unwrap(), second.recv().await.unwrap());
+}
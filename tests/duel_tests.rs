@@ -0,0 +1,79 @@
+use neural_network_arena::duel::{duel, duel_warriors, DuelSide};
+use neural_network_arena::neural::{Action, EnvironmentSensors, Genome, GenomeCodecKind, NeuralNetwork, NeuralWarrior};
+use neural_network_arena::testing::{fixed_rng, random_position};
+use std::f32::consts::{PI, TAU};
+
+/// A warrior whose network ignores its inputs and always picks `action`,
+/// attacking toward `attack_direction` (radians) at full strength and
+/// moving with zero intensity.
+fn scripted_warrior(action: usize, attack_direction: f32) -> NeuralWarrior {
+    let mut rng = fixed_rng(1);
+    let mut biases = vec![0.0; Action::OUTPUT_SIZE];
+    biases[action] = 1.0;
+    biases[Action::ATTACK_STRENGTH_OUTPUT] = 3.0;
+    // Outputs pass through tanh and directions scale them by 2π
+    biases[Action::ATTACK_DIRECTION_OUTPUT] = (attack_direction / TAU).atanh();
+    let network = NeuralNetwork::import_json(&serde_json::json!({
+        "format_version": 1,
+        "activation": "tanh",
+        "layer_sizes": [EnvironmentSensors::INPUT_SIZE, Action::OUTPUT_SIZE],
+        "weights": vec![0.0; EnvironmentSensors::INPUT_SIZE * Action::OUTPUT_SIZE],
+        "biases": biases,
+    }).to_string()).unwrap();
+    
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 0, random_position((1000.0, 1000.0), &mut rng));
+    warrior.network = network;
+    warrior
+}
+
+#[test]
+fn test_aggressor_beats_passive_opponent() {
+    // A starts on the left, so it attacks toward +x; B just stands still
+    let aggressor = scripted_warrior(1, 0.0);
+    let passive = scripted_warrior(0, 0.0);
+    
+    let result = duel_warriors(aggressor, passive, (400.0, 400.0), 200);
+    assert_eq!(result.winner, Some(DuelSide::A), "{:?}", result);
+    assert!(result.a.damage_dealt > 0.0);
+    assert_eq!(result.b.damage_dealt, 0.0);
+    assert!(result.a.final_energy > result.b.final_energy);
+    
+    // Swapping sides (and aiming the other way) swaps the winner
+    let swapped = duel_warriors(scripted_warrior(0, 0.0), scripted_warrior(1, PI), (400.0, 400.0), 200);
+    assert_eq!(swapped.winner, Some(DuelSide::B), "{:?}", swapped);
+}
+
+#[test]
+fn test_genome_duels_are_repeatable() {
+    // Direct-codec genomes decode to distinct networks
+    let mut rng = fixed_rng(2);
+    let mut direct_genome = || {
+        let mut genome = Genome::random_with_rng(&mut rng);
+        genome.set_codec(GenomeCodecKind::Direct);
+        genome
+    };
+    let (genome_a, genome_b) = (direct_genome(), direct_genome());
+    let inputs = [0.5; EnvironmentSensors::INPUT_SIZE];
+    assert_ne!(genome_a.to_network().forward(&inputs), genome_b.to_network().forward(&inputs));
+    
+    let first = duel(genome_a.clone(), genome_b.clone(), (300.0, 300.0), 50);
+    let second = duel(genome_a, genome_b, (300.0, 300.0), 50);
+    
+    assert_eq!(first, second);
+    assert!(first.ticks <= 50);
+}
+
+#[test]
+fn test_damage_on_the_killing_tick_counts() {
+    // Both attack at once, and each hit is lethal
+    let mut aggressor = scripted_warrior(1, 0.0);
+    let mut defender = scripted_warrior(1, PI);
+    aggressor.energy = 8.0;
+    defender.energy = 8.0;
+    
+    let result = duel_warriors(aggressor, defender, (400.0, 400.0), 200);
+    assert_eq!(result.ticks, 1, "{:?}", result);
+    assert!(!result.a.survived && !result.b.survived, "{:?}", result);
+    assert!(result.a.damage_dealt > 0.0 && result.b.damage_dealt > 0.0, "{:?}", result);
+    assert_eq!(result.winner, None);
+}
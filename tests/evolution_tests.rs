@@ -0,0 +1,494 @@
+use neural_network_arena::evolution::{FitnessFloor, SpeciationManager};
+use neural_network_arena::neural::{Genome, NeuralWarrior};
+use neural_network_arena::testing::{fixed_rng, fixed_warrior, random_position};
+
+/// A warrior whose genome and position are seeded by its id, so the same
+/// id always yields the same warrior.
+fn warrior_with_fitness(id: u32, fitness: f32) -> NeuralWarrior {
+    let mut rng = fixed_rng(id as u64);
+    let mut warrior = fixed_warrior(id, random_position((1000.0, 1000.0), &mut rng), &mut rng);
+    warrior.fitness_score = fitness;
+    warrior
+}
+
+#[test]
+fn test_extinct_species_ids_are_not_reused() {
+    let mut manager = SpeciationManager::new(4);
+    manager.auto_adjust_threshold = false;
+    
+    manager.speciate(&[warrior_with_fitness(1, 100.0)]);
+    let first_species = manager.species_of(1).unwrap();
+    
+    // A completely different population drives the first species extinct
+    manager.speciate(&[warrior_with_fitness(2, 500.0)]);
+    let second_species = manager.species_of(2).unwrap();
+    
+    assert!(!manager.species.contains_key(&first_species));
+    assert_ne!(second_species, first_species);
+    
+    manager.speciate(&[warrior_with_fitness(3, 900.0)]);
+    let third_species = manager.species_of(3).unwrap();
+    assert_ne!(third_species, first_species);
+    assert_ne!(third_species, second_species);
+    
+    // Lineage survives extinction so the species tree can still be drawn
+    assert!(manager.species_lineage.contains_key(&first_species));
+}
+
+#[test]
+fn test_species_founded_by_offspring_links_to_parent_species() {
+    let mut manager = SpeciationManager::new(4);
+    manager.auto_adjust_threshold = false;
+    
+    manager.speciate(&[warrior_with_fitness(1, 100.0)]);
+    let parent_species = manager.species_of(1).unwrap();
+    
+    // A fresh id with a genome too distant to join its parent's species
+    let mut child = warrior_with_fitness(50, 120.0);
+    child.parent_ids = vec![1];
+    manager.speciate(&[child]);
+    let child_species = manager.species_of(50).unwrap();
+    
+    assert_ne!(child_species, parent_species);
+    assert_eq!(manager.species_lineage[&child_species], Some(parent_species));
+}
+
+#[test]
+fn test_species_report_exposes_palette_indices() {
+    let mut manager = SpeciationManager::new(4);
+    manager.auto_adjust_threshold = false;
+    
+    manager.speciate(&[warrior_with_fitness(1, 100.0), warrior_with_fitness(2, 500.0)]);
+    let report = manager.get_species_stats();
+    
+    assert_eq!(report.species.len(), 2);
+    assert_ne!(report.species[0].palette_index, report.species[1].palette_index);
+}
+
+#[test]
+fn test_palette_slots_are_reused_only_after_cooldown() {
+    let mut manager = SpeciationManager::new(4);
+    manager.auto_adjust_threshold = false;
+    
+    // Representatives carry the warrior's fitness, so each warrior keeps
+    // its own species from round to round
+    let settled = |id: u32, fitness: f32| {
+        let mut warrior = warrior_with_fitness(id, fitness);
+        warrior.genome.set_fitness(fitness);
+        warrior
+    };
+    let palette_of = |manager: &SpeciationManager, id: u32| manager.species[&manager.species_of(id).unwrap()].palette_index;
+    
+    manager.speciate(&[settled(1, 100.0), settled(2, 500.0)]);
+    assert_eq!((palette_of(&manager, 1), palette_of(&manager, 2)), (0, 1));
+    
+    // Warrior 2's species dies out in round 2, retiring slot 1
+    let mut population = vec![settled(1, 100.0)];
+    for _ in 2..6 {
+        manager.speciate(&population);
+    }
+    
+    // Round 6 is still inside the cooldown, so a newcomer gets a fresh slot
+    population.push(settled(3, 900.0));
+    manager.speciate(&population);
+    assert_eq!(palette_of(&manager, 3), 2);
+    
+    // From round 7 the retired slot is free again
+    population.push(settled(4, 1300.0));
+    manager.speciate(&population);
+    assert_eq!(palette_of(&manager, 4), 1);
+    assert_eq!(palette_of(&manager, 1), 0);
+}
+
+#[test]
+fn test_deletion_and_parsimony_shrink_genomes() {
+    let mut manager = SpeciationManager::new(4);
+    manager.deletion_rate = 1.0;
+    manager.parsimony_coefficient = 10.0;
+    manager.reseed(11);
+    let mut rng = fixed_rng(11);
+    
+    let mean_size = |warriors: &[NeuralWarrior]| {
+        warriors.iter().map(|w| w.genome.size()).sum::<usize>() as f32 / warriors.len() as f32
+    };
+    
+    // Neutral landscape: every warrior has the same raw fitness
+    let mut warriors: Vec<NeuralWarrior> = (0..30).map(|id| fixed_warrior(id, (0.0, 0.0), &mut rng)).collect();
+    let initial_size = mean_size(&warriors);
+    
+    for _ in 0..20 {
+        manager.speciate(&warriors);
+        warriors = manager.perform_species_selection(&warriors);
+    }
+    
+    let final_size = mean_size(&warriors);
+    assert!(final_size < initial_size, "Mean genome size should shrink: {} -> {}", initial_size, final_size);
+    assert!(warriors.iter().all(|w| w.genome.size() >= Genome::MIN_SIZE));
+}
+
+#[test]
+fn test_max_offspring_fraction_caps_dominant_species() {
+    let mut rng = fixed_rng(2);
+    let mut manager = SpeciationManager::new(4);
+    manager.auto_adjust_threshold = false;
+    manager.max_offspring_fraction = 0.6;
+    manager.reseed(3);
+    // Group species purely by genome size
+    manager.compatibility_weights.lineage_weight = 0.0;
+    manager.compatibility_weights.fitness_weight = 0.0;
+    manager.compatibility_weights.age_weight = 0.0;
+    
+    let dominant_genome = Genome::new_random();
+    let weak_genome = std::iter::repeat_with(Genome::new_random)
+        .find(|genome| genome.size().abs_diff(dominant_genome.size()) > 10)
+        .unwrap();
+    let mut warriors = Vec::new();
+    for id in 0..20 {
+        let (genome, fitness) = if id < 10 { (&dominant_genome, 1000.0) } else { (&weak_genome, 1.0) };
+        let mut warrior = NeuralWarrior::with_position(genome.clone(), id, random_position((1000.0, 1000.0), &mut rng));
+        warrior.fitness_score = fitness;
+        warriors.push(warrior);
+    }
+    
+    manager.speciate(&warriors);
+    let dominant_species = manager.species_of(0).unwrap();
+    assert_ne!(manager.species_of(10), Some(dominant_species));
+    
+    let offspring = manager.perform_species_selection(&warriors);
+    assert_eq!(offspring.len(), warriors.len());
+    
+    let dominant_offspring = offspring.iter()
+        .filter(|child| child.parent_ids.iter().any(|&parent| manager.species_of(parent) == Some(dominant_species)))
+        .count();
+    assert_eq!(dominant_offspring, 12, "Dominant species should be capped at 60% of 20 offspring");
+}
+
+#[test]
+fn test_negative_fitness_never_yields_negative_offspring_shares() {
+    let mut rng = fixed_rng(3);
+    // Two species told apart by genome size, both scored below zero by a
+    // penalizing evaluator
+    let evaluator = |id: u32| if id < 10 { -5.0 } else { -50.0 };
+    let mild_genome = Genome::new_random();
+    let harsh_genome = std::iter::repeat_with(Genome::new_random)
+        .find(|genome| genome.size().abs_diff(mild_genome.size()) > 10)
+        .unwrap();
+    let warriors: Vec<NeuralWarrior> = (0..20)
+        .map(|id| {
+            let genome = if id < 10 { &mild_genome } else { &harsh_genome };
+            let mut warrior = NeuralWarrior::with_position(genome.clone(), id, random_position((1000.0, 1000.0), &mut rng));
+            warrior.fitness_score = evaluator(id);
+            warrior
+        })
+        .collect();
+    
+    let offspring_by_species = |floor: FitnessFloor| {
+        let mut manager = SpeciationManager::new(4);
+        manager.auto_adjust_threshold = false;
+        manager.reseed(3);
+        manager.compatibility_weights.lineage_weight = 0.0;
+        manager.compatibility_weights.fitness_weight = 0.0;
+        manager.compatibility_weights.age_weight = 0.0;
+        manager.fitness_floor = floor;
+        manager.speciate(&warriors);
+        let mild_species = manager.species_of(0).unwrap();
+        assert_ne!(manager.species_of(10), Some(mild_species));
+        
+        let offspring = manager.perform_species_selection(&warriors);
+        assert_eq!(offspring.len(), warriors.len());
+        let mild = offspring.iter()
+            .filter(|child| child.parent_ids.iter().any(|&parent| manager.species_of(parent) == Some(mild_species)))
+            .count();
+        (mild, offspring.len() - mild)
+    };
+    
+    // Everyone clamps to zero, so the whole generation goes to the best warrior's species
+    assert_eq!(offspring_by_species(FitnessFloor::default()), (20, 0));
+    
+    // Shifted to 95 and 50, the species share the generation in proportion
+    let (mild, harsh) = offspring_by_species(FitnessFloor::Offset(100.0));
+    assert!(mild > harsh && harsh > 0, "mild {} vs harsh {}", mild, harsh);
+}
+
+#[test]
+fn test_incremental_speciation_matches_one_shot() {
+    let first_round: Vec<NeuralWarrior> = (0..30).map(|id| warrior_with_fitness(id, id as f32)).collect();
+    // Some warriors die and new ones arrive before the next round
+    let mut second_round: Vec<NeuralWarrior> = first_round.iter().skip(10).cloned().collect();
+    second_round.extend((30..40).map(|id| warrior_with_fitness(id, id as f32)));
+    
+    let mut one_shot = SpeciationManager::new(4);
+    let mut sliced = SpeciationManager::new(4);
+    for round in [&first_round, &second_round] {
+        one_shot.speciate(round);
+        
+        let mut batches = 0;
+        while sliced.speciate_incremental(round, 7) > 0 {
+            batches += 1;
+        }
+        assert!(batches > 1);
+        assert!(sliced.is_speciation_pending());
+        sliced.finish_speciation(round);
+        assert!(!sliced.is_speciation_pending());
+        
+        let members = |manager: &SpeciationManager| -> Vec<(u32, Vec<u32>)> {
+            manager.species.iter().map(|(id, species)| {
+                let mut members = species.members.clone();
+                members.sort_unstable();
+                (*id, members)
+            }).collect()
+        };
+        assert_eq!(members(&one_shot), members(&sliced));
+        assert_eq!(one_shot.compatibility_threshold, sliced.compatibility_threshold);
+        for warrior in round.iter() {
+            assert_eq!(one_shot.species_of(warrior.id), sliced.species_of(warrior.id));
+        }
+        assert_eq!(sliced.species_of(0).is_some(), round.iter().any(|warrior| warrior.id == 0));
+    }
+}
+
+#[test]
+fn test_finish_speciation_drops_warriors_that_died_mid_round() {
+    let population: Vec<NeuralWarrior> = (0..10).map(|id| warrior_with_fitness(id, 1.0)).collect();
+    let mut manager = SpeciationManager::new(4);
+    
+    manager.speciate_incremental(&population, 10);
+    manager.finish_speciation(&population[5..]);
+    
+    for warrior in &population {
+        assert_eq!(manager.species_of(warrior.id).is_some(), warrior.id >= 5);
+    }
+    for species in manager.species.values() {
+        assert!(!species.members.is_empty());
+        assert!(species.members.iter().all(|&id| id >= 5));
+    }
+}
+
+#[test]
+fn test_selection_without_species_or_warriors_is_empty() {
+    let mut manager = SpeciationManager::new(4);
+    assert!(manager.perform_species_selection(&[]).is_empty());
+    
+    // Nothing has been speciated yet
+    let warriors: Vec<NeuralWarrior> = (0..5).map(|id| warrior_with_fitness(id, 10.0)).collect();
+    assert!(manager.perform_species_selection(&warriors).is_empty());
+    
+    // Zero total fitness leaves every slot to the ranked fill
+    let idle: Vec<NeuralWarrior> = (0..5).map(|id| warrior_with_fitness(id, 0.0)).collect();
+    manager.speciate(&idle);
+    assert_eq!(manager.perform_species_selection(&idle).len(), idle.len());
+    assert!(manager.perform_species_selection(&[]).is_empty());
+}
+
+#[test]
+fn test_batch_and_sequential_evaluation_agree() {
+    use neural_network_arena::evolution::{EvaluationError, Population};
+    use neural_network_arena::neural::{GenomeCodecKind, NeuralNetwork};
+    
+    let score = |network: &NeuralNetwork| {
+        let outputs = network.forward(&vec![0.5; network.layer_sizes()[0]]);
+        outputs.iter().sum::<f32>() + network.parameter_count() as f32
+    };
+    
+    // Direct-codec genomes decode to distinct networks, so a fitness value
+    // landing on the wrong genome shows up
+    let mut rng = fixed_rng(12);
+    let genomes = (0..12).map(|_| {
+        let mut genome = Genome::random_with_rng(&mut rng);
+        genome.set_codec(GenomeCodecKind::Direct);
+        genome
+    }).collect();
+    let mut sequential = Population::from_genomes(genomes);
+    let mut batch = sequential.clone();
+    sequential.evaluate_fitness(score);
+    batch.evaluate_fitness_batch(|networks| networks.iter().map(score).collect()).unwrap();
+    
+    let fitness = |population: &Population| population.genomes().iter().map(|genome| genome.fitness()).collect::<Vec<_>>();
+    let mut distinct = fitness(&sequential);
+    distinct.sort_by(f32::total_cmp);
+    distinct.dedup();
+    assert_eq!(distinct.len(), 12);
+    assert_eq!(fitness(&sequential), fitness(&batch));
+    
+    let mut reversed = sequential.clone();
+    reversed.evaluate_fitness_batch(|networks| networks.iter().rev().map(score).collect()).unwrap();
+    assert_ne!(fitness(&sequential), fitness(&reversed));
+    
+    #[cfg(feature = "parallel")]
+    {
+        let mut parallel = sequential.clone();
+        parallel.par_evaluate_fitness(score);
+        assert_eq!(fitness(&sequential), fitness(&parallel));
+    }
+    
+    let before = fitness(&batch);
+    assert_eq!(
+        batch.evaluate_fitness_batch(|_| vec![1.0]),
+        Err(EvaluationError::FitnessCount { expected: 12, actual: 1 })
+    );
+    assert_eq!(fitness(&batch), before);
+}
+
+#[test]
+fn test_species_policy_can_freeze_a_species() {
+    let mut rng = fixed_rng(4);
+    use neural_network_arena::evolution::{CrossoverStrategy, EvolutionParams};
+    
+    let mut manager = SpeciationManager::new(4);
+    manager.auto_adjust_threshold = false;
+    manager.reseed(5);
+    manager.deletion_rate = 0.5;
+    manager.compatibility_weights.lineage_weight = 0.0;
+    manager.compatibility_weights.fitness_weight = 0.0;
+    manager.compatibility_weights.age_weight = 0.0;
+    
+    let frozen_genome = Genome::new_random();
+    let other_genome = std::iter::repeat_with(Genome::new_random)
+        .find(|genome| genome.size().abs_diff(frozen_genome.size()) > 10)
+        .unwrap();
+    let warriors: Vec<NeuralWarrior> = (0..20)
+        .map(|id| {
+            let genome = if id < 10 { &frozen_genome } else { &other_genome };
+            let mut warrior = NeuralWarrior::with_position(genome.clone(), id, random_position((1000.0, 1000.0), &mut rng));
+            warrior.fitness_score = 100.0;
+            warrior
+        })
+        .collect();
+    
+    manager.speciate(&warriors);
+    let frozen_species = manager.species_of(0).unwrap();
+    let frozen = EvolutionParams {
+        mutation_rate_multiplier: 0.0,
+        crossover_strategy: CrossoverStrategy::Asexual,
+        interspecies_rate: 0.0,
+    };
+    manager.set_species_policy(Box::new(move |species| {
+        if species.id == frozen_species { frozen } else { EvolutionParams::default() }
+    }));
+    
+    let offspring = manager.perform_species_selection(&warriors);
+    let frozen_offspring: Vec<&NeuralWarrior> = offspring.iter()
+        .filter(|child| manager.species_of(child.parent_ids[0]) == Some(frozen_species))
+        .collect();
+    assert!(!frozen_offspring.is_empty());
+    for child in frozen_offspring {
+        assert_eq!(child.parent_ids.len(), 1);
+        let parent = &warriors[child.parent_ids[0] as usize];
+        assert_eq!(child.genome.bytes(), parent.genome.bytes());
+    }
+    
+    let report = manager.get_species_stats();
+    let summary = report.species.iter().find(|summary| summary.id == frozen_species).unwrap();
+    assert_eq!(summary.params, frozen);
+    assert!(report.species.iter()
+        .filter(|summary| summary.id != frozen_species)
+        .all(|summary| summary.params == EvolutionParams::default()));
+}
+
+#[test]
+fn test_nan_fitness_does_not_break_selection() {
+    let mut manager = SpeciationManager::new(4);
+    let warriors: Vec<NeuralWarrior> = (0..20)
+        .map(|id| {
+            let fitness = match id % 4 {
+                0 => f32::NAN,
+                1 => f32::INFINITY,
+                _ => id as f32,
+            };
+            warrior_with_fitness(id, fitness)
+        })
+        .collect();
+    
+    manager.speciate(&warriors);
+    let offspring = manager.perform_species_selection(&warriors);
+    
+    assert!(!offspring.is_empty());
+    assert!(manager.species.values().all(|species| species.average_fitness.is_finite()));
+}
+
+#[test]
+fn test_tournament_without_replacement_considers_every_member() {
+    let mut rng = fixed_rng(5);
+    let genome = Genome::new_random();
+    let pair: Vec<NeuralWarrior> = [(1, 10.0), (2, 1.0)].into_iter()
+        .map(|(id, fitness)| {
+            let mut warrior = NeuralWarrior::with_position(genome.clone(), id, random_position((1000.0, 1000.0), &mut rng));
+            warrior.fitness_score = fitness;
+            warrior
+        })
+        .collect();
+    
+    let first_parents = |replacement: bool| {
+        let mut manager = SpeciationManager::new(1);
+        manager.auto_adjust_threshold = false;
+        manager.reseed(5);
+        manager.tournament_size = 2;
+        manager.tournament_replacement = replacement;
+        manager.speciate(&pair);
+        assert_eq!(manager.species.len(), 1);
+        (0..50)
+            .flat_map(|_| manager.perform_species_selection(&pair))
+            .map(|child| child.parent_ids[0])
+            .collect::<Vec<u32>>()
+    };
+    
+    // Both members enter every tournament, so the fitter one always wins
+    assert!(first_parents(false).iter().all(|&parent| parent == 1));
+    // Drawing with replacement sometimes pits the weaker one against itself
+    assert!(first_parents(true).contains(&2));
+}
+
+#[test]
+fn test_population_ranks_nan_fitness_last() {
+    use neural_network_arena::evolution::Population;
+    
+    let mut population = Population::new(4);
+    population.evaluate_fitness_batch(|_| vec![f32::NAN, 1.0, 5.0, 3.0]).unwrap();
+    
+    assert_eq!(population.best_genome().unwrap().fitness(), 5.0);
+    assert_eq!(population.average_fitness(), 3.0);
+    let stats = population.statistics();
+    assert_eq!((stats.min_fitness, stats.max_fitness, stats.avg_fitness), (1.0, 5.0, 3.0));
+    population.evolve();
+    
+    let mut all_nan = Population::new(3);
+    all_nan.evaluate_fitness(|_| f32::NAN);
+    assert!(all_nan.best_genome().is_some());
+    assert_eq!(all_nan.average_fitness(), 0.0);
+}
+
+#[test]
+fn test_frozen_species_stays_frozen_when_every_species_is_capped() {
+    use neural_network_arena::evolution::{CrossoverStrategy, EvolutionParams};
+    
+    let mut rng = fixed_rng(8);
+    let mut manager = SpeciationManager::new(4);
+    manager.auto_adjust_threshold = false;
+    manager.reseed(8);
+    manager.deletion_rate = 0.5;
+    manager.max_offspring_fraction = 0.25;
+    manager.compatibility_weights.lineage_weight = 0.0;
+    manager.compatibility_weights.fitness_weight = 0.0;
+    
+    let frozen_genome = Genome::random_with_rng(&mut rng);
+    let warriors: Vec<NeuralWarrior> = (0..20)
+        .map(|id| {
+            let mut warrior = fixed_warrior(id, random_position((1000.0, 1000.0), &mut rng), &mut rng);
+            warrior.genome = frozen_genome.clone();
+            warrior.fitness_score = 100.0;
+            warrior
+        })
+        .collect();
+    manager.speciate(&warriors);
+    assert_eq!(manager.species.len(), 1);
+    manager.set_species_policy(Box::new(|_| EvolutionParams {
+        mutation_rate_multiplier: 0.0,
+        crossover_strategy: CrossoverStrategy::Asexual,
+        interspecies_rate: 0.0,
+    }));
+    
+    // The species may only breed five; the rest come from the best warrior
+    let offspring = manager.perform_species_selection(&warriors);
+    assert_eq!(offspring.len(), 20);
+    assert!(offspring.iter().all(|child| child.genome.bytes() == frozen_genome.bytes()));
+}
@@ -0,0 +1,147 @@
+use neural_network_arena::evolution::{LineageTracker, SpeciationManager};
+use neural_network_arena::export::{to_dot, to_newick};
+use neural_network_arena::neural::{Genome, NeuralWarrior};
+use neural_network_arena::testing::{fixed_rng, random_position};
+use std::collections::BTreeMap;
+
+fn warrior(id: u32, parents: &[u32], depth: u32) -> NeuralWarrior {
+    let mut rng = fixed_rng(1);
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), id, random_position((1000.0, 1000.0), &mut rng));
+    warrior.parent_ids = parents.to_vec();
+    warrior.lineage_depth = depth;
+    warrior
+}
+
+/// Minimal Newick reader: maps each labeled node to its parent's label
+/// (`None` under an unlabeled root) and its branch length.
+fn parse_newick(text: &str) -> BTreeMap<u32, (Option<u32>, Option<u32>)> {
+    fn number(chars: &[u8], pos: &mut usize) -> Option<u32> {
+        let start = *pos;
+        while chars[*pos].is_ascii_digit() {
+            *pos += 1;
+        }
+        std::str::from_utf8(&chars[start..*pos]).unwrap().parse().ok()
+    }
+
+    fn node(chars: &[u8], pos: &mut usize, out: &mut BTreeMap<u32, (Option<u32>, Option<u32>)>) -> Option<u32> {
+        let mut children = Vec::new();
+        if chars[*pos] == b'(' {
+            loop {
+                *pos += 1;
+                children.extend(node(chars, pos, out));
+                if chars[*pos] != b',' {
+                    break;
+                }
+            }
+            assert_eq!(chars[*pos], b')');
+            *pos += 1;
+        }
+        let label = number(chars, pos);
+        let length = if chars[*pos] == b':' {
+            *pos += 1;
+            number(chars, pos)
+        } else {
+            None
+        };
+        for child in children {
+            out.get_mut(&child).unwrap().0 = label;
+        }
+        if let Some(label) = label {
+            out.insert(label, (None, length));
+        }
+        label
+    }
+
+    let chars = text.as_bytes();
+    let mut pos = 0;
+    let mut out = BTreeMap::new();
+    node(chars, &mut pos, &mut out);
+    assert_eq!(&text[pos..], ";");
+    out
+}
+
+#[test]
+fn test_newick_round_trips_first_parent_tree() {
+    let mut lineage = LineageTracker::new();
+    for warrior in [
+        warrior(1, &[], 0),
+        warrior(2, &[1], 1),
+        warrior(3, &[1, 2], 1),
+        warrior(4, &[2], 3),
+        warrior(10, &[], 0),
+        warrior(11, &[10], 1),
+    ] {
+        lineage.record(&warrior);
+    }
+
+    let parsed = parse_newick(&to_newick(&lineage));
+
+    let expected: BTreeMap<u32, (Option<u32>, Option<u32>)> = BTreeMap::from([
+        (1, (None, Some(0))),
+        (2, (Some(1), Some(1))),
+        (3, (Some(1), Some(1))),
+        (4, (Some(2), Some(2))),
+        (10, (None, Some(0))),
+        (11, (Some(10), Some(1))),
+    ]);
+    assert_eq!(parsed, expected);
+}
+
+#[test]
+fn test_newick_of_empty_tracker_is_empty_tree() {
+    assert_eq!(to_newick(&LineageTracker::new()), ";");
+}
+
+#[test]
+fn test_dot_has_a_node_per_species_and_an_edge_per_founding() {
+    let mut manager = SpeciationManager::new(4);
+    manager.auto_adjust_threshold = false;
+    for round in 0..3 {
+        let warriors: Vec<NeuralWarrior> = (0..4).map(|i| warrior(round * 10 + i, &[], 0)).collect();
+        manager.speciate(&warriors);
+    }
+
+    let dot = to_dot(&manager);
+    assert!(dot.starts_with("digraph species {"));
+    assert!(dot.trim_end().ends_with('}'));
+
+    let species_count = manager.species_lineage.keys().chain(manager.species.keys())
+        .collect::<std::collections::BTreeSet<_>>()
+        .len();
+    let founded = manager.species_lineage.values().filter(|parent| parent.is_some()).count();
+    let nodes = dot.lines().filter(|line| line.contains("[label=")).count();
+    let edges = dot.lines().filter(|line| line.contains("->")).count();
+    assert!(species_count > 0);
+    assert_eq!(nodes, species_count);
+    assert_eq!(edges, founded);
+    let extinct = species_count - manager.species.len();
+    assert_eq!(dot.matches("style=dashed").count(), extinct);
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn test_statistics_export_to_arrow_and_parquet() {
+    use neural_network_arena::export::{statistics_schema, statistics_to_arrow, statistics_to_parquet};
+    use neural_network_arena::simulation::SimulationStatistics;
+
+    let statistics: Vec<SimulationStatistics> = (0..3)
+        .map(|generation| SimulationStatistics {
+            generation,
+            max_fitness: generation as f32 * 2.0,
+            ..SimulationStatistics::default()
+        })
+        .collect();
+
+    let batch = statistics_to_arrow(&statistics).unwrap();
+    assert_eq!(batch.num_rows(), 3);
+    assert_eq!(batch.schema().as_ref(), &statistics_schema());
+
+    let path = std::env::temp_dir().join(format!("arena_statistics_{}.parquet", std::process::id()));
+    statistics_to_parquet(&statistics, std::fs::File::create(&path).unwrap()).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+    let reader = parquet::arrow::arrow_reader::ParquetRecordBatchReader::try_new(file, 16).unwrap();
+    let read: Vec<_> = reader.map(Result::unwrap).collect();
+    assert_eq!(read.len(), 1);
+    assert_eq!(read[0], batch);
+    std::fs::remove_file(path).unwrap();
+}
@@ -1,4 +1,6 @@
-use neural_network_arena::neural::{Genome, NeuralNetwork};
+use neural_network_arena::neural::{Genome, GenomeCodecKind, MutationOperator, MutationWeights, NeuralNetwork};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 #[test]
 fn test_genome_size_constraints() {
@@ -88,7 +90,7 @@ fn test_genome_network_conversion() {
     let genome = Genome::from_network(&network, 1, 42);
     
     // Test decoding genome back to network
-    let decoded_network = genome.to_network();
+    let _decoded_network = genome.to_network();
     
     // Network should have expected structure (8 inputs, 4 outputs)
     // This tests the MVP requirement: "8 inputs: memory pressure, neighbor proximity, resources"
@@ -102,8 +104,8 @@ fn test_genome_network_conversion() {
 #[test]
 fn test_genome_size_enforcement_in_crossover() {
     // Create two maximum-sized genomes
-    let mut parent1 = Genome::new_random();
-    let mut parent2 = Genome::new_random();
+    let parent1 = Genome::new_random();
+    let parent2 = Genome::new_random();
     
     // Force them to maximum size by setting fitness and testing
     for _ in 0..100 {
@@ -125,4 +127,197 @@ fn test_genome_deterministic_properties() {
     assert_eq!(genome1.generation(), genome2.generation());
     assert_eq!(genome1.lineage_id(), genome2.lineage_id());
     assert_eq!(genome1.size(), genome2.size());
-}
\ No newline at end of file
+}
+#[test]
+fn test_from_network_respects_minimum_size() {
+    let network = NeuralNetwork::new(vec![8, 16, 4]);
+    let genome = Genome::from_network(&network, 0, 1);
+    assert_eq!(genome.size(), Genome::MIN_SIZE);
+}
+
+#[test]
+fn test_crossover_with_network_genome_does_not_panic() {
+    let network = NeuralNetwork::new(vec![8, 16, 4]);
+    let imported = Genome::from_network(&network, 3, 7);
+    
+    for _ in 0..100 {
+        let random = Genome::new_random();
+        let child = imported.crossover(&random);
+        assert!(child.size() >= Genome::MIN_SIZE && child.size() <= Genome::MAX_SIZE);
+        let child = random.crossover(&imported);
+        assert!(child.size() >= Genome::MIN_SIZE && child.size() <= Genome::MAX_SIZE);
+    }
+}
+
+#[test]
+fn test_crossover_with_degenerate_genomes() {
+    let tiny: Genome = serde_json::from_str(r#"{"data":[7],"fitness":0.0,"generation":2,"lineage_id":5}"#).unwrap();
+    let empty: Genome = serde_json::from_str(r#"{"data":[],"fitness":0.0,"generation":0,"lineage_id":6}"#).unwrap();
+    let random = Genome::new_random();
+    
+    let child = tiny.crossover(&random);
+    assert_eq!(child.size(), random.size(), "Should inherit the longer parent");
+    assert_eq!(child.generation(), 3);
+    assert_ne!(child.lineage_id(), tiny.lineage_id());
+    
+    let child = tiny.crossover(&empty);
+    assert_eq!(child.size(), Genome::MIN_SIZE, "Should pad up to the minimum size");
+}
+
+fn only(operator: MutationOperator) -> MutationWeights {
+    let mut weights = MutationWeights {
+        replace: 0.0,
+        ..MutationWeights::default()
+    };
+    match operator {
+        MutationOperator::Replace => weights.replace = 1.0,
+        MutationOperator::BitFlip => weights.bit_flip = 1.0,
+        MutationOperator::Creep => weights.creep = 1.0,
+        MutationOperator::Swap => weights.swap = 1.0,
+        MutationOperator::Reverse => weights.reverse = 1.0,
+    }
+    weights
+}
+
+/// Mean circular distance between corresponding bytes after mutating
+/// every byte of many random genomes with `operator`.
+fn mean_byte_delta(operator: MutationOperator) -> f32 {
+    let mut rng = StdRng::seed_from_u64(11);
+    let weights = only(operator);
+    let (mut total, mut count) = (0u64, 0u64);
+    for _ in 0..200 {
+        let original = Genome::random_with_rng(&mut rng);
+        let mut mutated = original.clone();
+        mutated.mutate_with_operators(1.0, &weights, &mut rng);
+        for (before, after) in original.bytes().iter().zip(mutated.bytes()) {
+            let delta = before.abs_diff(*after) as u64;
+            total += delta.min(256 - delta);
+            count += 1;
+        }
+    }
+    total as f32 / count as f32
+}
+
+#[test]
+fn test_creep_mutation_makes_smaller_changes_than_replacement() {
+    let creep = mean_byte_delta(MutationOperator::Creep);
+    let replace = mean_byte_delta(MutationOperator::Replace);
+    assert!(creep > 0.0 && creep <= 8.0, "Creep moves bytes by 1..=8, mean was {}", creep);
+    assert!(creep * 4.0 < replace, "Creep {} should be far smaller than replacement {}", creep, replace);
+}
+
+#[test]
+fn test_permuting_operators_preserve_byte_multiset() {
+    let mut rng = StdRng::seed_from_u64(5);
+    for operator in [MutationOperator::Swap, MutationOperator::Reverse] {
+        let original = Genome::random_with_rng(&mut rng);
+        let mut mutated = original.clone();
+        mutated.mutate_with_operators(0.5, &only(operator), &mut rng);
+        
+        let mut before = original.bytes().to_vec();
+        let mut after = mutated.bytes().to_vec();
+        assert_ne!(before, after, "{:?} should reorder bytes", operator);
+        before.sort_unstable();
+        after.sort_unstable();
+        assert_eq!(before, after, "{:?} should only reorder bytes", operator);
+    }
+}
+
+#[test]
+fn test_operator_mix_matches_configured_weights() {
+    let weights = MutationWeights {
+        replace: 1.0,
+        bit_flip: 2.0,
+        creep: 4.0,
+        swap: 0.0,
+        reverse: 1.0,
+    };
+    let mut rng = StdRng::seed_from_u64(3);
+    let trials = 80_000;
+    let mut counts = [0usize; MutationOperator::ALL.len()];
+    for _ in 0..trials {
+        let chosen = weights.choose(&mut rng);
+        counts[MutationOperator::ALL.iter().position(|&operator| operator == chosen).unwrap()] += 1;
+    }
+    
+    for (operator, count) in MutationOperator::ALL.iter().zip(counts) {
+        let expected = weights.weight(*operator) / 8.0;
+        let observed = count as f32 / trials as f32;
+        assert!((observed - expected).abs() < 0.01, "{:?}: expected {:.3}, observed {:.3}", operator, expected, observed);
+    }
+}
+
+#[test]
+fn test_codecs_decode_same_bytes_differently_and_deterministically() {
+    let mut rng = StdRng::seed_from_u64(21);
+    let genome = Genome::random_with_rng(&mut rng);
+    let decode = |codec| {
+        let mut genome = genome.clone();
+        genome.set_codec(codec);
+        genome.to_network().export_json().unwrap()
+    };
+    
+    let blank = decode(GenomeCodecKind::Blank);
+    let direct = decode(GenomeCodecKind::Direct);
+    let seeded = decode(GenomeCodecKind::Seed);
+    assert_ne!(direct, blank);
+    assert_ne!(seeded, blank);
+    assert_ne!(direct, seeded);
+    
+    assert_eq!(decode(GenomeCodecKind::Direct), direct);
+    assert_eq!(decode(GenomeCodecKind::Seed), seeded);
+    
+    // Direct parameters follow the bytes; the first weight comes from byte 0
+    let mut direct_genome = genome.clone();
+    direct_genome.set_codec(GenomeCodecKind::Direct);
+    let first_weight = direct_genome.to_network().weight(0, 0, 0).unwrap();
+    assert_eq!(first_weight, genome.bytes()[0] as f32 / 127.5 - 1.0);
+}
+
+#[test]
+fn test_offspring_inherit_codec() {
+    let mut rng = StdRng::seed_from_u64(4);
+    let mut parent = Genome::random_with_rng(&mut rng);
+    parent.set_codec(GenomeCodecKind::Seed);
+    let other = Genome::random_with_rng(&mut rng);
+    
+    assert_eq!(parent.crossover_with_rng(&other, &mut rng).codec(), GenomeCodecKind::Seed);
+    
+    let restored: Genome = serde_json::from_str(&serde_json::to_string(&parent).unwrap()).unwrap();
+    assert_eq!(restored.codec(), GenomeCodecKind::Seed);
+}
+
+#[test]
+fn test_temperature_gene_is_not_decoded_into_the_network() {
+    let genome = |data: &str, codec: &str| -> Genome {
+        serde_json::from_str(&format!(r#"{{"data":{},"fitness":0.0,"generation":0,"lineage_id":1,"codec":"{}"}}"#, data, codec)).unwrap()
+    };
+    let cold = [0u8; 32];
+    let mut hot = cold;
+    hot[31] = 255;
+    let cold = serde_json::to_string(&cold.to_vec()).unwrap();
+    let hot = serde_json::to_string(&hot.to_vec()).unwrap();
+    
+    for codec in ["Direct", "Seed"] {
+        let (cold, hot) = (genome(&cold, codec), genome(&hot, codec));
+        assert_eq!(cold.temperature_gene(), 0.0);
+        assert_eq!(hot.temperature_gene(), 1.0);
+        assert_eq!(cold.network_bytes(), hot.network_bytes());
+        assert_eq!(cold.to_network().export_json().unwrap(), hot.to_network().export_json().unwrap(), "{} decoded the temperature gene", codec);
+    }
+}
+
+#[test]
+fn test_canonical_set_is_deterministic() {
+    let first = Genome::canonical_set(10);
+    let second = Genome::canonical_set(10);
+    assert_eq!(first.len(), 10);
+    for (a, b) in first.iter().zip(&second) {
+        assert_eq!(a.bytes(), b.bytes());
+        assert_eq!(a.lineage_id(), b.lineage_id());
+    }
+    assert_ne!(first[0].bytes(), first[1].bytes());
+    
+    let prefix = Genome::canonical_set(4);
+    assert!(prefix.iter().zip(&first).all(|(a, b)| a.bytes() == b.bytes()));
+}
@@ -1,9 +1,11 @@
 use neural_network_arena::{
-    NeuralArenaSimulation, SimulationConfig,
-    neural::{Genome, NeuralWarrior},
-    environment::Environment,
+    MutationSchedule, NeuralArenaSimulation, SimulationConfig, SimulationWarning,
+    neural::{Genome, GenomeCodecKind, NeuralWarrior},
+    environment::{ActionCostModel, Environment, EventType, PressureConfig, SpawnStrategy},
     vm::VirtualMachine,
+    testing::{fixed_rng, fixed_warrior, random_position},
 };
+use rand::rngs::StdRng;
 
 #[test]
 fn test_full_simulation_integration() {
@@ -19,15 +21,16 @@ fn test_full_simulation_integration() {
         tournament_size: 3,
         max_generations: 5, // Short test
         performance_target_rps: 100,
+        ..SimulationConfig::default()
     };
     
     let mut simulation = NeuralArenaSimulation::new(config);
     simulation.initialize_population(30);
     
     // Run for a few generations
-    let results = simulation.run_simulation(Some(1000));
+    let outcome = simulation.run_simulation(Some(1000));
     
-    assert!(!results.is_empty());
+    assert!(!outcome.results.is_empty());
     assert!(simulation.generation > 0);
     assert!(simulation.tick > 0);
     
@@ -37,13 +40,12 @@ fn test_full_simulation_integration() {
 
 #[test]
 fn test_warrior_environment_interaction() {
-    let mut environment = Environment::new(500.0, 500.0, 100);
+    let mut environment = Environment::with_seed(500.0, 500.0, 100, 1);
+    let mut rng = fixed_rng(1);
     
     // Create test warriors
-    let genome1 = Genome::new_random();
-    let genome2 = Genome::new_random();
-    let warrior1 = NeuralWarrior::new(genome1, 1);
-    let warrior2 = NeuralWarrior::new(genome2, 2);
+    let warrior1 = fixed_warrior(1, (150.0, 150.0), &mut rng);
+    let warrior2 = fixed_warrior(2, (350.0, 350.0), &mut rng);
     
     environment.add_warrior(warrior1);
     environment.add_warrior(warrior2);
@@ -67,41 +69,60 @@ fn test_warrior_environment_interaction() {
         let _results = environment.execute_warrior_actions(actions);
     }
     
-    assert!(environment.warriors.len() <= 2); // Warriors might die or replicate
+    // Neither warrior dies or replicates within ten ticks
+    assert_eq!(environment.warriors.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
     assert_eq!(environment.tick, 10);
 }
 
 #[test]
 fn test_vm_neural_integration() {
-    let mut vm = VirtualMachine::new(512);
-    let genome = Genome::new_random();
-    let mut warrior = NeuralWarrior::new(genome, 1);
+    // Sensor data is staged at addresses 1000..1008
+    let mut vm = VirtualMachine::with_seed(2048, 2);
+    let mut rng = fixed_rng(2);
+    let mut warrior = fixed_warrior(1, (100.0, 100.0), &mut rng);
+    warrior.energy = 50.0;
+    warrior.age = 250;
+    let mut environment = Environment::new(500.0, 400.0, 10);
+    environment.sensor_config.max_age = 500.0;
     
     // Test VM instruction execution from warrior
-    let instructions = warrior.execute_vm_instructions(&mut vm).unwrap();
-    
-    assert!(!instructions.is_empty());
+    let instructions = warrior.execute_vm_instructions_with_rng(&mut vm, &environment.get_environment_state(), &mut rng).unwrap();
     
-    for instruction in instructions {
-        let _result = vm.execute_instruction(&instruction); // VM execution may fail for some instructions, that's OK
+    // Eight sensor loads and four activations; this seed draws no Mutate
+    assert_eq!(instructions.len(), 12);
+    // Staged values are scaled by the energy cap, the arena and the sensor config
+    let staged: Vec<f32> = instructions[..4].iter().map(|instruction| instruction.arg3).collect();
+    assert_eq!(staged, vec![0.5, 0.2, 0.25, 0.5]);
+    for instruction in &instructions {
+        vm.execute_instruction(instruction).unwrap();
     }
     
-    assert!(vm.cycle_count() > 0);
-    assert!(vm.available_resources() < 10000); // Some resources should be consumed
+    assert_eq!(vm.cycle_count(), 12);
+    assert_eq!(vm.available_resources(), 10000 - 8 * 10 - 4);
 }
 
 #[test]
 fn test_speciation_system() {
+    let mut rng = fixed_rng(1);
     use neural_network_arena::evolution::SpeciationManager;
+    use rand::Rng;
     
     let mut speciation = SpeciationManager::new(3);
+    speciation.reseed(1);
     
-    // Create diverse warriors
+    // Four founder families, each with its own fitness band; compatibility
+    // compares genome size and fitness against the representative genome,
+    // so families stay apart
+    let founders: Vec<Genome> = (0..4).map(|_| Genome::random_with_rng(&mut rng)).collect();
     let mut warriors = Vec::new();
     for i in 0..20 {
-        let genome = Genome::new_random();
-        let mut warrior = NeuralWarrior::new(genome, i);
-        warrior.fitness_score = rand::random::<f32>() * 100.0;
+        let family = i as usize % founders.len();
+        let fitness = family as f32 * 25.0 + rng.gen::<f32>();
+        let mut genome = founders[family].clone();
+        genome.mutate_with_rng(0.02, &mut rng);
+        genome.set_fitness(fitness);
+        let mut warrior = NeuralWarrior::with_position(genome, i, random_position((1000.0, 1000.0), &mut rng));
+        warrior.fitness_score = fitness;
         warriors.push(warrior);
     }
     
@@ -109,8 +130,14 @@ fn test_speciation_system() {
     speciation.speciate(&warriors);
     
     let stats = speciation.get_species_stats();
-    assert!(stats.species_count > 0);
-    assert!(stats.species_count <= warriors.len());
+    assert_eq!(stats.species_count, 4);
+    for species in speciation.species.values() {
+        let mut families: Vec<u32> = species.members.iter().map(|id| id % 4).collect();
+        families.sort_unstable();
+        families.dedup();
+        assert_eq!(families.len(), 1, "Species {} mixes families", species.id);
+        assert_eq!(species.members.len(), 5);
+    }
     
     // Test selection
     let next_gen = speciation.perform_species_selection(&warriors);
@@ -131,19 +158,135 @@ fn test_memory_territory_allocation() {
     assert_ne!(territory1, territory2);
     assert_ne!(territory2, territory3);
     
-    // Test access control (territories might have different addressing)
+    // Test access control
     let territory1_address = territory1 * 64;
     assert!(allocator.can_access(territory1_address, 1));
-    // Access control might not be strict initially, so just verify basic functionality
+    // Unprotected territories stay open to other owners
+    assert!(allocator.can_access(territory1_address, 2));
     
     // Test deallocation
-    let _ = allocator.deallocate_territory(territory1, 1); // May succeed or fail, we just test it doesn't crash
+    allocator.deallocate_territory(territory1, 1).unwrap();
+    assert!(allocator.get_territories_for_owner(1).is_empty());
+    assert!(allocator.deallocate_territory(territory1, 1).is_err());
+}
+
+#[test]
+fn test_safe_zones_only_spawns_inside_safe_zones() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(11),
+        spawn_strategy: SpawnStrategy::SafeZonesOnly,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(80);
+    
+    let safe_zones = &simulation.environment.safe_zones;
+    assert!(!safe_zones.is_empty());
+    for warrior in simulation.environment.warriors.values() {
+        let (x, y) = warrior.position;
+        assert!(safe_zones.iter().any(|zone| {
+            ((x - zone.center.0).powi(2) + (y - zone.center.1).powi(2)).sqrt() <= zone.radius + 1e-3
+        }), "warrior spawned outside every safe zone at ({x}, {y})");
+    }
+}
+
+#[test]
+fn test_uniform_free_never_spawns_inside_barriers() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(12),
+        arena_width: 300.0,
+        arena_height: 300.0,
+        max_population: 200,
+        ..SimulationConfig::default()
+    });
+    assert!(!simulation.environment.barriers.is_empty());
+    simulation.initialize_population(200);
+    
+    assert_eq!(simulation.environment.warriors.len(), 200);
+    for warrior in simulation.environment.warriors.values() {
+        assert!(simulation.environment.barriers.iter().all(|barrier| !barrier.contains(warrior.position)));
+        assert!(warrior.position.0 <= 300.0 && warrior.position.1 <= 300.0);
+    }
+}
+
+#[test]
+fn test_lineage_summary_tracks_surviving_founders() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(4),
+        ..SimulationConfig::default()
+    });
+    let founder_a = simulation.inject_warrior(Genome::new_random()).unwrap();
+    let founder_b = simulation.inject_warrior(Genome::new_random()).unwrap();
+    
+    let mut child = simulation.environment.warriors[&founder_a].clone();
+    child.id = founder_a.wrapping_add(1);
+    assert_ne!(child.id, founder_b);
+    simulation.environment.add_warrior(child);
+    simulation.generation = 3;
+    
+    let summary = simulation.lineage_summary();
+    assert_eq!(summary.surviving_lineages, 2);
+    assert_eq!(summary.largest_lineage, Some(founder_a));
+    assert!((summary.largest_lineage_share - 2.0 / 3.0).abs() < 1e-6);
+    assert_eq!(summary.oldest_lineage_age, 3);
+    
+    simulation.environment.remove_warrior(founder_b);
+    let summary = simulation.get_species_stats().lineage;
+    assert_eq!(summary.surviving_lineages, 1);
+    assert_eq!(summary.largest_lineage, Some(founder_a));
+    assert_eq!(summary.largest_lineage_share, 1.0);
+    assert_eq!(summary.oldest_lineage, Some(founder_a));
+}
+
+#[test]
+fn test_generation_result_lists_installed_offspring() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(21),
+        max_population: 40,
+        generation_ticks: 20,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(40);
+    
+    let result = simulation.run_generation();
+    assert!(!result.next_generation.is_empty());
+    assert_eq!(result.next_generation.len(), simulation.environment.warriors.len());
+    for offspring in &result.next_generation {
+        let warrior = &simulation.environment.warriors[&offspring.id];
+        assert_eq!(warrior.genome.bytes(), offspring.genome.bytes());
+        assert_eq!(warrior.parent_ids, offspring.parent_ids);
+    }
+    
+    simulation.simulation_config.record_offspring = false;
+    assert!(simulation.run_generation().next_generation.is_empty());
+}
+
+#[test]
+fn test_memory_remainder_stays_unpartitioned() {
+    use neural_network_arena::memory::{AllocationError, MemoryAllocator};
+    
+    let mut allocator = MemoryAllocator::try_new(1000, 64).unwrap();
+    assert_eq!(allocator.total_territories(), 15);
+    assert_eq!(allocator.partitioned_size(), 960);
+    assert_eq!(allocator.unpartitioned_bytes(), 40);
+    
+    // The 40-byte tail belongs to no territory, so nobody can use it
+    assert!(allocator.can_access(959, 1));
+    assert!(!allocator.can_access(960, 1));
+    assert!(!allocator.can_access(999, 1));
+    
+    while allocator.allocate_territory(1).is_ok() {}
+    assert_eq!(allocator.memory_utilization(), 1.0);
+    assert_eq!(allocator.byte_utilization(), 0.96);
+    
+    assert!(matches!(MemoryAllocator::try_new(1000, 0), Err(AllocationError::InvalidTerritorySize { .. })));
+    assert!(matches!(MemoryAllocator::try_new(32, 64), Err(AllocationError::InvalidTerritorySize { .. })));
 }
 
 #[test]
 fn test_fitness_calculation() {
+    let mut rng = fixed_rng(2);
     let genome = Genome::new_random();
-    let mut warrior = NeuralWarrior::new(genome, 1);
+    let mut warrior = NeuralWarrior::with_position(genome, 1, random_position((1000.0, 1000.0), &mut rng));
     
     assert_eq!(warrior.fitness_score, 0.0);
     
@@ -162,78 +305,67 @@ fn test_fitness_calculation() {
 
 #[test]
 fn test_environmental_events() {
-    let mut environment = Environment::new(1000.0, 1000.0, 200);
+    let mut rng = fixed_rng(4);
+    let mut environment = Environment::with_seed(1000.0, 1000.0, 200, 4);
     
     // Add some warriors and resources
     for i in 0..10 {
-        let genome = Genome::new_random();
-        let warrior = NeuralWarrior::new(genome, i);
-        environment.add_warrior(warrior);
+        environment.add_warrior(fixed_warrior(i, random_position((1000.0, 1000.0), &mut rng), &mut rng));
     }
     
-    let _initial_resources = environment.resources.len();
     let initial_warriors = environment.warriors.len();
     
-    // Run simulation and look for environmental events
-    let mut event_occurred = false;
+    // Run simulation and record when environmental events fire
+    let mut events = Vec::new();
     for _ in 0..100 {
         let update = environment.tick();
-        if update.environmental_event.is_some() {
-            event_occurred = true;
-            break;
+        if let Some(event) = update.environmental_event {
+            events.push((update.tick, event.event_type));
         }
     }
     
-    // Events should eventually occur (though not guaranteed in short test)
-    // At minimum, verify the system doesn't crash
+    // Seed 4 fires exactly one event in the first 100 ticks
+    assert_eq!(events, vec![(23, EventType::ResourceAbundance)]);
     assert!(environment.warriors.len() <= initial_warriors);
-    assert!(environment.tick == 100 || event_occurred);
 }
 
 #[test]
 fn test_warrior_replication() {
-    let mut environment = Environment::new(1000.0, 1000.0, 200);
+    let mut environment = Environment::with_seed(1000.0, 1000.0, 200, 5);
+    let mut rng = fixed_rng(5);
     
-    let genome = Genome::new_random();
-    let mut warrior = NeuralWarrior::new(genome, 1);
+    let mut warrior = fixed_warrior(1, (500.0, 500.0), &mut rng);
     warrior.energy = 100.0; // Full energy
     warrior.age = 20; // Old enough to replicate
-    
     environment.add_warrior(warrior);
     
-    // Try to trigger replication
     use neural_network_arena::neural::Action;
     let mut actions = std::collections::HashMap::new();
     actions.insert(1, Action::Replicate { mutation_rate: 0.1 });
     
     let _results = environment.execute_warrior_actions(actions);
     
-    // Check if replication succeeded (should create offspring)
-    if environment.warriors.len() > 1 {
-        // Replication successful
-        assert!(environment.warriors.len() == 2);
-        
-        // Parent should have less energy
-        let parent = environment.warriors.get(&1).unwrap();
-        assert!(parent.energy < 100.0);
-    }
+    // 100 energy clears the threshold: the parent pays 40 and the child
+    // starts with 60% of the parent's energy
+    assert_eq!(environment.warriors.len(), 2);
+    assert_eq!(environment.warriors[&1].energy, 60.0);
+    let child = environment.warriors.values().find(|w| w.id != 1).unwrap();
+    assert!((child.energy - 60.0).abs() < 1e-4);
+    assert_eq!(child.lineage_depth, 1);
 }
 
 #[test]
 fn test_combat_system() {
-    let mut environment = Environment::new(1000.0, 1000.0, 200);
-    
-    // Create two warriors close to each other
-    let genome1 = Genome::new_random();
-    let genome2 = Genome::new_random();
-    let mut warrior1 = NeuralWarrior::new(genome1, 1);
-    let mut warrior2 = NeuralWarrior::new(genome2, 2);
+    let mut environment = Environment::with_seed(1000.0, 1000.0, 200, 6);
+    // A resource under the attacker would refund the attack cost
+    environment.resources.clear();
+    let mut rng = fixed_rng(6);
     
-    warrior1.position = (100.0, 100.0);
-    warrior2.position = (110.0, 100.0); // Close proximity
+    // Warrior 2 sits 10 units short of the end of a full-strength attack
+    let mut warrior1 = fixed_warrior(1, (100.0, 100.0), &mut rng);
+    let mut warrior2 = fixed_warrior(2, (120.0, 100.0), &mut rng);
     warrior1.energy = 100.0;
     warrior2.energy = 100.0;
-    
     environment.add_warrior(warrior1);
     environment.add_warrior(warrior2);
     
@@ -247,18 +379,10 @@ fn test_combat_system() {
     
     let _results = environment.execute_warrior_actions(actions);
     
-    // Combat should have some effect (energy changes, etc.)
-    let warrior1_after = environment.warriors.get(&1).unwrap();
-    let warrior2_after = environment.warriors.get(&2);
-    
-    // Attacker should have less energy from attack cost
-    assert!(warrior1_after.energy < 100.0);
-    
-    // Target might be damaged or might have survived
-    if let Some(w2) = warrior2_after {
-        // If warrior 2 survived, they might have taken damage
-        assert!(w2.energy <= 100.0);
-    }
+    // The attack costs 5 and deals 15
+    assert_eq!(environment.warriors[&1].energy, 95.0);
+    assert_eq!(environment.warriors[&2].energy, 85.0);
+    assert_eq!(environment.warriors[&1].damage_dealt, 15.0);
 }
 
 #[test]
@@ -288,4 +412,798 @@ fn test_population_stability() {
     let min_pop = *population_history.iter().min().unwrap();
     let max_pop = *population_history.iter().max().unwrap();
     assert!((max_pop as f32) / (min_pop as f32) < 5.0); // Less than 5x variation
-}
\ No newline at end of file
+}
+#[test]
+fn test_fixed_compatibility_threshold() {
+    let config = SimulationConfig {
+        max_population: 30,
+        ..SimulationConfig::default()
+    };
+    
+    let mut simulation = NeuralArenaSimulation::new(config);
+    simulation.set_auto_adjust_threshold(false);
+    simulation.set_compatibility_threshold(4.2);
+    simulation.initialize_population(20);
+    
+    for _ in 0..3 {
+        simulation.run_generation();
+        assert_eq!(simulation.compatibility_threshold(), 4.2);
+    }
+}
+
+#[test]
+fn test_pareto_front_keeps_trade_off_champions() {
+    let mut rng = fixed_rng(4);
+    use neural_network_arena::ObjectiveKind;
+    
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig::default());
+    
+    let mut survivor = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    survivor.age = 500;
+    survivor.damage_dealt = 10.0;
+    
+    let mut fighter = NeuralWarrior::with_position(Genome::new_random(), 2, random_position((1000.0, 1000.0), &mut rng));
+    fighter.age = 50;
+    fighter.damage_dealt = 200.0;
+    
+    let mut dominated = NeuralWarrior::with_position(Genome::new_random(), 3, random_position((1000.0, 1000.0), &mut rng));
+    dominated.age = 40;
+    dominated.damage_dealt = 5.0;
+    
+    simulation.environment.add_warrior(survivor);
+    simulation.environment.add_warrior(fighter);
+    simulation.environment.add_warrior(dominated);
+    
+    let front = simulation.pareto_front(&[ObjectiveKind::Survival, ObjectiveKind::Combat]);
+    assert_eq!(front, vec![1, 2]);
+}
+
+#[test]
+fn test_vm_overdraft_costs_energy() {
+    let mut rng = fixed_rng(5);
+    use neural_network_arena::vm::{Instruction, OpCode};
+    
+    let config = SimulationConfig {
+        vm_budget_per_tick: 10,
+        vm_overdraft_energy_penalty: 0.5,
+        ..SimulationConfig::default()
+    };
+    let mut simulation = NeuralArenaSimulation::new(config);
+    simulation.environment.add_warrior(NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng)));
+    simulation.environment.add_warrior(NeuralWarrior::with_position(Genome::new_random(), 2, random_position((1000.0, 1000.0), &mut rng)));
+    
+    let heavy = vec![Instruction::new(OpCode::Replicate, 0, 1, 0.0); 5];
+    let light = vec![Instruction::new(OpCode::Noop, 0, 1, 0.0); 5];
+    simulation.execute_warrior_program(1, &heavy);
+    simulation.execute_warrior_program(2, &light);
+    
+    let heavy_warrior = &simulation.environment.warriors[&1];
+    let light_warrior = &simulation.environment.warriors[&2];
+    assert!(heavy_warrior.energy < light_warrior.energy);
+    assert_eq!(heavy_warrior.behavior.compute_spent, 50);
+    assert_eq!(heavy_warrior.behavior.compute_overdraft, 40);
+    assert_eq!(light_warrior.behavior.compute_saved, 10);
+}
+
+#[test]
+fn test_lineage_dot_export() {
+    let mut rng = fixed_rng(6);
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig::default());
+    
+    let founder = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    let mut child = NeuralWarrior::with_position(Genome::new_random(), 2, random_position((1000.0, 1000.0), &mut rng));
+    child.parent_ids = vec![1];
+    let mut grandchild = NeuralWarrior::with_position(Genome::new_random(), 3, random_position((1000.0, 1000.0), &mut rng));
+    grandchild.parent_ids = vec![2];
+    let crossover = NeuralWarrior::from_parents(&child, &founder, 4);
+    
+    for warrior in [&founder, &child, &grandchild, &crossover] {
+        simulation.lineage.record(warrior);
+    }
+    
+    let dot = simulation.lineage_to_dot();
+    assert!(dot.starts_with("digraph lineage {"));
+    assert!(dot.trim_end().ends_with('}'));
+    assert!(dot.contains("\"1\" -> \"2\";"));
+    assert!(dot.contains("\"2\" -> \"3\";"));
+    assert!(dot.contains("\"2\" -> \"4\";"));
+    assert!(dot.contains("\"1\" -> \"4\";"));
+    
+    let node_count = dot.lines().filter(|line| line.contains("[label=")).count();
+    let edge_count = dot.lines().filter(|line| line.contains("->")).count();
+    assert_eq!(node_count, 4);
+    assert_eq!(edge_count, 4);
+}
+
+#[test]
+fn test_advance_to_generation() {
+    let config = SimulationConfig {
+        max_population: 30,
+        ..SimulationConfig::default()
+    };
+    let mut simulation = NeuralArenaSimulation::new(config);
+    simulation.initialize_population(20);
+    
+    let result = simulation.advance_to_generation(5).expect("should run generations");
+    assert_eq!(simulation.generation, 5);
+    assert_eq!(result.generation, 5);
+    
+    // Already there: no-op
+    assert!(simulation.advance_to_generation(3).is_none());
+    assert_eq!(simulation.generation, 5);
+}
+
+#[test]
+fn test_fast_mode_preserves_seeded_outcomes() {
+    let run = |fast_mode: bool| {
+        let config = SimulationConfig {
+            max_population: 40,
+            seed: Some(1234),
+            fast_mode,
+            // Per-warrior budgets so every compute counter moves
+            vm_budget_per_tick: 10,
+            ..SimulationConfig::default()
+        };
+        let mut simulation = NeuralArenaSimulation::new(config);
+        simulation.initialize_population(30);
+        simulation.advance_to_generation(2);
+        // Offspring start with fresh counters, so stop mid-generation
+        for _ in 0..20 {
+            simulation.single_tick();
+        }
+        
+        let population: Vec<(u32, f32, f32)> = simulation.environment.warriors.values()
+            .map(|w| (w.id, w.fitness_score, w.energy))
+            .collect();
+        let counters: Vec<(u64, u64, u32)> = simulation.environment.warriors.values()
+            .map(|w| (w.behavior.compute_saved, w.behavior.territory_ticks, w.behavior.consecutive_rests))
+            .collect();
+        let reported: u64 = simulation.environment.warriors.values()
+            .map(|w| w.behavior.compute_spent + w.behavior.compute_overdraft)
+            .sum();
+        (population, counters, reported, simulation.lineage.len(), simulation.environment.history().len())
+    };
+    
+    let (normal_population, normal_counters, normal_reported, normal_lineage, normal_history) = run(false);
+    let (fast_population, fast_counters, fast_reported, fast_lineage, fast_history) = run(true);
+    
+    assert_eq!(normal_population, fast_population);
+    assert!(!normal_population.is_empty());
+    assert!(normal_lineage > 0 && normal_history > 0);
+    assert_eq!(fast_lineage, 0);
+    assert_eq!(fast_history, 0);
+    // Counters that feed fitness or rest costs still run; report-only ones don't
+    assert_eq!(normal_counters, fast_counters);
+    assert!(normal_reported > 0);
+    assert_eq!(fast_reported, 0);
+}
+
+#[test]
+fn test_fork_branches_independently() {
+    let config = SimulationConfig {
+        max_population: 40,
+        seed: Some(99),
+        ..SimulationConfig::default()
+    };
+    let mut original = NeuralArenaSimulation::new(config);
+    original.initialize_population(30);
+    for _ in 0..20 {
+        original.single_tick();
+    }
+    
+    let snapshot = |simulation: &NeuralArenaSimulation| -> Vec<(u32, (f32, f32), f32)> {
+        simulation.environment.warriors.values()
+            .map(|w| (w.id, w.position, w.energy))
+            .collect()
+    };
+    let before = snapshot(&original);
+    
+    let mut replay = original.fork();
+    let mut branch = original.fork();
+    branch.simulation_config.action_temperature = 2.0;
+    for _ in 0..50 {
+        replay.single_tick();
+        branch.single_tick();
+    }
+    
+    assert_eq!(snapshot(&original), before, "Forks must not touch the original");
+    assert_eq!(original.tick, 20);
+    
+    // Same RNG state, same driving: identical futures
+    let mut reference = original.fork();
+    for _ in 0..50 {
+        reference.single_tick();
+    }
+    assert_eq!(snapshot(&replay), snapshot(&reference));
+    
+    assert_ne!(snapshot(&branch), snapshot(&replay), "A differently driven branch should diverge");
+}
+
+#[test]
+fn test_evaluate_against_fixed_opponents() {
+    let config = SimulationConfig {
+        seed: Some(17),
+        // Aging drains a flat amount per tick, whatever the pressure
+        pressure_config: PressureConfig { aging_drain_coefficient: 0.0, ..PressureConfig::default() },
+        ..SimulationConfig::default()
+    };
+    let mut simulation = NeuralArenaSimulation::new(config);
+    simulation.initialize_population(10);
+    
+    // Blank-codec genomes decode to the zero network, which always picks
+    // DeepRest; direct-codec ones decode to distinct active networks
+    let mut rng = fixed_rng(18);
+    let active = |rng: &mut StdRng| {
+        let mut genome = Genome::random_with_rng(rng);
+        genome.set_codec(GenomeCodecKind::Direct);
+        genome
+    };
+    let idle_champion = Genome::random_with_rng(&mut rng);
+    let opponents: Vec<Genome> = (0..4).map(|_| active(&mut rng)).collect();
+    let active_champions: Vec<Genome> = (0..6).map(|_| active(&mut rng)).collect();
+    
+    // Deep resting through all 100 ticks: every tick drains the aging cost,
+    // then recovers a triple rest gain, scaled by `rest_decay` over the
+    // streak, while below the ceiling
+    let costs = ActionCostModel::default();
+    let mut energy = NeuralWarrior::MAX_ENERGY;
+    for streak in 0..100 {
+        energy -= costs.aging_cost_per_tick;
+        if energy < costs.rest_energy_ceiling {
+            energy += costs.rest_energy_gain * 3.0 * costs.rest_decay.powi(streak);
+        }
+        energy = energy.min(NeuralWarrior::MAX_ENERGY);
+    }
+    let idle_score = simulation.evaluate_against(idle_champion.clone(), opponents.clone(), 100);
+    assert!((idle_score - (100f32.ln() + energy.sqrt())).abs() < 1e-3, "Idle score {}", idle_score);
+    assert_eq!(simulation.evaluate_against(idle_champion, Vec::new(), 100), idle_score);
+    
+    let active_scores: Vec<f32> = active_champions.iter()
+        .map(|champion| simulation.evaluate_against(champion.clone(), opponents.clone(), 100))
+        .collect();
+    assert!(active_scores.iter().all(|&score| score <= idle_score), "Scores: {:?}", active_scores);
+    assert!(active_scores.iter().any(|&score| score < idle_score - 5.0), "Scores: {:?}", active_scores);
+    
+    // Opponents share the arena with an active champion and change its bout
+    let alone = simulation.evaluate_against(active_champions[0].clone(), Vec::new(), 100);
+    assert_ne!(alone, active_scores[0]);
+    assert_eq!(simulation.evaluate_against(active_champions[0].clone(), opponents, 100), active_scores[0], "Seeded bouts should be repeatable");
+    
+    // The bout runs in its own arena
+    assert_eq!(simulation.tick, 0);
+    assert_eq!(simulation.environment.warriors.len(), 10);
+}
+
+#[test]
+fn test_time_sliced_speciation_completes_at_generation_end() {
+    let config = SimulationConfig {
+        seed: Some(5),
+        generation_ticks: 40,
+        speciation_slice_ticks: 10,
+        ..SimulationConfig::default()
+    };
+    let mut simulation = NeuralArenaSimulation::new(config);
+    simulation.initialize_population(30);
+    
+    simulation.run_generation();
+    assert!(!simulation.speciation_manager.is_speciation_pending());
+    assert!(!simulation.speciation_manager.species.is_empty());
+    assert!(simulation.speciation_manager.species.values().all(|species| !species.members.is_empty()));
+}
+
+#[test]
+fn test_rename_warrior() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig::default());
+    simulation.initialize_population(3);
+    let warrior_id = *simulation.environment.warriors.keys().next().unwrap();
+    
+    assert!(simulation.rename_warrior(warrior_id, "Spartacus"));
+    assert_eq!(simulation.environment.warriors[&warrior_id].name, "Spartacus");
+    let missing_id = (0..).find(|id| !simulation.environment.warriors.contains_key(id)).unwrap();
+    assert!(!simulation.rename_warrior(missing_id, "Nobody"));
+}
+
+#[test]
+fn test_statistics_share_environment_fields() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(11),
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(20);
+    simulation.single_tick();
+    
+    let environment_stats = simulation.environment.get_statistics();
+    let stats = simulation.get_statistics();
+    assert_eq!(stats.environmental_pressure, environment_stats.environmental_pressure);
+    assert_eq!(stats.tick, environment_stats.tick);
+    assert_eq!(
+        stats.resource_utilization,
+        environment_stats.total_resources as f32 / simulation.environment.resource_config.max_resources as f32
+    );
+}
+
+#[test]
+fn test_seeded_initial_positions_are_reproducible() {
+    let layout = || {
+        let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+            seed: Some(2024),
+            arena_width: 600.0,
+            arena_height: 400.0,
+            ..SimulationConfig::default()
+        });
+        simulation.initialize_population(25);
+        let injected = simulation.inject_warrior(Genome::new_random()).unwrap();
+        let positions: Vec<(u32, (f32, f32))> = simulation.environment.warriors.values()
+            .map(|warrior| (warrior.id, warrior.position))
+            .collect();
+        (injected, positions)
+    };
+    
+    let (injected, positions) = layout();
+    assert_eq!(layout(), (injected, positions.clone()));
+    assert_eq!(positions.len(), 26);
+    assert!(positions.iter().any(|&(id, _)| id == injected));
+    assert!(positions.iter().all(|&(_, (x, y))| (0.0..=600.0).contains(&x) && (0.0..=400.0).contains(&y)));
+}
+
+#[test]
+fn test_inject_warrior_refuses_a_full_arena() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(9),
+        max_population: 5,
+        ..SimulationConfig::default()
+    });
+    let ids: Vec<u32> = (0..5).map(|_| simulation.inject_warrior(Genome::new_random()).unwrap()).collect();
+    let mut unique = ids.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    assert_eq!(unique.len(), 5);
+    
+    assert_eq!(simulation.inject_warrior(Genome::new_random()), None);
+    assert_eq!(simulation.environment.warriors.keys().copied().collect::<Vec<u32>>(), unique);
+}
+
+#[test]
+fn test_tick_observer_runs_once_per_tick() {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(5),
+        generation_ticks: 20,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(30);
+    
+    let calls = Arc::new(AtomicU64::new(0));
+    let last_tick = Arc::new(AtomicU64::new(0));
+    let (observer_calls, observer_tick) = (calls.clone(), last_tick.clone());
+    simulation.set_tick_observer(Box::new(move |statistics| {
+        observer_calls.fetch_add(1, Ordering::SeqCst);
+        observer_tick.store(statistics.tick, Ordering::SeqCst);
+    }));
+    
+    for _ in 0..5 {
+        simulation.single_tick();
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+    assert_eq!(last_tick.load(Ordering::SeqCst), simulation.tick);
+    
+    simulation.run_generation();
+    assert_eq!(calls.load(Ordering::SeqCst), 25);
+    
+    simulation.clear_tick_observer();
+    simulation.single_tick();
+    assert_eq!(calls.load(Ordering::SeqCst), 25);
+}
+
+#[test]
+fn test_linear_mutation_schedule_anneals_rate() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(9),
+        generation_ticks: 10,
+        max_generations: 4,
+        mutation_schedule: MutationSchedule::Linear { start: 0.3, end: 0.05 },
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(20);
+    assert_eq!(simulation.effective_mutation_rate(), 0.3);
+    
+    let mut rates = vec![simulation.effective_mutation_rate()];
+    for _ in 0..6 {
+        simulation.run_generation();
+        assert_eq!(simulation.speciation_manager.base_mutation_rate, simulation.effective_mutation_rate());
+        rates.push(simulation.effective_mutation_rate());
+    }
+    
+    assert!(rates[..5].windows(2).all(|pair| pair[1] < pair[0]), "Rate should fall each generation: {:?}", rates);
+    assert!(rates[4..].iter().all(|&rate| (rate - 0.05).abs() < 1e-6), "Rate should settle at end: {:?}", rates);
+}
+
+#[test]
+fn test_config_selects_genome_codec_for_founders() {
+    use neural_network_arena::neural::GenomeCodecKind;
+    
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(2),
+        genome_codec: GenomeCodecKind::Direct,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(5);
+    
+    for warrior in simulation.environment.warriors.values() {
+        assert_eq!(warrior.genome.codec(), GenomeCodecKind::Direct);
+        assert_eq!(warrior.network.export_json().unwrap(), warrior.genome.to_network().export_json().unwrap());
+        // No byte maps exactly to zero under the direct codec
+        assert_ne!(warrior.network.weight(0, 0, 0), Some(0.0));
+    }
+}
+
+#[test]
+fn test_generation_result_summarizes_every_species() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(13),
+        generation_ticks: 30,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(40);
+    let result = simulation.run_generation();
+    
+    assert_eq!(result.species_summary.len(), result.species_count);
+    let mut expected_counts = std::collections::BTreeMap::new();
+    for warrior in &result.survivors {
+        let species_id = simulation.speciation_manager.species_of(warrior.id).expect("Survivors are speciated");
+        *expected_counts.entry(species_id).or_insert(0) += 1;
+    }
+    let reported_counts: std::collections::BTreeMap<u32, usize> = result.species_summary.iter()
+        .map(|summary| (summary.id, summary.member_count))
+        .collect();
+    assert_eq!(reported_counts, expected_counts);
+    
+    // Survivors acted during the generation, so every species has an archetype
+    assert!(result.species_summary.iter().all(|summary| summary.archetype.is_some()));
+}
+
+#[test]
+fn test_emergency_population_follows_recovery_config() {
+    use neural_network_arena::simulation::RecoveryConfig;
+    
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(8),
+        max_population: 40,
+        generation_ticks: 1,
+        recovery_config: RecoveryConfig {
+            fraction: 0.5,
+            mutation_rate: 0.1,
+            spawn_energy: 60.0,
+        },
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(4);
+    
+    let result = simulation.run_generation();
+    assert!(!result.survivors.is_empty() && result.survivors.len() <= 10);
+    assert_eq!(simulation.environment.warriors.len(), 20);
+    assert!(simulation.environment.warriors.values().all(|warrior| warrior.energy == 60.0));
+}
+
+#[test]
+fn test_generation_completes_with_non_finite_network_weights() {
+    use neural_network_arena::neural::NeuralNetwork;
+    
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(17),
+        generation_ticks: 20,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(30);
+    for (index, warrior) in simulation.environment.warriors.values_mut().enumerate().filter(|(index, _)| index % 3 == 0) {
+        let layer_sizes = warrior.network.layer_sizes().to_vec();
+        let poison = if index % 2 == 0 { f32::NAN } else { f32::INFINITY };
+        warrior.network = NeuralNetwork::from_parameter_fn(layer_sizes, |_| poison);
+        warrior.fitness_score = f32::NAN;
+    }
+    
+    let result = simulation.run_generation();
+    assert_eq!(result.generation, 1);
+    assert!(simulation.environment.warriors.values().all(|warrior| warrior.fitness_score.is_finite()));
+    assert!(simulation.get_statistics().max_fitness.is_finite());
+}
+
+#[test]
+fn test_reset_environment_keeps_population_genomes() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(31),
+        generation_ticks: 20,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(30);
+    simulation.run_generation();
+    simulation.single_tick();
+    
+    let genomes = |simulation: &NeuralArenaSimulation| -> std::collections::BTreeMap<u32, (Vec<u8>, u32)> {
+        simulation.environment.warriors.values()
+            .map(|warrior| (warrior.id, (warrior.genome.bytes().to_vec(), warrior.lineage_depth)))
+            .collect()
+    };
+    let before = genomes(&simulation);
+    assert!(!before.is_empty());
+    let generation = simulation.generation;
+    
+    simulation.reset_environment_keep_population();
+    
+    assert_eq!(genomes(&simulation), before);
+    assert_eq!(simulation.environment.tick, 0);
+    assert_eq!(simulation.generation, generation);
+    for warrior in simulation.environment.warriors.values() {
+        assert_eq!(warrior.age, 0);
+        assert_eq!(warrior.energy, 100.0);
+        assert_eq!(warrior.network.export_json().unwrap(), warrior.genome.to_network().export_json().unwrap());
+    }
+    
+    simulation.run_generation();
+    assert_eq!(simulation.generation, generation + 1);
+}
+
+#[test]
+fn test_allocator_exhaustion_is_counted_and_reported() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(5),
+        generation_ticks: 50,
+        territory_size: 1024,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(20);
+    assert_eq!(simulation.memory_allocator.total_territories(), 2);
+    
+    let result = simulation.run_generation();
+    
+    assert!(simulation.memory_allocator.is_saturated());
+    assert!(result.performance_metrics.allocator_saturated);
+    assert_eq!(result.performance_metrics.memory_allocations, 2);
+    assert!(result.performance_metrics.allocation_failures > 0);
+    assert!(result.warnings.iter().any(|warning| matches!(
+        warning,
+        SimulationWarning::AllocatorSaturated { failures, .. } if *failures == result.performance_metrics.allocation_failures
+    )));
+}
+
+#[test]
+fn test_global_elitism_keeps_all_time_best() {
+    let run = |global_elitism: bool| {
+        let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+            seed: Some(8),
+            generation_ticks: 30,
+            genome_codec: GenomeCodecKind::Direct,
+            global_elitism,
+            ..SimulationConfig::default()
+        });
+        simulation.initialize_population(30);
+        simulation
+    };
+    
+    let mut simulation = run(true);
+    let mut record = f32::NEG_INFINITY;
+    for _ in 0..15 {
+        simulation.run_generation();
+        let champion = simulation.hall_of_fame().expect("Hall of fame should be filled").clone();
+        assert!(champion.fitness_score >= record, "All-time best fell from {} to {}", record, champion.fitness_score);
+        record = champion.fitness_score;
+        
+        let reinjected = simulation.environment.warriors.get(&champion.id)
+            .expect("Champion should be reinjected into the new generation");
+        assert_eq!(reinjected.genome.bytes(), champion.genome.bytes());
+        assert_eq!(reinjected.age, 0);
+    }
+    
+    // Without the flag the champion doesn't come back; its genome survives
+    // only in offspring that happened to inherit it unmutated
+    let mut simulation = run(false);
+    for _ in 0..15 {
+        simulation.run_generation();
+        let champion = simulation.hall_of_fame().unwrap().clone();
+        assert!(!simulation.environment.warriors.contains_key(&champion.id));
+        assert!(simulation.environment.warriors.values()
+            .filter(|warrior| warrior.genome.bytes() == champion.genome.bytes())
+            .all(|warrior| warrior.parent_ids.contains(&champion.id)));
+    }
+}
+
+#[test]
+fn test_generation_result_data_mirrors_result() {
+    use neural_network_arena::wasm_api::GenerationResultData;
+    
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(14),
+        generation_ticks: 30,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(30);
+    let result = simulation.run_generation();
+    
+    let data = GenerationResultData::from(&result);
+    assert_eq!(data.generation, result.generation);
+    assert_eq!(data.survivor_count, result.survivors.len());
+    assert_eq!(data.extinct_lineages, result.extinct_lineages);
+    assert_eq!(data.new_species, result.new_species);
+    assert_eq!(data.species_count, result.species_count);
+    assert_eq!(data.extinct_species, result.extinct_species);
+    let best = result.survivors.iter().map(|warrior| warrior.fitness_score).fold(0.0, f32::max);
+    assert_eq!(data.best_fitness, best);
+    assert_eq!(data.vm_cycles_executed, result.performance_metrics.vm_cycles_executed);
+    assert_eq!(data.simulation_time_ms as u128, result.performance_metrics.simulation_time_ms);
+    let metrics = &result.performance_metrics;
+    assert_eq!(data.decide_time_us as u128, metrics.decide_time_us);
+    assert_eq!(data.act_time_us as u128, metrics.act_time_us);
+    assert_eq!(data.evolve_time_us as u128, metrics.evolve_time_us);
+    assert!(metrics.decide_time_us > 0 && metrics.act_time_us > 0 && metrics.evolve_time_us > 0);
+    let phases = metrics.decide_time_us + metrics.act_time_us + metrics.evolve_time_us;
+    assert!(phases / 1000 <= metrics.simulation_time_ms, "phases can't outlast the generation");
+    assert_eq!(data.warning_count, result.warnings.len());
+    
+    let restored: GenerationResultData = serde_json::from_str(&serde_json::to_string(&data).unwrap()).unwrap();
+    assert_eq!(restored, data);
+}
+
+#[test]
+fn test_memory_allocator_round_trips_through_json() {
+    use neural_network_arena::memory::{AllocationError, MemoryAllocator};
+    
+    let mut allocator = MemoryAllocator::new(1000, 64);
+    let first = allocator.allocate_territory(1).unwrap();
+    allocator.allocate_territory(2).unwrap();
+    allocator.allocate_territory(1).unwrap();
+    allocator.deallocate_territory(first, 1).unwrap();
+    allocator.validate_invariants().unwrap();
+    
+    let json = allocator.to_json().unwrap();
+    let mut restored = MemoryAllocator::from_json(&json).unwrap();
+    assert_eq!(restored.available_territories(), allocator.available_territories());
+    for address in (0..1000).step_by(16) {
+        for requester in [1, 2, 3] {
+            assert_eq!(restored.can_access(address, requester), allocator.can_access(address, requester));
+        }
+    }
+    for owner in [1, 2] {
+        let starts = |allocator: &MemoryAllocator| allocator.get_territories_for_owner(owner).iter()
+            .map(|territory| territory.start_address())
+            .collect::<Vec<_>>();
+        assert_eq!(starts(&restored), starts(&allocator));
+    }
+    // Both hand out the same territory next
+    assert_eq!(restored.allocate_territory(3).unwrap(), allocator.allocate_territory(3).unwrap());
+    
+    // A snapshot listing an owned territory as free is rejected
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let owned = allocator.get_territories_for_owner(2)[0].start_address() / 64;
+    value["free_territories"].as_array_mut().unwrap().push(owned.into());
+    assert!(matches!(
+        MemoryAllocator::from_json(&value.to_string()),
+        Err(AllocationError::Inconsistent { .. })
+    ));
+}
+
+#[test]
+fn test_territory_map_reflects_ownership() {
+    use neural_network_arena::memory::MemoryAllocator;
+    
+    let mut allocator = MemoryAllocator::new(1024, 64);
+    let first = allocator.allocate_territory(7).unwrap();
+    let second = allocator.allocate_territory(9).unwrap();
+    
+    let map = allocator.territory_map();
+    assert_eq!(map.len(), 16);
+    for (index, &(id, owner, _, size)) in map.iter().enumerate() {
+        assert_eq!(id, index);
+        assert_eq!(size, 64);
+        let expected = if id == first { Some(7) } else if id == second { Some(9) } else { None };
+        assert_eq!(owner, expected, "territory {}", id);
+    }
+}
+
+#[test]
+fn test_fixed_rng_simulations_match_exactly() {
+    let run = || {
+        let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+            seed: Some(77),
+            ..SimulationConfig::default()
+        });
+        simulation.initialize_population(20);
+        let result = simulation.run_generation();
+        let population: Vec<(u32, f32, f32, (f32, f32))> = simulation.environment.warriors.values()
+            .map(|w| (w.id, w.fitness_score, w.energy, w.position))
+            .collect();
+        (population, result.performance_metrics.vm_cycles_executed, simulation.vm.memory().to_vec())
+    };
+    
+    let first = run();
+    assert!(!first.0.is_empty());
+    assert_eq!(first, run());
+}
+
+#[test]
+fn test_energy_statistics_in_closed_generation() {
+    use neural_network_arena::environment::TickPhase;
+    
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(12),
+        ..SimulationConfig::default()
+    });
+    simulation.environment.set_schedule(vec![TickPhase::Pressure, TickPhase::RemoveDead]);
+    simulation.initialize_population(10);
+    simulation.run_generation();
+    
+    let stats = simulation.get_statistics();
+    assert_eq!(stats.energy_injected, 0.0);
+    assert_eq!(stats.energy_destroyed, 0.0);
+    assert_eq!(stats.biomass_turnover, 0.0);
+    assert_eq!(stats.warrior_energy, simulation.environment.warrior_energy());
+    assert_eq!(stats.resource_energy, simulation.environment.resource_energy());
+    
+    // The default tick spawns and ages
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(12),
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(10);
+    simulation.run_generation();
+    let stats = simulation.get_statistics();
+    assert!(stats.energy_injected > 0.0 && stats.energy_destroyed > 0.0);
+    assert!(stats.biomass_turnover > 0.0);
+}
+
+#[test]
+fn test_compute_usage_is_attributed_per_warrior() {
+    use neural_network_arena::vm::{Instruction, OpCode};
+    
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(4),
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(3);
+    let ids: Vec<u32> = simulation.environment.warriors.keys().copied().collect();
+    
+    let activate = Instruction::new(OpCode::Activate, 0, 1, 0.0);
+    let replicate = Instruction::new(OpCode::Replicate, 1, 2, 0.0);
+    simulation.execute_warrior_program(ids[0], std::slice::from_ref(&activate));
+    simulation.execute_warrior_program(ids[1], &[activate.clone(), activate, replicate.clone()]);
+    simulation.execute_warrior_program(ids[1], &[replicate]);
+    
+    let usage = simulation.compute_usage();
+    assert_eq!(usage[&ids[0]], 1);
+    assert_eq!(usage[&ids[1]], 22);
+    assert!(!usage.contains_key(&ids[2]));
+    assert_eq!(simulation.environment.warriors[&ids[1]].behavior.compute_spent, 22);
+    
+    // A generation starts from a clean slate and rates fitness against it
+    simulation.run_generation();
+    assert!(simulation.compute_usage().values().all(|&spent| spent > 0));
+    assert!(simulation.get_statistics().computational_efficiency > 0.0);
+}
+
+#[test]
+fn test_offspring_are_placed_by_the_spawn_strategy() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(15),
+        arena_width: 500.0,
+        arena_height: 500.0,
+        generation_ticks: 200,
+        spawn_strategy: SpawnStrategy::Perimeter,
+        genome_codec: GenomeCodecKind::Direct,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(100);
+    let result = simulation.run_generation();
+    assert!(result.survivors.len() > 10, "selection, not emergency recovery, should fill the generation");
+    
+    // Survivors wandered inward, so only fresh placement puts offspring back on the edge
+    let warriors = &simulation.environment.warriors;
+    assert!(!warriors.is_empty());
+    for warrior in warriors.values() {
+        let (x, y) = warrior.position;
+        assert!(
+            x == 0.0 || x == 500.0 || y == 0.0 || y == 500.0,
+            "offspring {} placed off the perimeter at ({x}, {y})", warrior.id,
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use neural_network_arena::neural::{LayerView, NetworkError, NeuralNetwork};
+
+#[test]
+fn test_direct_input_to_output_network() {
+    let network = NeuralNetwork::try_new(vec![8, 4]).unwrap();
+    assert_eq!(network.layer_sizes(), &[8, 4]);
+    assert_eq!(network.forward(&[0.5; 8]).len(), 4);
+    
+    // Short input vectors are zero-padded rather than panicking
+    assert_eq!(network.forward(&[0.5; 3]).len(), 4);
+}
+
+#[test]
+fn test_empty_hidden_layers_are_dropped() {
+    let network = NeuralNetwork::try_new(vec![8, 0, 0, 4]).unwrap();
+    assert_eq!(network.layer_sizes(), &[8, 4]);
+    assert_eq!(network.parameter_count(), 8 * 4 + 4);
+    assert_eq!(network.weight(0, 7, 3), Some(0.0));
+    assert_eq!(network.weight(1, 0, 0), None);
+}
+
+#[test]
+fn test_invalid_topologies_are_rejected() {
+    assert_eq!(NeuralNetwork::try_new(vec![]).unwrap_err(), NetworkError::EmptyTopology);
+    assert_eq!(NeuralNetwork::try_new(vec![0, 4]).unwrap_err(), NetworkError::EmptyLayer { layer: 0 });
+    assert_eq!(NeuralNetwork::try_new(vec![8, 16, 0]).unwrap_err(), NetworkError::EmptyLayer { layer: 2 });
+}
+
+#[test]
+fn test_export_import_preserves_outputs_exactly() {
+    let mut network = NeuralNetwork::try_new(vec![5, 7, 3]).unwrap();
+    network.mutate(1.0, 0.9);
+    
+    let imported = NeuralNetwork::import_json(&network.export_json().unwrap()).unwrap();
+    assert_eq!(imported.layer_sizes(), network.layer_sizes());
+    
+    let inputs = [0.3, -0.7, 0.11, 1.0, -0.05];
+    let expected: Vec<u32> = network.forward(&inputs).iter().map(|value| value.to_bits()).collect();
+    let actual: Vec<u32> = imported.infer(&inputs).unwrap().iter().map(|value| value.to_bits()).collect();
+    assert_eq!(actual, expected);
+    
+    assert_eq!(imported.infer(&inputs[..4]).unwrap_err(), NetworkError::InputSize { expected: 5, actual: 4 });
+}
+
+#[test]
+fn test_import_rejects_mismatched_parameters() {
+    let network = NeuralNetwork::try_new(vec![2, 3]).unwrap();
+    let mut export: serde_json::Value = serde_json::from_str(&network.export_json().unwrap()).unwrap();
+    
+    export["weights"].as_array_mut().unwrap().pop();
+    assert_eq!(
+        NeuralNetwork::import_json(&export.to_string()).unwrap_err(),
+        NetworkError::ParameterCount { kind: "weights", expected: 6, actual: 5 }
+    );
+    
+    export["layer_sizes"] = serde_json::json!([2, 0, 3]);
+    assert_eq!(NeuralNetwork::import_json(&export.to_string()).unwrap_err(), NetworkError::EmptyLayer { layer: 1 });
+    
+    assert!(matches!(NeuralNetwork::import_json("{\"weights\": []}"), Err(NetworkError::Parse(_))));
+}
+
+#[test]
+fn test_import_rejects_oversized_topologies_without_allocating() {
+    let import = |layer_sizes: serde_json::Value| NeuralNetwork::import_json(&serde_json::json!({
+        "format_version": 1,
+        "activation": "tanh",
+        "layer_sizes": layer_sizes,
+        "weights": [],
+        "biases": [],
+    }).to_string());
+    
+    // Too many weights to even count
+    assert_eq!(import(serde_json::json!([u64::MAX, 2])).unwrap_err(), NetworkError::TopologyTooLarge);
+    assert_eq!(import(serde_json::json!([1, u64::MAX, u64::MAX])).unwrap_err(), NetworkError::TopologyTooLarge);
+    
+    // Countable, but far more than the export carries
+    assert_eq!(
+        import(serde_json::json!([100_000, 10_000])).unwrap_err(),
+        NetworkError::ParameterCount { kind: "weights", expected: 1_000_000_000, actual: 0 }
+    );
+}
+
+#[test]
+fn test_layers_expose_weight_matrices_and_biases() {
+    let network = NeuralNetwork::import_json(&serde_json::json!({
+        "format_version": 1,
+        "activation": "tanh",
+        "layer_sizes": [2, 3, 1],
+        "weights": [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, -1.0, -2.0, -3.0],
+        "biases": [0.01, 0.02, 0.03, 0.5],
+    }).to_string()).unwrap();
+    
+    let layers = network.layers();
+    assert_eq!(layers, vec![
+        LayerView {
+            input_size: 2,
+            output_size: 3,
+            weights: vec![vec![0.1, 0.2, 0.3], vec![0.4, 0.5, 0.6]],
+            biases: vec![0.01, 0.02, 0.03],
+        },
+        LayerView {
+            input_size: 3,
+            output_size: 1,
+            weights: vec![vec![-1.0], vec![-2.0], vec![-3.0]],
+            biases: vec![0.5],
+        },
+    ]);
+    assert_eq!(layers[0].shape(), (2, 3));
+    assert_eq!(layers[0].weights[1][2], network.weight(0, 1, 2).unwrap());
+    
+    // Multiplying through the views reproduces the forward pass
+    let inputs = [0.7, -0.3];
+    let mut activations = inputs.to_vec();
+    for layer in &layers {
+        activations = (0..layer.output_size)
+            .map(|to| (layer.biases[to] + (0..layer.input_size).map(|from| activations[from] * layer.weights[from][to]).sum::<f32>()).tanh())
+            .collect();
+    }
+    let expected = network.forward(&inputs);
+    assert!(activations.iter().zip(&expected).all(|(a, b)| (a - b).abs() < 1e-6));
+}
+
+#[test]
+fn test_non_finite_parameters_produce_finite_outputs() {
+    let poisoned = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY];
+    let network = NeuralNetwork::from_parameter_fn(vec![4, 3, 2], |index| poisoned[index % poisoned.len()]);
+    
+    let outputs = network.forward(&[1.0, f32::NAN, f32::INFINITY, -1.0]);
+    assert_eq!(outputs.len(), 2);
+    assert!(outputs.iter().all(|output| output.is_finite()), "Outputs: {:?}", outputs);
+}
@@ -0,0 +1,85 @@
+use neural_network_arena::presets::PresetError;
+use neural_network_arena::{ConfigError, NeuralArenaSimulation, SimulationConfig};
+
+#[test]
+fn test_every_preset_validates_and_runs() {
+    for name in SimulationConfig::PRESET_NAMES {
+        let mut config = SimulationConfig::preset(name).unwrap();
+        config.validate().unwrap_or_else(|e| panic!("Preset {} is invalid: {}", name, e));
+        config.seed = Some(11);
+        
+        let mut simulation = NeuralArenaSimulation::new(config);
+        simulation.initialize_population(20);
+        for _ in 0..5 {
+            simulation.single_tick();
+        }
+        assert!(!simulation.environment.warriors.is_empty(), "Preset {} died out immediately", name);
+    }
+}
+
+#[test]
+fn test_preset_shapes_environment() {
+    let simulation = NeuralArenaSimulation::new(SimulationConfig::preset_coevolution_combat());
+    assert!(simulation.environment.safe_zones.is_empty());
+    
+    let simulation = NeuralArenaSimulation::new(SimulationConfig::preset_quick_demo());
+    assert_eq!((simulation.environment.width, simulation.environment.height), (500.0, 500.0));
+}
+
+#[test]
+fn test_preset_json_with_overrides() {
+    let config = SimulationConfig::from_json(
+        r#"{"preset": "quick_demo", "overrides": {"max_population": 80, "resource_config": {"spawn_rate": 0.3}}}"#,
+    ).unwrap();
+    
+    assert_eq!(config.max_population, 80);
+    assert_eq!(config.generation_ticks, 200);
+    assert_eq!(config.resource_config.spawn_rate, 0.3);
+    assert_eq!(config.resource_config.max_resources, 200, "Nested overrides should merge, not replace");
+}
+
+#[test]
+fn test_invalid_preset_requests_are_rejected() {
+    assert!(matches!(
+        SimulationConfig::from_json(r#"{"preset": "nope"}"#),
+        Err(PresetError::UnknownPreset { .. })
+    ));
+    assert!(matches!(
+        SimulationConfig::from_json(r#"{"preset": "scarcity", "overrides": {"mutation_rate": 2.0}}"#),
+        Err(PresetError::Invalid(ConfigError::OutOfUnitRange { field: "mutation_rate", .. }))
+    ));
+}
+
+#[test]
+fn test_non_finite_energy_range_is_rejected() {
+    let mut config = SimulationConfig::default();
+    config.resource_config.energy_range = (5.0, f32::INFINITY);
+    assert!(matches!(
+        config.validate(),
+        Err(ConfigError::NotFinite { field: "resource_config.energy_range.1", .. })
+    ));
+
+    config.resource_config.energy_range = (f32::NAN, 25.0);
+    assert!(matches!(
+        config.validate(),
+        Err(ConfigError::NotFinite { field: "resource_config.energy_range.0", .. })
+    ));
+}
+
+#[test]
+fn test_starting_resources_follow_the_configured_resource_settings() {
+    let mut config = SimulationConfig {
+        seed: Some(6),
+        safe_zones: false,
+        ..SimulationConfig::default()
+    };
+    config.resource_config.energy_range = (10.0, 10.0);
+    config.resource_config.computational_bonus = 1.0;
+    config.resource_config.territory_control_bonus = 1.0;
+    config.resource_config.max_resources = 40;
+    let simulation = NeuralArenaSimulation::new(config);
+    
+    let resources = &simulation.environment.resources;
+    assert_eq!(resources.len(), 40);
+    assert!(resources.iter().all(|resource| resource.energy_value == 10.0), "no default ranges or safe zone bonuses");
+}
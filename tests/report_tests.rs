@@ -0,0 +1,99 @@
+use neural_network_arena::{NeuralArenaSimulation, RunReport, SimulationConfig};
+use neural_network_arena::neural::NeuralWarrior;
+use neural_network_arena::report::{BestGenomeDelta, ByteChange};
+
+#[test]
+fn test_run_report_is_consistent_with_results() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(8),
+        generation_ticks: 40,
+        max_generations: 4,
+        ..SimulationConfig::preset_quick_demo()
+    });
+    simulation.initialize_population(30);
+    
+    let outcome = simulation.run_simulation(None);
+    let report = &outcome.report;
+    assert_eq!(report.trajectory.len(), outcome.results.len());
+    assert_eq!(report.final_generation, simulation.generation);
+    
+    let best = report.trajectory.iter().map(|point| point.best_fitness).fold(0.0, f32::max);
+    assert_eq!(report.peak_fitness, best);
+    let peak = report.trajectory.iter().find(|point| Some(point.generation) == report.peak_generation).unwrap();
+    assert_eq!(peak.best_fitness, report.peak_fitness);
+    
+    assert_eq!(report.max_population, report.trajectory.iter().map(|point| point.population).max().unwrap());
+    assert!(report.mean_population <= report.max_population as f32);
+    for (point, result) in report.trajectory.iter().zip(&outcome.results) {
+        assert_eq!(point.species_count, result.species_count);
+    }
+    
+    // Seed 8 ends with survivors, so a dominant lineage must be reported
+    assert!(report.dominant_lineage.is_some());
+    let lineage = report.dominant_lineage.as_ref().unwrap();
+    let survivors = &outcome.results.last().unwrap().survivors;
+    let members_of = |surname: &str| survivors.iter().filter(|warrior| warrior.surname() == surname).count();
+    assert!(lineage.members > 0);
+    assert_eq!(lineage.members, members_of(&lineage.surname));
+    assert!(survivors.iter().all(|warrior| members_of(warrior.surname()) <= lineage.members));
+    
+    let restored: RunReport = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(&restored, report);
+    assert!(report.to_string().contains("Peak fitness"));
+}
+
+#[test]
+fn test_empty_run_report() {
+    let simulation = NeuralArenaSimulation::new(SimulationConfig::default());
+    let report = RunReport::from_results(&[], simulation.get_statistics());
+    assert_eq!(report.peak_generation, None);
+    assert_eq!(report.mean_population, 0.0);
+    assert!(report.dominant_lineage.is_none());
+}
+
+#[test]
+fn test_best_genome_delta_reports_changed_bytes() {
+    let genome: Vec<u8> = (0..40).collect();
+    assert!(BestGenomeDelta::between(&genome, &genome).is_empty());
+    assert_eq!(BestGenomeDelta::between(&genome, &genome).to_string(), "unchanged");
+    
+    let mut mutated = genome.clone();
+    mutated[17] = 200;
+    let delta = BestGenomeDelta::between(&genome, &mutated);
+    assert_eq!(delta.changed, vec![ByteChange { index: 17, before: 17, after: 200 }]);
+    assert!(delta.inserted.is_empty());
+    assert_eq!(delta.truncated, 0);
+    assert_eq!(delta.to_string(), "1 changed [17: 11->c8]");
+    
+    let longer: Vec<u8> = genome.iter().copied().chain([7, 8]).collect();
+    let grown = BestGenomeDelta::between(&genome, &longer);
+    assert_eq!((grown.changed.len(), grown.inserted.clone(), grown.truncated), (0, vec![7, 8], 0));
+    let shrunk = BestGenomeDelta::between(&longer, &genome);
+    assert_eq!((shrunk.changed.len(), shrunk.inserted.len(), shrunk.truncated), (0, 0, 2));
+}
+
+#[test]
+fn test_generation_results_diff_consecutive_best_genomes() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(12),
+        generation_ticks: 30,
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(30);
+    
+    let best_bytes = |survivors: &[NeuralWarrior]| {
+        survivors.iter()
+            .max_by(|a, b| a.fitness_score.total_cmp(&b.fitness_score).then(b.id.cmp(&a.id)))
+            .map(|warrior| warrior.genome.bytes().to_vec())
+    };
+    
+    let first = simulation.run_generation();
+    assert!(first.best_genome_delta.is_none());
+    let mut previous = best_bytes(&first.survivors).unwrap();
+    for _ in 0..4 {
+        let result = simulation.run_generation();
+        let current = best_bytes(&result.survivors).unwrap();
+        assert_eq!(result.best_genome_delta, Some(BestGenomeDelta::between(&previous, &current)));
+        previous = current;
+    }
+}
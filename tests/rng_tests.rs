@@ -0,0 +1,53 @@
+use neural_network_arena::rng::{splitmix64, RngStream};
+use rand::Rng;
+use std::collections::HashSet;
+
+const STREAMS: [RngStream; 8] = [
+    RngStream::Simulation,
+    RngStream::Environment,
+    RngStream::Terrain,
+    RngStream::Resources,
+    RngStream::Events,
+    RngStream::Mutation,
+    RngStream::Speciation,
+    RngStream::Vm,
+];
+
+#[test]
+fn test_streams_are_distinct_and_stable() {
+    let seeds: HashSet<u64> = STREAMS.iter().map(|stream| stream.seed(7)).collect();
+    assert_eq!(seeds.len(), STREAMS.len());
+    
+    assert_eq!(RngStream::Terrain.seed(7), splitmix64(7 ^ RngStream::Terrain.tag()));
+    let mut first = RngStream::Mutation.rng(7);
+    let mut second = RngStream::Mutation.rng(7);
+    assert_eq!(first.gen::<u64>(), second.gen::<u64>());
+    assert_ne!(RngStream::Mutation.seed(7), RngStream::Mutation.seed(8));
+}
+
+#[test]
+fn test_simulation_vm_draws_from_the_vm_stream_of_the_master_seed() {
+    use neural_network_arena::vm::VirtualMachine;
+    use neural_network_arena::{NeuralArenaSimulation, SimulationConfig};
+    
+    let config = SimulationConfig { seed: Some(7), ..SimulationConfig::default() };
+    let simulation = NeuralArenaSimulation::new(config.clone());
+    let expected = VirtualMachine::with_seed(config.vm_memory_size, RngStream::Vm.seed(7));
+    let rederived = VirtualMachine::with_seed(config.vm_memory_size, RngStream::Vm.seed(RngStream::Vm.seed(7)));
+    
+    let snapshot = simulation.vm.snapshot(true).unwrap();
+    assert_eq!(snapshot, expected.snapshot(true).unwrap());
+    assert_ne!(snapshot, rederived.snapshot(true).unwrap());
+}
+
+#[test]
+fn test_unused_id_skips_taken_ids() {
+    use neural_network_arena::rng::unused_id;
+    
+    let mut draws = RngStream::Simulation.rng(3);
+    let first: u32 = draws.gen();
+    let second: u32 = draws.gen();
+    
+    assert_eq!(unused_id(&mut RngStream::Simulation.rng(3), |_| false), first);
+    assert_eq!(unused_id(&mut RngStream::Simulation.rng(3), |id| id == first), second);
+}
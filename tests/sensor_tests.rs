@@ -1,11 +1,16 @@
-use neural_network_arena::neural::{NeuralWarrior, Genome};
-use neural_network_arena::neural::warrior::SensorType;
-use neural_network_arena::environment::Environment;
+use neural_network_arena::neural::{Action, NeuralWarrior, Genome};
+use std::collections::HashMap;
+use neural_network_arena::neural::warrior::{ResourceType, SensorType};
+use neural_network_arena::environment::{Environment, MemoryBarrier, SafeZone};
+use neural_network_arena::neural::{Resource, ResourceGrid, Territory};
+use neural_network_arena::testing::{fixed_rng, random_position};
 
 #[test]
-fn test_all_eight_sensors_exist() {
-    // Test that all 8 required sensor types exist (MVP requirement)
-    let sensor_types = vec![
+fn test_all_sensors_exist() {
+    // The 8 MVP sensors plus barrier, safe zone, edge, grudge, resource claim,
+    // crowding, nearest-resource distance and bearing, trajectory perception
+    // and the resource density gradient
+    let sensor_types = [
         SensorType::Energy,
         SensorType::NeighborProximity,
         SensorType::ResourceDensity,
@@ -14,16 +19,28 @@ fn test_all_eight_sensors_exist() {
         SensorType::Threat,
         SensorType::Age,
         SensorType::LineageDepth,
+        SensorType::BarrierProximity,
+        SensorType::SafeZoneProximity,
+        SensorType::EdgeProximity,
+        SensorType::NeighborGrudge,
+        SensorType::ResourceContested,
+        SensorType::Crowding,
+        SensorType::ResourceDistance,
+        SensorType::ResourceBearing,
+        SensorType::Speed,
+        SensorType::Straightness,
+        SensorType::ResourceGradient,
     ];
     
-    assert_eq!(sensor_types.len(), 8, "MVP requires exactly 8 sensor inputs");
+    assert_eq!(sensor_types.len(), 19, "Expected 19 sensor inputs");
 }
 
 #[test]
 fn test_warrior_sensor_readings() {
+    let mut rng = fixed_rng(1);
     let mut environment = Environment::new(1000.0, 1000.0, 100);
     let genome = Genome::new_random();
-    let mut warrior = NeuralWarrior::new(genome, 1);
+    let mut warrior = NeuralWarrior::with_position(genome, 1, random_position((1000.0, 1000.0), &mut rng));
     
     // Place warrior in environment
     warrior.position = (100.0, 100.0);
@@ -46,19 +63,24 @@ fn test_warrior_sensor_readings() {
         SensorType::Threat,
         SensorType::Age,
         SensorType::LineageDepth,
+        SensorType::BarrierProximity,
+        SensorType::SafeZoneProximity,
+        SensorType::EdgeProximity,
+        SensorType::NeighborGrudge,
     ];
     
     for sensor_type in &sensor_types {
         let reading = warrior.get_sensor_reading(*sensor_type, &environment);
-        assert!(reading >= 0.0 && reading <= 1.0, 
+        assert!((0.0..=1.0).contains(&reading), 
                "Sensor {:?} reading {} out of bounds [0.0, 1.0]", sensor_type, reading);
     }
 }
 
 #[test]
 fn test_energy_sensor_accuracy() {
+    let mut rng = fixed_rng(2);
     let genome = Genome::new_random();
-    let mut warrior = NeuralWarrior::new(genome, 1);
+    let mut warrior = NeuralWarrior::with_position(genome, 1, random_position((1000.0, 1000.0), &mut rng));
     let environment = Environment::new(1000.0, 1000.0, 100);
     
     // Test different energy levels
@@ -81,12 +103,10 @@ fn test_neighbor_proximity_sensor() {
     let genome = Genome::new_random();
     
     // Create first warrior
-    let mut warrior1 = NeuralWarrior::new(genome.clone(), 1);
-    warrior1.position = (100.0, 100.0);
+    let warrior1 = NeuralWarrior::with_position(genome.clone(), 1, (100.0, 100.0));
     
     // Create second warrior nearby
-    let mut warrior2 = NeuralWarrior::new(genome, 2);
-    warrior2.position = (110.0, 100.0); // 10 units away
+    let warrior2 = NeuralWarrior::with_position(genome, 2, (110.0, 100.0)); // 10 units away
     
     environment.add_warrior(warrior1.clone());
     environment.add_warrior(warrior2);
@@ -107,8 +127,9 @@ fn test_neighbor_proximity_sensor() {
 
 #[test]
 fn test_age_sensor() {
+    let mut rng = fixed_rng(3);
     let genome = Genome::new_random();
-    let mut warrior = NeuralWarrior::new(genome, 1);
+    let mut warrior = NeuralWarrior::with_position(genome, 1, random_position((1000.0, 1000.0), &mut rng));
     let environment = Environment::new(1000.0, 1000.0, 100);
     
     // Test young warrior
@@ -117,15 +138,20 @@ fn test_age_sensor() {
     assert!(young_reading < 0.1);
     
     // Test older warrior
-    warrior.age = 1000; // Assuming max age around 1000
+    let max_age = environment.sensor_config.max_age;
+    warrior.age = (max_age * 0.9) as u32;
     let old_reading = warrior.get_sensor_reading(SensorType::Age, &environment);
-    assert!(old_reading > 0.8);
+    assert!((old_reading - 0.9).abs() < 1e-3);
+    
+    warrior.age = max_age as u32 * 2;
+    assert_eq!(warrior.get_sensor_reading(SensorType::Age, &environment), 1.0);
 }
 
 #[test]
 fn test_lineage_depth_sensor() {
+    let mut rng = fixed_rng(4);
     let genome = Genome::new_random();
-    let mut warrior = NeuralWarrior::new(genome, 1);
+    let mut warrior = NeuralWarrior::with_position(genome, 1, random_position((1000.0, 1000.0), &mut rng));
     let environment = Environment::new(1000.0, 1000.0, 100);
     
     // Test first generation
@@ -134,16 +160,18 @@ fn test_lineage_depth_sensor() {
     assert_eq!(first_gen_reading, 0.0);
     
     // Test deeper lineage
-    warrior.lineage_depth = 10;
+    let max_depth = environment.sensor_config.max_lineage_depth;
+    warrior.lineage_depth = (max_depth / 5.0) as u32;
     let deep_reading = warrior.get_sensor_reading(SensorType::LineageDepth, &environment);
-    assert!(deep_reading > 0.0);
+    assert!((deep_reading - 0.2).abs() < 1e-6);
 }
 
 #[test]
 fn test_sensor_readings_change_with_environment() {
+    let mut rng = fixed_rng(5);
     let mut environment = Environment::new(1000.0, 1000.0, 100);
     let genome = Genome::new_random();
-    let mut warrior = NeuralWarrior::new(genome, 1);
+    let mut warrior = NeuralWarrior::with_position(genome, 1, random_position((1000.0, 1000.0), &mut rng));
     
     // Position the test warrior where it can detect the others
     warrior.position = (50.0, 50.0);
@@ -157,7 +185,7 @@ fn test_sensor_readings_change_with_environment() {
     // Add more warriors nearby to increase population density
     for i in 2..=10 {
         let genome = Genome::new_random();
-        let mut new_warrior = NeuralWarrior::new(genome, i);
+        let mut new_warrior = NeuralWarrior::with_position(genome, i, random_position((1000.0, 1000.0), &mut rng));
         // Place warriors close to the first warrior for population detection
         new_warrior.position = (50.0 + (i as f32 * 10.0), 50.0);
         environment.add_warrior(new_warrior);
@@ -169,6 +197,549 @@ fn test_sensor_readings_change_with_environment() {
            "Population sensor should increase with more warriors");
     
     // Sensors should return valid values
-    assert!(new_population >= 0.0 && new_population <= 1.0);
-    assert!(initial_resource >= 0.0 && initial_resource <= 1.0);
-}
\ No newline at end of file
+    assert!((0.0..=1.0).contains(&new_population));
+    assert!((0.0..=1.0).contains(&initial_resource));
+}
+
+#[test]
+fn test_population_radius_is_configurable() {
+    let mut environment = Environment::new(1000.0, 1000.0, 100);
+    let warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (500.0, 500.0));
+    
+    // Neighbors spread between 20 and 90 units away
+    for i in 0..8u32 {
+        let neighbor = NeuralWarrior::with_position(Genome::new_random(), 100 + i, (520.0 + i as f32 * 10.0, 500.0));
+        environment.add_warrior(neighbor);
+    }
+    
+    let wide_reading = warrior.get_sensor_reading(SensorType::Population, &environment);
+    
+    environment.sensor_config.population_radius /= 2.0;
+    let narrow_reading = warrior.get_sensor_reading(SensorType::Population, &environment);
+    
+    assert!(narrow_reading < wide_reading,
+           "Halving the radius should detect fewer neighbors ({} vs {})", narrow_reading, wide_reading);
+}
+
+fn open_terrain_environment() -> Environment {
+    let mut environment = Environment::new(1000.0, 1000.0, 100);
+    environment.barriers.clear();
+    environment.safe_zones.clear();
+    environment
+}
+
+#[test]
+fn test_safe_zone_proximity_reads_one_inside() {
+    let mut environment = open_terrain_environment();
+    environment.safe_zones.push(SafeZone {
+        center: (500.0, 500.0),
+        radius: 50.0,
+        protection_level: 0.8,
+        resource_bonus: 1.5,
+    });
+    
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (510.0, 490.0));
+    assert_eq!(warrior.get_sensor_reading(SensorType::SafeZoneProximity, &environment), 1.0);
+    
+    // Just outside the boundary reads high but below 1.0
+    warrior.position = (550.0 + environment.sensor_config.safe_zone_radius / 10.0, 500.0);
+    let near_edge = warrior.get_sensor_reading(SensorType::SafeZoneProximity, &environment);
+    assert!((near_edge - 0.9).abs() < 1e-4, "Near edge reading was {}", near_edge);
+}
+
+#[test]
+fn test_barrier_proximity_uses_rect_edge_distance() {
+    let mut rng = fixed_rng(6);
+    let mut environment = open_terrain_environment();
+    environment.barriers.push(MemoryBarrier {
+        position: (400.0, 400.0),
+        width: 200.0,
+        height: 20.0,
+        strength: 1.0,
+    });
+    
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    
+    // Half the barrier radius below the long edge, far from the rect's center
+    warrior.position = (580.0, 420.0 + environment.sensor_config.barrier_radius / 2.0);
+    let reading = warrior.get_sensor_reading(SensorType::BarrierProximity, &environment);
+    assert!((reading - 0.5).abs() < 1e-4, "Expected 0.5 at half range, got {}", reading);
+    
+    warrior.position = (500.0, 410.0);
+    assert_eq!(warrior.get_sensor_reading(SensorType::BarrierProximity, &environment), 1.0);
+}
+
+#[test]
+fn test_open_ground_reads_no_barriers_or_shelter() {
+    let mut environment = open_terrain_environment();
+    environment.barriers.push(MemoryBarrier {
+        position: (0.0, 0.0),
+        width: 20.0,
+        height: 20.0,
+        strength: 1.0,
+    });
+    environment.safe_zones.push(SafeZone {
+        center: (900.0, 900.0),
+        radius: 30.0,
+        protection_level: 0.8,
+        resource_bonus: 1.5,
+    });
+    
+    let warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (500.0, 500.0));
+    
+    let barrier = warrior.get_sensor_reading(SensorType::BarrierProximity, &environment);
+    let shelter = warrior.get_sensor_reading(SensorType::SafeZoneProximity, &environment);
+    assert!(barrier < 1e-6, "Barrier reading in open ground was {}", barrier);
+    assert!(shelter < 1e-6, "Safe zone reading in open ground was {}", shelter);
+}
+
+fn single_territory_environment(owner_id: Option<u32>) -> Environment {
+    let mut environment = open_terrain_environment();
+    environment.territories = vec![Territory {
+        center: (300.0, 300.0),
+        radius: 80.0,
+        owner_id,
+        resource_multiplier: 1.0,
+    }];
+    environment
+}
+
+#[test]
+fn test_territory_pressure_reflects_ownership() {
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (320.0, 300.0));
+    warrior.energy = 25.0;
+    
+    let unowned = single_territory_environment(None);
+    assert_eq!(warrior.get_sensor_reading(SensorType::TerritoryPressure, &unowned), 0.0);
+    
+    let mut foreign = single_territory_environment(Some(2));
+    let mut owner = NeuralWarrior::with_position(Genome::new_random(), 2, (300.0, 300.0));
+    owner.energy = 75.0;
+    foreign.add_warrior(owner);
+    assert_eq!(warrior.get_sensor_reading(SensorType::TerritoryPressure, &foreign), 0.75);
+    
+    let own = single_territory_environment(Some(1));
+    assert_eq!(warrior.get_sensor_reading(SensorType::TerritoryPressure, &own), 0.0);
+    
+    // Outside every territory there is no pressure, whoever owns it
+    warrior.position = (600.0, 600.0);
+    assert_eq!(warrior.get_sensor_reading(SensorType::TerritoryPressure, &foreign), 0.0);
+}
+
+#[test]
+fn test_edge_proximity_sensor() {
+    let environment = open_terrain_environment();
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (0.0, 500.0));
+    assert_eq!(warrior.get_sensor_reading(SensorType::EdgeProximity, &environment), 1.0);
+    
+    let edge_radius = environment.sensor_config.edge_radius;
+    warrior.position = (500.0, 1000.0 - edge_radius / 2.0);
+    assert_eq!(warrior.get_sensor_reading(SensorType::EdgeProximity, &environment), 0.5);
+    
+    warrior.position = (500.0, 500.0);
+    assert_eq!(warrior.get_sensor_reading(SensorType::EdgeProximity, &environment), 0.0);
+}
+
+#[test]
+fn test_grudge_sensor_remembers_attacker_until_expiry() {
+    let mut environment = open_terrain_environment();
+    environment.sensor_config.grudge_memory_ticks = 5;
+    
+    let attacker = NeuralWarrior::with_position(Genome::new_random(), 1, (500.0, 500.0));
+    let victim = NeuralWarrior::with_position(Genome::new_random(), 2, (530.0, 500.0));
+    environment.add_warrior(attacker);
+    environment.add_warrior(victim);
+    
+    let grudge = |environment: &Environment| {
+        environment.warriors[&2].get_sensor_reading(SensorType::NeighborGrudge, environment)
+    };
+    assert_eq!(grudge(&environment), 0.0);
+    
+    let mut actions = HashMap::new();
+    actions.insert(1, Action::Attack { target_direction: 0.0, strength: 1.0 });
+    environment.execute_warrior_actions(actions);
+    assert_eq!(grudge(&environment), 1.0);
+    assert_eq!(environment.warriors[&1].get_sensor_reading(SensorType::NeighborGrudge, &environment), 0.0);
+    
+    for _ in 0..5 {
+        environment.tick();
+    }
+    assert_eq!(grudge(&environment), 0.0);
+    assert!(environment.warriors[&2].recent_attackers.is_empty());
+}
+
+#[test]
+fn test_vision_cone_hides_threats_behind() {
+    let mut environment = open_terrain_environment();
+    environment.sensor_config.vision_cone = Some(std::f32::consts::FRAC_PI_2);
+    
+    // Facing +x, with an equally strong enemy either ahead or behind
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (500.0, 500.0));
+    warrior.heading = 0.0;
+    let mut enemy = NeuralWarrior::with_position(Genome::new_random(), 2, (505.0, 500.0));
+    environment.add_warrior(enemy.clone());
+    let in_front = warrior.get_sensor_reading(SensorType::Threat, &environment);
+    
+    enemy.position = (495.0, 500.0);
+    environment.add_warrior(enemy);
+    let behind = warrior.get_sensor_reading(SensorType::Threat, &environment);
+    
+    assert!(in_front > 0.1, "Enemy ahead should register, read {}", in_front);
+    assert_eq!(behind, 0.0);
+    
+    environment.sensor_config.vision_cone = None;
+    assert!(warrior.get_sensor_reading(SensorType::Threat, &environment) > 0.1);
+}
+
+#[test]
+fn test_vision_cone_limits_attacks_and_moves_turn_warriors() {
+    let mut environment = open_terrain_environment();
+    environment.sensor_config.vision_cone = Some(std::f32::consts::FRAC_PI_2);
+    
+    let mut attacker = NeuralWarrior::with_position(Genome::new_random(), 1, (500.0, 500.0));
+    attacker.heading = std::f32::consts::PI; // Facing away from the target
+    let target = NeuralWarrior::with_position(Genome::new_random(), 2, (530.0, 500.0));
+    environment.add_warrior(attacker);
+    environment.add_warrior(target);
+    
+    let attack = || HashMap::from([(1, Action::Attack { target_direction: 0.0, strength: 1.0 })]);
+    environment.execute_warrior_actions(attack());
+    assert_eq!(environment.warriors[&2].energy, 100.0, "Targets outside the cone can't be hit");
+    
+    // Stepping toward the target turns the attacker to face it
+    environment.execute_warrior_actions(HashMap::from([(1, Action::Move { direction: -std::f32::consts::TAU, intensity: 0.1 })]));
+    assert!(environment.warriors[&1].heading.abs() < 1e-4);
+    environment.execute_warrior_actions(attack());
+    assert!(environment.warriors[&2].energy < 100.0);
+}
+
+#[test]
+fn test_collisions_keep_personal_space() {
+    let mut environment = open_terrain_environment();
+    environment.collision_config.enabled = true;
+    environment.resources.clear();
+    let radius = environment.sensor_config.personal_radius;
+    
+    for (id, x) in [(1, 490.0), (2, 510.0)] {
+        let warrior = NeuralWarrior::with_position(Genome::new_random(), id, (x, 500.0));
+        environment.add_warrior(warrior);
+    }
+    
+    // Both step onto (500, 500)
+    environment.execute_warrior_actions(HashMap::from([
+        (1, Action::Move { direction: 0.0, intensity: 1.0 }),
+        (2, Action::Move { direction: std::f32::consts::PI, intensity: 1.0 }),
+    ]));
+    
+    let (a, b) = (environment.warriors[&1].position, environment.warriors[&2].position);
+    let gap = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+    assert!(gap >= radius - 1e-3, "warriors ended {gap} apart");
+    // Pushing costs energy on top of the move itself
+    assert!(environment.warriors[&1].energy < 98.0);
+    
+    // One neighbor inside the personal radius
+    environment.warriors.get_mut(&2).unwrap().position = a;
+    let state = environment.get_environment_state();
+    assert_eq!(environment.warriors[&1].sense_environment(&state).crowding, 0.2);
+}
+
+#[test]
+fn test_resource_bearing_and_forward_steering() {
+    let north = -std::f32::consts::FRAC_PI_2;
+    let mut environment = Environment::new(1000.0, 1000.0, 100);
+    environment.resources.clear();
+    environment.barriers.clear();
+    environment.relative_steering = true;
+    environment.resources.push(Resource {
+        id: 999,
+        position: (500.0, 470.0),
+        energy_value: 10.0,
+        resource_type: ResourceType::Energy,
+        claim: None,
+    });
+    
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (500.0, 500.0));
+    warrior.heading = north;
+    environment.add_warrior(warrior);
+    
+    let warrior = &environment.warriors[&1];
+    let distance = warrior.get_sensor_reading(SensorType::ResourceDistance, &environment);
+    assert!((distance - 30.0 / environment.sensor_config.resource_radius).abs() < 1e-5);
+    assert!((warrior.get_sensor_reading(SensorType::ResourceBearing, &environment) - 0.5).abs() < 1e-5);
+    
+    // A zero direction output means "forward" under relative steering
+    let mut outputs = vec![0.0; Action::OUTPUT_SIZE];
+    outputs[0] = 0.9;
+    outputs[Action::MOVE_INTENSITY_OUTPUT] = 1.0;
+    let action = warrior.interpret_neural_output(&outputs, 0.0, &mut rand::thread_rng());
+    environment.execute_warrior_actions(HashMap::from([(1, action)]));
+    
+    let warrior = &environment.warriors[&1];
+    let closer = warrior.get_sensor_reading(SensorType::ResourceDistance, &environment);
+    assert!(closer < distance, "Moving forward should close the distance: {} -> {}", distance, closer);
+    assert!((warrior.get_sensor_reading(SensorType::ResourceBearing, &environment) - 0.5).abs() < 1e-3);
+}
+
+/// A scene of neighbors, resources, a barrier and a safe zone laid out
+/// relative to the arena size, with every sensor range scaled to match.
+fn scaled_scene(scale: f32) -> (Environment, NeuralWarrior) {
+    let mut environment = Environment::new(1000.0 * scale, 1000.0 * scale, 100);
+    environment.barriers.clear();
+    environment.safe_zones.clear();
+    environment.resources.clear();
+    let config = &mut environment.sensor_config;
+    for range in [
+        &mut config.neighbor_proximity_scale,
+        &mut config.resource_radius,
+        &mut config.population_radius,
+        &mut config.barrier_radius,
+        &mut config.safe_zone_radius,
+        &mut config.edge_radius,
+        &mut config.personal_radius,
+        &mut config.resource_gradient_radius,
+    ] {
+        *range *= scale;
+    }
+    
+    let at = |x: f32, y: f32| (x * scale, y * scale);
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, at(970.0, 500.0));
+    warrior.heading = 0.3;
+    environment.add_warrior(warrior.clone());
+    for (id, x, y) in [(2, 975.0, 505.0), (3, 900.0, 480.0), (4, 830.0, 560.0)] {
+        let neighbor = NeuralWarrior::with_position(Genome::new_random(), id, at(x, y));
+        environment.add_warrior(neighbor);
+    }
+    for (id, x, y) in [(10, 950.0, 470.0), (11, 990.0, 530.0), (12, 700.0, 500.0)] {
+        environment.resources.push(Resource {
+            id,
+            position: at(x, y),
+            energy_value: 10.0,
+            resource_type: ResourceType::Energy,
+            claim: None,
+        });
+    }
+    environment.barriers.push(MemoryBarrier {
+        position: at(900.0, 400.0),
+        width: 40.0 * scale,
+        height: 20.0 * scale,
+        strength: 1.0,
+    });
+    environment.safe_zones.push(SafeZone {
+        center: at(900.0, 600.0),
+        radius: 40.0 * scale,
+        protection_level: 0.8,
+        resource_bonus: 1.5,
+    });
+    (environment, warrior)
+}
+
+#[test]
+fn test_doubling_arena_and_ranges_preserves_readings() {
+    let (environment, warrior) = scaled_scene(1.0);
+    let (doubled, doubled_warrior) = scaled_scene(2.0);
+    
+    for sensor_type in [
+        SensorType::ResourceDensity,
+        SensorType::Population,
+        SensorType::BarrierProximity,
+        SensorType::SafeZoneProximity,
+        SensorType::EdgeProximity,
+        SensorType::Crowding,
+        SensorType::ResourceDistance,
+        SensorType::ResourceBearing,
+    ] {
+        let reading = warrior.get_sensor_reading(sensor_type, &environment);
+        let scaled = doubled_warrior.get_sensor_reading(sensor_type, &doubled);
+        assert!((reading - scaled).abs() < 1e-4, "{:?} changed from {} to {}", sensor_type, reading, scaled);
+    }
+    
+    // Neighbor proximity offsets the distance by one unit, so it only
+    // approximately scales
+    let proximity = warrior.get_sensor_reading(SensorType::NeighborProximity, &environment);
+    let scaled = doubled_warrior.get_sensor_reading(SensorType::NeighborProximity, &doubled);
+    assert!((proximity - scaled).abs() < 0.05, "NeighborProximity changed from {} to {}", proximity, scaled);
+}
+
+#[test]
+fn test_straightness_separates_travel_from_oscillation() {
+    let mut environment = open_terrain_environment();
+    environment.event_config.probability_per_tick = 0.0;
+    for (id, y) in [(1, 300.0), (2, 700.0)] {
+        let warrior = NeuralWarrior::with_position(Genome::new_random(), id, (300.0, y));
+        environment.add_warrior(warrior);
+    }
+    
+    for step in 0..NeuralWarrior::POSITION_HISTORY_CAPACITY * 2 {
+        environment.tick();
+        let back_and_forth = if step % 2 == 0 { 0.0 } else { std::f32::consts::PI };
+        environment.execute_warrior_actions(HashMap::from([
+            (1, Action::Move { direction: back_and_forth, intensity: 1.0 }),
+            (2, Action::Move { direction: 0.0, intensity: 1.0 }),
+        ]));
+    }
+    
+    let reading = |id: u32, sensor_type: SensorType| environment.warriors[&id].get_sensor_reading(sensor_type, &environment);
+    assert!(reading(1, SensorType::Straightness) < 0.2, "Oscillating warrior read {}", reading(1, SensorType::Straightness));
+    assert!(reading(2, SensorType::Straightness) > 0.99, "Traveling warrior read {}", reading(2, SensorType::Straightness));
+    assert!(reading(1, SensorType::Speed) > 0.99 && reading(2, SensorType::Speed) > 0.99);
+}
+
+#[test]
+fn test_resource_gradient_points_toward_denser_resources() {
+    let mut environment = open_terrain_environment();
+    environment.resources.clear();
+    // Sparse to the west, dense to the east
+    let mut id = 0;
+    for (x, count) in [(420.0, 1), (560.0, 3), (640.0, 6)] {
+        for k in 0..count {
+            id += 1;
+            environment.resources.push(Resource {
+                id,
+                position: (x, 480.0 + k as f32 * 8.0),
+                energy_value: 10.0,
+                resource_type: ResourceType::Energy,
+                claim: None,
+            });
+        }
+    }
+    
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (500.0, 500.0));
+    warrior.heading = 0.0;
+    environment.add_warrior(warrior);
+    let reading = |environment: &Environment| environment.warriors[&1].get_sensor_reading(SensorType::ResourceGradient, environment);
+    
+    // Facing east, up-gradient is roughly dead ahead
+    assert!((reading(&environment) - 0.5).abs() < 0.05, "Facing east read {}", reading(&environment));
+    
+    // Facing west, up-gradient is behind
+    environment.warriors.get_mut(&1).unwrap().heading = std::f32::consts::PI;
+    let behind = reading(&environment);
+    assert!(!(0.05..=0.95).contains(&behind), "Facing west read {}", behind);
+    
+    // Facing north, up-gradient is a quarter turn clockwise
+    environment.warriors.get_mut(&1).unwrap().heading = 3.0 * std::f32::consts::FRAC_PI_2;
+    assert!((reading(&environment) - 0.75).abs() < 0.05, "Facing north read {}", reading(&environment));
+    
+    environment.resources.clear();
+    assert_eq!(reading(&environment), 0.5);
+}
+
+#[test]
+fn test_resource_gradient_weights_nearer_resources_more() {
+    let mut environment = open_terrain_environment();
+    environment.resources.clear();
+    // One resource close by to the west, two far off to the east
+    for (id, position) in [(1, (480.0, 500.0)), (2, (650.0, 495.0)), (3, (650.0, 505.0))] {
+        environment.resources.push(Resource {
+            id,
+            position,
+            energy_value: 10.0,
+            resource_type: ResourceType::Energy,
+            claim: None,
+        });
+    }
+    
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (500.0, 500.0));
+    warrior.heading = std::f32::consts::PI;
+    environment.add_warrior(warrior);
+    
+    let reading = environment.warriors[&1].get_sensor_reading(SensorType::ResourceGradient, &environment);
+    assert!((reading - 0.5).abs() < 0.05, "Facing the near resource read {}", reading);
+}
+
+fn noisy_scene() -> (Environment, NeuralWarrior) {
+    let mut environment = Environment::with_seed(500.0, 500.0, 50, 3);
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, (250.0, 250.0));
+    warrior.energy = 60.0;
+    environment.add_warrior(warrior.clone());
+    environment.add_warrior(NeuralWarrior::with_position(Genome::new_random(), 2, (270.0, 250.0)));
+    (environment, warrior)
+}
+
+#[test]
+fn test_zero_sensor_noise_keeps_clean_readings() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    
+    let (environment, warrior) = noisy_scene();
+    let state = environment.get_environment_state();
+    let clean = warrior.sense_environment(&state).to_inputs();
+    
+    let mut rng = StdRng::seed_from_u64(4);
+    let mut sensors = warrior.sense_environment(&state);
+    sensors.apply_noise(&state.sensor_config, &mut rng);
+    assert_eq!(sensors.to_inputs(), clean);
+    assert_eq!(rng.gen::<u64>(), StdRng::seed_from_u64(4).gen::<u64>(), "Noiseless sensing should not draw from the RNG");
+}
+
+#[test]
+fn test_sensor_noise_varies_readings_within_bounds() {
+    use rand::{rngs::StdRng, SeedableRng};
+    
+    let (mut environment, warrior) = noisy_scene();
+    environment.sensor_config.sensor_noise = 0.5;
+    let state = environment.get_environment_state();
+    let clean = warrior.sense_environment(&state).to_inputs();
+    
+    let mut rng = StdRng::seed_from_u64(5);
+    let readings: Vec<Vec<f32>> = (0..5).map(|_| {
+        let mut sensors = warrior.sense_environment(&state);
+        sensors.apply_noise(&state.sensor_config, &mut rng);
+        sensors.to_inputs()
+    }).collect();
+    
+    assert!(readings.iter().all(|inputs| inputs.iter().all(|value| (0.0..=1.0).contains(value))));
+    assert!(readings.windows(2).all(|pair| pair[0] != pair[1]));
+    assert!(readings.iter().all(|inputs| inputs != &clean));
+    
+    // Only the overridden sensor is noisy
+    environment.sensor_config.sensor_noise = 0.0;
+    environment.sensor_config.sensor_noise_overrides.insert(SensorType::Threat, 0.5);
+    let state = environment.get_environment_state();
+    let threat = SensorType::ALL.iter().position(|&sensor| sensor == SensorType::Threat).unwrap();
+    let changed: Vec<usize> = (0..20).flat_map(|_| {
+        let mut sensors = warrior.sense_environment(&state);
+        sensors.apply_noise(&state.sensor_config, &mut rng);
+        let inputs = sensors.to_inputs();
+        (0..inputs.len()).filter(|&index| inputs[index] != clean[index]).collect::<Vec<_>>()
+    }).collect();
+    assert!(!changed.is_empty());
+    assert!(changed.iter().all(|&index| index == threat));
+}
+
+#[test]
+fn test_resource_gradient_reads_the_same_from_the_grid_as_from_a_full_scan() {
+    let mut rng = fixed_rng(12);
+    let mut environment = Environment::with_seed(1000.0, 1000.0, 50, 12);
+    environment.sensor_config.resource_gradient_radius = 150.0;
+    let indexed = environment.get_environment_state();
+    let mut unindexed = indexed.clone();
+    unindexed.resource_grid = ResourceGrid::default();
+    assert!(environment.resources.len() >= 50);
+    
+    for id in 0..40 {
+        let warrior = NeuralWarrior::with_position(Genome::new_random(), id, random_position((1000.0, 1000.0), &mut rng));
+        assert_eq!(
+            warrior.sense_environment(&indexed).resource_gradient,
+            warrior.sense_environment(&unindexed).resource_gradient,
+        );
+    }
+}
+
+#[test]
+fn test_collision_pushes_stop_at_barriers() {
+    let mut environment = open_terrain_environment();
+    environment.collision_config.enabled = true;
+    environment.resources.clear();
+    let barrier = MemoryBarrier { position: (500.0, 400.0), width: 20.0, height: 200.0, strength: 1.0 };
+    environment.barriers.push(barrier.clone());
+    
+    // The pair overlaps right next to the barrier's west face
+    environment.add_warrior(NeuralWarrior::with_position(Genome::new_random(), 1, (496.0, 500.0)));
+    environment.add_warrior(NeuralWarrior::with_position(Genome::new_random(), 2, (498.0, 500.0)));
+    environment.execute_warrior_actions(HashMap::from([(1, Action::Rest), (2, Action::Rest)]));
+    
+    for warrior in environment.warriors.values() {
+        assert!(!barrier.contains(warrior.position), "warrior {} pushed into the barrier at {:?}", warrior.id, warrior.position);
+    }
+    assert!(environment.warriors[&1].position.0 < 496.0, "the free side of the pair still separates");
+}
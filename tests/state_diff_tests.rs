@@ -0,0 +1,63 @@
+use neural_network_arena::state_diff::{diff_states, WarriorDiff};
+use neural_network_arena::wasm_api::{SimulationState, WarriorData};
+
+fn warrior(id: u32, x: f32, y: f32) -> WarriorData {
+    WarriorData {
+        id,
+        name: format!("Warrior {}", id),
+        x,
+        y,
+        energy: 100.0,
+        age: 10,
+        fitness: 1.0,
+        lineage_depth: 0,
+        species_id: Some(1),
+        action: "idle".to_string(),
+        status_flags: 0,
+    }
+}
+
+fn state(warriors: Vec<WarriorData>) -> SimulationState {
+    SimulationState {
+        population_size: warriors.len(),
+        warriors,
+        resources: Vec::new(),
+        territories: Vec::new(),
+        barriers: Vec::new(),
+        safe_zones: Vec::new(),
+        generation: 2,
+        tick: 40,
+        species_count: 1,
+        average_fitness: 1.0,
+        max_fitness: 1.0,
+        diversity_score: 0.5,
+        environmental_pressure: 0.1,
+    }
+}
+
+#[test]
+fn test_diff_reports_only_the_moved_warrior() {
+    let a = state(vec![warrior(1, 10.0, 10.0), warrior(2, 50.0, 50.0), warrior(3, 90.0, 90.0)]);
+    let b = state(vec![warrior(1, 10.0, 10.0), warrior(2, 53.0, 46.0), warrior(3, 90.0, 90.0)]);
+    
+    let diff = diff_states(&a, &b);
+    assert_eq!(diff.changed_warriors, vec![WarriorDiff { id: 2, position_delta: (3.0, -4.0), energy_delta: 0.0 }]);
+    assert!(diff.added_warriors.is_empty() && diff.removed_warriors.is_empty());
+    assert!(diff.stats.is_empty());
+    assert!(diff_states(&a, &a).is_empty());
+}
+
+#[test]
+fn test_diff_reports_added_removed_warriors_and_stats() {
+    let a = state(vec![warrior(1, 10.0, 10.0), warrior(2, 50.0, 50.0)]);
+    let mut b = state(vec![warrior(2, 50.0, 50.0), warrior(3, 90.0, 90.0), warrior(4, 0.0, 0.0)]);
+    b.tick = 41;
+    
+    let diff = diff_states(&a, &b);
+    assert_eq!(diff.added_warriors, vec![3, 4]);
+    assert_eq!(diff.removed_warriors, vec![1]);
+    assert!(diff.changed_warriors.is_empty());
+    
+    let stats: Vec<(&str, f64, f64)> = diff.stats.iter().map(|stat| (stat.stat.as_str(), stat.a, stat.b)).collect();
+    assert_eq!(stats, vec![("tick", 40.0, 41.0), ("population_size", 2.0, 3.0)]);
+}
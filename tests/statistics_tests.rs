@@ -0,0 +1,103 @@
+use neural_network_arena::neural::{Genome, NeuralWarrior};
+use neural_network_arena::statistics::PopulationAccumulator;
+use neural_network_arena::{NeuralArenaSimulation, SimulationConfig};
+use neural_network_arena::testing::{fixed_rng, random_position};
+
+fn known_population() -> Vec<NeuralWarrior> {
+    let mut rng = fixed_rng(1);
+    (0..12).map(|id| {
+        let mut warrior = NeuralWarrior::with_position(Genome::new_random(), id, random_position((1000.0, 1000.0), &mut rng));
+        warrior.fitness_score = id as f32 * 1.5 - 3.0;
+        warrior.energy = 10.0 + id as f32;
+        warrior.age = id * 7;
+        warrior.lineage_depth = id % 5;
+        warrior
+    }).collect()
+}
+
+#[test]
+fn test_accumulator_matches_full_recompute() {
+    let warriors = known_population();
+    let mut accumulator = PopulationAccumulator::new();
+    for warrior in &warriors {
+        accumulator.add(warrior);
+    }
+    
+    let n = warriors.len() as f32;
+    let mean_fitness = warriors.iter().map(|w| w.fitness_score).sum::<f32>() / n;
+    let variance = warriors.iter().map(|w| (w.fitness_score - mean_fitness).powi(2)).sum::<f32>() / (n - 1.0);
+    
+    assert_eq!(accumulator.count(), warriors.len());
+    assert!((accumulator.average_fitness() - mean_fitness).abs() < 1e-5);
+    assert!((accumulator.fitness_std_dev() - variance.sqrt()).abs() < 1e-4);
+    assert_eq!(accumulator.max_fitness(), 13.5);
+    assert_eq!(accumulator.average_energy(), warriors.iter().map(|w| w.energy).sum::<f32>() / n);
+    assert_eq!(accumulator.average_age(), warriors.iter().map(|w| w.age).sum::<u32>() as f32 / n);
+    assert_eq!(accumulator.max_lineage_depth(), 4);
+    assert_eq!(accumulator.mean_genome_size(), warriors.iter().map(|w| w.genome.size()).sum::<usize>() as f32 / n);
+}
+
+#[test]
+fn test_simulation_keeps_accumulator_in_sync() {
+    let mut simulation = NeuralArenaSimulation::new(SimulationConfig {
+        seed: Some(5),
+        ..SimulationConfig::default()
+    });
+    simulation.initialize_population(40);
+    
+    for _ in 0..25 {
+        simulation.single_tick();
+    }
+    
+    // A newcomer is counted by the next tick and the dead are dropped
+    let mut rng = fixed_rng(2);
+    simulation.environment.add_warrior(NeuralWarrior::with_position(Genome::random_with_rng(&mut rng), 9_999, random_position((1000.0, 1000.0), &mut rng)));
+    let doomed: Vec<u32> = simulation.environment.warriors.keys().take(3).copied().collect();
+    for id in &doomed {
+        simulation.environment.warriors.get_mut(id).unwrap().energy = 0.0;
+    }
+    let update = simulation.single_tick();
+    assert!(doomed.iter().all(|id| update.dead_warriors.contains(id)));
+    assert_eq!(simulation.population_stats.count(), simulation.environment.warriors.len());
+    
+    assert_matches_recompute(&simulation.population_stats, &simulation.environment.warriors.values().cloned().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_removing_the_fittest_falls_back_to_the_next() {
+    let mut warriors = known_population();
+    let mut accumulator = PopulationAccumulator::from_warriors(&warriors);
+    
+    // Replacing a warrior's values swaps its contribution
+    warriors[3].fitness_score = 20.0;
+    warriors[3].energy = 50.0;
+    accumulator.update(&warriors[3]);
+    assert_eq!(accumulator.count(), warriors.len());
+    assert_eq!(accumulator.max_fitness(), 20.0);
+    assert_matches_recompute(&accumulator, &warriors);
+    
+    let fittest = warriors.remove(3);
+    accumulator.remove(fittest.id);
+    assert_eq!(accumulator.max_fitness(), 13.5);
+    let deepest = warriors.iter().position(|w| w.lineage_depth == 4).unwrap();
+    accumulator.remove(warriors.remove(deepest).id);
+    assert_matches_recompute(&accumulator, &warriors);
+    
+    for warrior in warriors.drain(..) {
+        accumulator.remove(warrior.id);
+    }
+    assert_eq!(accumulator, PopulationAccumulator::new());
+}
+
+fn assert_matches_recompute(accumulator: &PopulationAccumulator, warriors: &[NeuralWarrior]) {
+    let recomputed = PopulationAccumulator::from_warriors(warriors);
+    let close = |a: f32, b: f32| (a - b).abs() <= 1e-3 * b.abs().max(1.0);
+    assert_eq!(accumulator.count(), recomputed.count());
+    assert_eq!(accumulator.max_fitness(), recomputed.max_fitness());
+    assert_eq!(accumulator.max_lineage_depth(), recomputed.max_lineage_depth());
+    assert_eq!(accumulator.average_age(), recomputed.average_age());
+    assert_eq!(accumulator.mean_genome_size(), recomputed.mean_genome_size());
+    assert!(close(accumulator.average_fitness(), recomputed.average_fitness()));
+    assert!(close(accumulator.fitness_std_dev(), recomputed.fitness_std_dev()));
+    assert!(close(accumulator.average_energy(), recomputed.average_energy()));
+}
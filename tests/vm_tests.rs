@@ -1,5 +1,7 @@
-use neural_network_arena::vm::{Instruction, OpCode, VirtualMachine};
-use std::collections::HashMap;
+use neural_network_arena::environment::Environment;
+use neural_network_arena::neural::{Genome, NeuralWarrior};
+use neural_network_arena::vm::{Instruction, OpCode, VirtualMachine, VmError};
+use neural_network_arena::testing::{fixed_rng, random_position};
 
 #[test]
 fn test_vm_creation() {
@@ -92,7 +94,7 @@ fn test_memory_territory_allocation() {
     assert_eq!(vm.territory_owner(territory_id).unwrap(), 0);
     
     // Test memory access within territory bounds
-    let territory_start = vm.territory_start_address(territory_id).unwrap();
+    let _territory_start = vm.territory_start_address(territory_id).unwrap();
     vm.write_territory_memory(territory_id, 0, 42.0).unwrap();
     assert_eq!(vm.read_territory_memory(territory_id, 0).unwrap(), 42.0);
     
@@ -120,3 +122,95 @@ fn test_memory_territory_isolation() {
     // Cross-territory access should be denied
     assert!(vm.cross_territory_access_denied(territory1, territory2));
 }
+
+#[test]
+fn test_disabled_opcode_is_rejected() {
+    let mut rng = fixed_rng(1);
+    let mut vm = VirtualMachine::new(1024);
+    vm.set_enabled_opcodes(OpCode::ALL.into_iter().filter(|&opcode| opcode != OpCode::Mutate).collect());
+    let mutate = Instruction::new(OpCode::Mutate, 0, 1, 0.1);
+    
+    assert!(matches!(vm.execute_instruction(&mutate), Err(VmError::OpcodeDisabled { opcode: OpCode::Mutate })));
+    assert!(matches!(vm.load_program(0, vec![mutate]), Err(VmError::OpcodeDisabled { .. })));
+    assert_eq!(vm.cycle_count(), 0);
+    
+    assert!(vm.execute_instruction(&Instruction::new(OpCode::Activate, 0, 1, 0.0)).is_ok());
+    
+    // Warriors don't generate instructions the VM would reject
+    vm.set_enabled_opcodes(vec![OpCode::Replicate]);
+    let warrior = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    let environment = Environment::new(1000.0, 1000.0, 10);
+    let program = warrior.execute_vm_instructions(&mut vm, &environment.get_environment_state()).unwrap();
+    assert!(!program.is_empty());
+    assert!(program.iter().all(|instruction| instruction.opcode == OpCode::Replicate));
+}
+
+#[test]
+fn test_vm_snapshot_round_trip() {
+    let mut vm = VirtualMachine::new(4096);
+    let territory = vm.allocate_territory(0, 64).unwrap();
+    vm.write_territory_memory(territory, 3, 0.5).unwrap();
+    // Programs take turns, out of step, sensing the shrinking resource pool
+    // into one shared cell and copying it out, so the restored VM only
+    // matches if it runs the programs in the same order
+    for id in 0..3 {
+        let program = (0..250).map(|step| match (step + id) % 2 {
+            0 => Instruction::new(OpCode::Sense, 0, 100, 0.0),
+            _ => Instruction::new(OpCode::Replicate, 100, 110 + id, 0.0),
+        }).collect();
+        vm.load_program(id, program).unwrap();
+    }
+    // Random sensor reads and mutations continue from the saved RNG state
+    let random = (0..250).map(|step| match step % 2 {
+        0 => Instruction::new(OpCode::Sense, 2, 120, 0.0),
+        _ => Instruction::new(OpCode::Mutate, 120, 121 + step % 5, 0.5),
+    }).collect();
+    vm.load_program(3, random).unwrap();
+    for _ in 0..100 {
+        vm.execute_round_robin_cycle().unwrap();
+    }
+    
+    let full = vm.snapshot(false).unwrap();
+    let compact = vm.snapshot(true).unwrap();
+    // Dense memory spends at least "0.0," on each of the ~4000 zero cells
+    assert!(compact.len() + 4 * 4000 < full.len());
+    
+    for json in [full, compact] {
+        let mut original = vm.clone();
+        let mut restored = VirtualMachine::restore(&json).unwrap();
+        assert_eq!(restored.memory(), original.memory());
+        assert_eq!(restored.read_territory_memory(territory, 3).unwrap(), 0.5);
+        for _ in 0..100 {
+            original.execute_round_robin_cycle().unwrap();
+            restored.execute_round_robin_cycle().unwrap();
+        }
+        assert_eq!(restored.memory(), original.memory());
+        assert_eq!(restored.cycle_count(), original.cycle_count());
+        assert_eq!(restored.available_resources(), original.available_resources());
+        for id in 0..4 {
+            assert_eq!(restored.program_counter(id), original.program_counter(id));
+        }
+    }
+}
+
+#[test]
+fn test_vm_restore_rejects_inconsistent_snapshots() {
+    let mut vm = VirtualMachine::new(128);
+    vm.load_program(0, vec![Instruction::new(OpCode::Noop, 0, 0, 0.0)]).unwrap();
+    vm.allocate_territory(0, 32).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&vm.snapshot(true).unwrap()).unwrap();
+    
+    let mut bad_counter = value.clone();
+    bad_counter["program_counters"]["0"] = 5.into();
+    assert!(matches!(
+        VirtualMachine::restore(&bad_counter.to_string()),
+        Err(VmError::ProgramCounterOutOfRange { id: 0, counter: 5, len: 1 })
+    ));
+    
+    let mut bad_territory = value;
+    bad_territory["territories"]["0"]["start_address"] = 120.into();
+    assert!(matches!(
+        VirtualMachine::restore(&bad_territory.to_string()),
+        Err(VmError::TerritoryOutOfRange { id: 0, end: 152, size: 128, .. })
+    ));
+}
@@ -0,0 +1,178 @@
+use neural_network_arena::neural::{Action, ActionKind, Genome, GenomeCodecKind, MutationWeights, NeuralWarrior};
+use neural_network_arena::testing::{fixed_rng, random_position};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::mem::discriminant;
+
+#[test]
+fn test_zero_temperature_is_deterministic_argmax() {
+    let mut rng = fixed_rng(1);
+    let warrior = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    let outputs = [0.1, 0.9, 0.3, 0.2];
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for _ in 0..100 {
+        let action = warrior.interpret_neural_output(&outputs, 0.0, &mut rng);
+        assert!(matches!(action, Action::Attack { .. }), "Expected argmax Attack, got {:?}", action);
+    }
+}
+
+#[test]
+fn test_zero_temperature_ties_go_to_the_last_output() {
+    let mut rng = fixed_rng(8);
+    let warrior = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    
+    let action = warrior.interpret_neural_output(&[0.1, 0.7, 0.7, 0.2, 0.0, 0.0], 0.0, &mut rng);
+    assert!(matches!(action, Action::Defend { .. }), "Expected Defend, got {:?}", action);
+    // A blank network's signed zeros tie too
+    let action = warrior.interpret_neural_output(&[0.0, 0.0, 0.0, 0.0, 0.0, -0.0], 0.0, &mut rng);
+    assert_eq!(action, Action::DeepRest);
+}
+
+#[test]
+fn test_high_temperature_samples_distribution() {
+    let mut rng = fixed_rng(2);
+    let warrior = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    let outputs = [0.1, 0.9, 0.3, 0.2];
+    let mut rng = StdRng::seed_from_u64(7);
+
+    let mut seen = Vec::new();
+    for _ in 0..500 {
+        let action = warrior.interpret_neural_output(&outputs, 5.0, &mut rng);
+        if !seen.iter().any(|a: &Action| discriminant(a) == discriminant(&action)) {
+            seen.push(action);
+        }
+    }
+
+    assert!(seen.len() >= 3, "High temperature should explore several actions, saw {:?}", seen);
+}
+
+#[test]
+fn test_action_frequencies_match_history() {
+    let mut rng = fixed_rng(3);
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    warrior.set_action_history_capacity(8);
+    
+    for _ in 0..4 {
+        warrior.record_action(Action::Rest);
+    }
+    for _ in 0..3 {
+        warrior.record_action(Action::Move { direction: 0.0, intensity: 1.0 });
+    }
+    warrior.record_action(Action::Defend { shield_strength: 0.5 });
+    
+    let frequencies = warrior.action_frequencies();
+    assert!((frequencies.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+    assert_eq!(frequencies[ActionKind::Rest as usize], 0.5);
+    assert_eq!(frequencies[ActionKind::Move as usize], 0.375);
+    assert_eq!(frequencies[ActionKind::Defend as usize], 0.125);
+    assert_eq!(frequencies[ActionKind::Attack as usize], 0.0);
+}
+
+#[test]
+fn test_action_history_capacity_bounds_history() {
+    let mut rng = fixed_rng(4);
+    let mut warrior = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    assert_eq!(warrior.action_frequencies(), [0.0; ActionKind::ALL.len()]);
+    
+    warrior.set_action_history_capacity(3);
+    for _ in 0..10 {
+        warrior.record_action(Action::Rest);
+    }
+    warrior.record_action(Action::Sense { sensor_type: neural_network_arena::neural::warrior::SensorType::Energy });
+    assert_eq!(warrior.action_history.len(), 3);
+    assert!(matches!(warrior.action_history.back(), Some(Action::Sense { .. })));
+    
+    warrior.set_action_history_capacity(0);
+    warrior.record_action(Action::Rest);
+    assert!(warrior.action_history.is_empty());
+}
+
+#[test]
+fn test_names_are_deterministic_and_inherited() {
+    let mut rng = fixed_rng(5);
+    let founder = NeuralWarrior::with_position(Genome::new_random(), 42, random_position((1000.0, 1000.0), &mut rng));
+    assert_eq!(founder.name, NeuralWarrior::with_position(Genome::new_random(), 42, random_position((1000.0, 1000.0), &mut rng)).name);
+    assert_ne!(founder.name, NeuralWarrior::with_position(Genome::new_random(), 43, random_position((1000.0, 1000.0), &mut rng)).name);
+    
+    let partner = NeuralWarrior::with_position(Genome::new_random(), 7, random_position((1000.0, 1000.0), &mut rng));
+    let child = NeuralWarrior::from_parents_with_rng(&founder, &partner, 100, &mut StdRng::seed_from_u64(1));
+    assert_eq!(child.surname(), founder.surname());
+    assert!(child.name.ends_with("-G1"), "Unexpected child name {}", child.name);
+    
+    let grandchild = NeuralWarrior::from_parents_with_rng(&child, &partner, 101, &mut StdRng::seed_from_u64(2));
+    assert_eq!(grandchild.surname(), founder.surname());
+    assert!(grandchild.name.ends_with("-G2"));
+    
+    // A renamed warrior founds a new dynasty
+    let mut renamed = founder.clone();
+    renamed.name = "Hercules".to_string();
+    let heir = NeuralWarrior::from_parents_with_rng(&renamed, &partner, 102, &mut StdRng::seed_from_u64(3));
+    assert_eq!(heir.surname(), "Hercules");
+}
+
+#[test]
+fn test_action_parameters_come_from_dedicated_outputs() {
+    let mut rng = fixed_rng(6);
+    let warrior = NeuralWarrior::with_position(Genome::new_random(), 1, random_position((1000.0, 1000.0), &mut rng));
+    assert_eq!(warrior.network.layer_sizes().last(), Some(&Action::OUTPUT_SIZE));
+    let mut rng = StdRng::seed_from_u64(7);
+    
+    let mut outputs = vec![0.0; Action::OUTPUT_SIZE];
+    outputs[1] = 0.9; // Attack wins the selection
+    outputs[Action::ATTACK_DIRECTION_OUTPUT] = 0.25;
+    outputs[Action::ATTACK_STRENGTH_OUTPUT] = -0.3;
+    match warrior.interpret_neural_output(&outputs, 0.0, &mut rng) {
+        Action::Attack { target_direction, strength } => {
+            assert!((target_direction - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+            assert!((strength - 0.3).abs() < 1e-6);
+        },
+        other => panic!("Expected Attack, got {:?}", other),
+    }
+    
+    outputs[1] = 0.0;
+    outputs[2] = 0.9;
+    outputs[Action::SHIELD_STRENGTH_OUTPUT] = 0.2;
+    assert_eq!(
+        warrior.interpret_neural_output(&outputs, 0.0, &mut rng),
+        Action::Defend { shield_strength: 0.2 }
+    );
+}
+
+#[test]
+fn test_spawn_child_network_reflects_mutated_genome() {
+    let mut rng = StdRng::seed_from_u64(21);
+    let mut genome = Genome::random_with_rng(&mut rng);
+    genome.set_codec(GenomeCodecKind::Direct);
+    let mut parent = NeuralWarrior::with_position(genome, 4, random_position((1000.0, 1000.0), &mut rng));
+    parent.age = 30;
+    parent.fitness_score = 12.0;
+    parent.record_action(Action::Rest);
+
+    let child = parent.spawn_child(9, 1.0, &mut rng);
+
+    assert_ne!(child.genome.bytes(), parent.genome.bytes());
+    let rebuilt = child.genome.to_network().export_json().unwrap();
+    assert_eq!(child.network.export_json().unwrap(), rebuilt);
+    assert_ne!(child.network.export_json().unwrap(), parent.network.export_json().unwrap());
+
+    assert_eq!(child.id, 9);
+    assert_eq!(child.parent_ids, vec![4]);
+    assert_eq!(child.lineage_depth, parent.lineage_depth + 1);
+    assert_eq!(child.age, 0);
+    assert_eq!(child.fitness_score, 0.0);
+    assert!(child.action_history.is_empty());
+}
+
+#[test]
+fn test_spawn_child_with_operators_applies_deletion_before_rebuilding() {
+    let mut rng = StdRng::seed_from_u64(22);
+    let mut genome = Genome::random_with_rng(&mut rng);
+    genome.set_codec(GenomeCodecKind::Direct);
+    let parent = NeuralWarrior::with_position(genome, 4, random_position((1000.0, 1000.0), &mut rng));
+    
+    let child = parent.spawn_child_with_operators(9, 0.0, 1.0, &MutationWeights::default(), &mut rng);
+    
+    assert_eq!(child.genome.size(), parent.genome.size() - 1);
+    assert_eq!(child.network.export_json().unwrap(), child.genome.to_network().export_json().unwrap());
+}